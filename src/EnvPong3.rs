@@ -1,7 +1,7 @@
 // patham9 comptible pong
 
 use rand::Rng;
-use rand::rngs::ThreadRng;
+use rand::rngs::StdRng;
 
 pub struct EnvState {
     pub szX:i64,
@@ -49,7 +49,7 @@ pub fn makeEnvState() -> EnvState {
 }
 
 
-pub fn simStep(env:&mut EnvState, rng:&mut ThreadRng) {
+pub fn simStep(env:&mut EnvState, rng:&mut StdRng) {
     env.t+=1;
 
     /* commented because we don't visualize!
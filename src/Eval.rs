@@ -1,18 +1,16 @@
 use std::sync::{Arc, Mutex};
 use parking_lot::RwLock;
 
-use crate::Term::{Term, convTermToStr};
 use crate::Nar::*;
-use crate::NarWorkingCycle::QHandler;
+use crate::NarObserver::NarObserver;
 use crate::NarSentence::Sentence;
-use crate::NarSentence::convSentenceTermPunctToStr;
 
 
 // evaluate how good NARS is with the narsese-program
 pub fn run(nar:&mut Nar)->Option<i64> {
     let global:Arc<Mutex<Global>> = Arc::new(Mutex::new(Global{foundAnswer:false,}));
     
-    nar.mem.read().globalQaHandlers.write().push(Arc::new(RwLock::new(QHandlerImpl{global:Arc::clone(&global)}))); // register Q&A handler to send answers to all clients
+    addObserver(nar, Arc::new(RwLock::new(ObserverImpl{global:Arc::clone(&global)}))); // register observer to get notified about answers
 
     let mut maxCycles = 180;
     for iCycle in 0..maxCycles {
@@ -37,16 +35,13 @@ pub struct Global {
 }
 
 
-/// handler to send answer to clients
-pub struct QHandlerImpl {
+/// observer to remember if a answer was found
+pub struct ObserverImpl {
     pub global:Arc<Mutex<Global>>,
 }
 
-impl QHandler for QHandlerImpl {
-    fn answer(&mut self, question:&Term, answer:&Sentence) {
-        // print question and send answer
-        let msg = "TRACE answer: ".to_owned() + &convTermToStr(&question) + "? " + &convSentenceTermPunctToStr(&answer, true);
-
+impl NarObserver for ObserverImpl {
+    fn answered(&mut self, _question:&Sentence, _answer:&Sentence) {
         let mut data = self.global.lock().unwrap(); // unwrap to panic when it can't unlock
         data.foundAnswer = true;
     }
@@ -1,19 +1,25 @@
 //! Non-Axiomatic Reasoner
 //! is exposing a NAR as one "unit" which can be instantiated
 
+use std::fmt;
 use std::rc::Rc;
-use std::sync::{Arc};
+use std::sync::{Arc, Weak};
 use std::sync::atomic::{AtomicI64, Ordering};
 use parking_lot::RwLock;
 
 use crate::Tv::*;
 use crate::Term::*;
-use crate::NarseseParser::parseNarsese;
+use crate::NarseseParser::{parseNarsese2, NarseseError};
 use crate::NarSentence::*;
 use crate::NarStamp::*;
 use crate::NarWorkingCycle::*;
 use crate::NarProc;
 use crate::NarGoalSystem;
+use crate::NarObserver::{NarObserver, notify};
+use crate::NarConfig::*;
+use crate::NarPersist;
+use crate::NarPersist::NarPersistError;
+use crate::NarRule::{Rule, PatternRule, RuleStats, RuleParseError, ruleTableAdd, ruleTableSetEnabled, ruleTableRetStats, loadRuleFile};
 
 
 /// single Non-Axiomatic Reasoner
@@ -30,62 +36,380 @@ pub struct Nar {
     /// used to track "Usage" by competing for recent accessed beliefs
     pub currentTime: AtomicI64,
 
-    /// verbosity of input
-    pub cfgVerbosityInput:i32,
+    /// configuration, change it with setConfigValue() to apply it to the running NAR
+    pub cfg: NarConfig,
+}
+
+/// error which describes why input was rejected
+// PUBLICAPI
+#[derive(Clone, Debug, PartialEq)]
+pub enum NarInputError {
+    Narsese(NarseseError), // narsese couldn't get parsed
+    BadConfidence(f64), // confidence isn't in [0, 1), the amount of evidence of confidence 1.0 is infinite
+    BadExpDt(i64), // expDt isn't a index into the table of the exponential intervals
+    NoEvent, // sequence of =/> contains only intervals
+}
+
+impl fmt::Display for NarInputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NarInputError::Narsese(err) => write!(f, "{}", err),
+            NarInputError::BadConfidence(c) => write!(f, "confidence {} must be in [0, 1)", c),
+            NarInputError::BadExpDt(expDt) => write!(f, "expDt {} isn't a index into the table of the exponential intervals", expDt),
+            NarInputError::NoEvent => write!(f, "sequence of =/> must contain a event"),
+        }
+    }
+}
 
-    /// number of beliefs of concept
-    pub cfg__nConceptBeliefs: usize,
+impl std::error::Error for NarInputError {}
 
-    pub cfg__maxComplexity: i64,
+impl From<NarseseError> for NarInputError {
+    fn from(err: NarseseError) -> NarInputError {
+        NarInputError::Narsese(err)
+    }
 }
 
 /// creates a new NAR with a default configuration
 // PUBLICAPI
 pub fn createNar() -> Nar {
-    let cfg__nConceptBeliefs = 20;
-    let cfg__maxComplexity = 64;
+    createNarWithConfig(NarConfig::default())
+}
 
-    Nar{
-        procNar:NarProc::narInit(),
-        mem:createMem2(cfg__maxComplexity, cfg__nConceptBeliefs),
+/// creates a new NAR with a configuration
+///
+/// the configuration should be validated, for example by building it with NarConfigBuilder
+// PUBLICAPI
+pub fn createNarWithConfig(cfg:NarConfig) -> Nar {
+    let mut nar = Nar{
+        procNar:NarProc::narInit2(&cfg),
+        mem:createMem2(&cfg),
         currentTime:AtomicI64::new(0),
-        cfgVerbosityInput:0, // enable verbose input by default
-        cfg__nConceptBeliefs:cfg__nConceptBeliefs,
-        cfg__maxComplexity: cfg__maxComplexity,        
+        cfg:cfg,
+    };
+    nar.procNar.observers = Arc::clone(&nar.mem.read().observers); // declarative and procedural reasoner share the observers
+    nar.mem.read().shared.write().procMem = Arc::clone(&nar.procNar.evidenceMem); // temporal questions are answered with the procedural knowledge
+    nar
+}
+
+/// changes a value of the configuration of a running NAR
+///
+/// the configuration isn't changed if the key can only get set at creation or if the value is invalid
+// PUBLICAPI
+pub fn setConfigValue(nar:&mut Nar, key:&str, value:&str) -> Result<(), NarConfigError> {
+    let mut cfg = nar.cfg.clone();
+    setNarConfigValue(&mut cfg, key, value)?;
+    if !isRuntimeKey(key) {
+        return Err(NarConfigError{kind:EnumNarConfigErrorKind::NOT_RUNTIME, key:key.to_string(), msg:"can only get set at creation".to_string(), line:None});
+    }
+    validateNarConfig(&cfg)?;
+    nar.cfg = cfg;
+
+    // apply to the running reasoners
+    nar.mem.write().cfg = nar.cfg.clone();
+    NarProc::applyConfig(&mut nar.procNar, &nar.cfg);
+    Ok(())
+}
+
+/// registers a observer which gets notified about events of the NAR
+// PUBLICAPI
+pub fn addObserver(nar:&Nar, observer:Arc<RwLock<dyn NarObserver>>) {
+    nar.mem.read().observers.write().push(observer);
+}
+
+/// adds a rule to the rules of the declarative reasoner, a rule with the same name is replaced
+// PUBLICAPI
+pub fn addRule(nar:&Nar, rule:Arc<dyn Rule>) {
+    ruleTableAdd(&mut nar.mem.read().shared.read().rules.write(), rule);
+}
+
+/// enables or disables a rule of the declarative reasoner
+///
+/// returns false if the rule isn't known
+// PUBLICAPI
+pub fn setRuleEnabled(nar:&Nar, name:&str, enabled:bool) -> bool {
+    ruleTableSetEnabled(&mut nar.mem.read().shared.read().rules.write(), name, enabled)
+}
+
+/// loads declarative rules from a rule file and adds them to the rules of the declarative reasoner
+///
+/// returns the number of loaded rules, no rule is added if the file has a error
+// PUBLICAPI
+pub fn loadRules(nar:&Nar, path:&String) -> Result<usize, RuleParseError> {
+    let rules:Vec<PatternRule> = loadRuleFile(path)?;
+    let nRules:usize = rules.len();
+    for iRule in rules {
+        addRule(nar, Arc::new(iRule));
+    }
+    Ok(nRules)
+}
+
+/// returns the statistics of the rules of the declarative reasoner
+// PUBLICAPI
+pub fn retRuleStats(nar:&Nar) -> Vec<RuleStats> {
+    ruleTableRetStats(&nar.mem.read().shared.read().rules.read())
+}
+
+/// saves the knowledge, goals, op names and counters of the NAR to a SQLite database
+// PUBLICAPI
+pub fn save(nar:&Nar, path:&String) -> Result<(), NarPersistError> {
+    NarPersist::save(nar, path)
+}
+
+/// loads the knowledge, goals, op names and counters of the NAR from a SQLite database which was written with save()
+///
+/// ops should be registered before loading, ops which aren't registered are registered as NOP ops
+// PUBLICAPI
+pub fn load(nar:&mut Nar, path:&String) -> Result<(), NarPersistError> {
+    NarPersist::load(nar, path)
+}
+
+/// answer to a question which was asked with ask() or askStream()
+// PUBLICAPI
+#[derive(Clone)]
+pub struct Answer {
+    pub sentence:Sentence,
+    pub tv:Tv,
+    pub stamp:Stamp,
+    /// value of the cycle counter when the answer was found
+    pub cycle:i64,
+}
+
+/// asks a question and gives the NAR up to maxCycles cycles to answer it
+///
+/// stops early when a answer has at least the expectation askExpThreshold of the configuration
+/// returns the best answer which was found, the question is removed afterwards
+// PUBLICAPI
+pub fn ask(nar:&mut Nar, question:&Term, maxCycles:i64) -> Option<Answer> {
+    let expThreshold:f64 = nar.cfg.askExpThreshold;
+    ask2(nar, question, maxCycles, expThreshold)
+}
+
+/// asks a question and gives the NAR up to maxCycles cycles to answer it
+///
+/// /param expThreshold the NAR isn't cycled anymore when a answer has at least this expectation
+// PUBLICAPI
+pub fn ask2(nar:&mut Nar, question:&Term, maxCycles:i64, expThreshold:f64) -> Option<Answer> {
+    retGoodAnswer(askStream(nar, question, maxCycles), expThreshold)
+}
+
+/// returns the first answer with at least the expectation or the best answer
+fn retGoodAnswer(stream:AskStream, expThreshold:f64) -> Option<Answer> {
+    let mut best:Option<Answer> = None;
+    for iAnswer in stream { // answers get better
+        let isGood:bool = calcExp(&iAnswer.tv) >= expThreshold;
+        best = Some(iAnswer);
+        if isGood {
+            break;
+        }
+    }
+    best
+}
+
+/// asks a question and returns a iterator over the answers, every answer is better than the previous one
+///
+/// the NAR is cycled while the iterator is advanced, up to maxCycles cycles in total
+/// the question is removed when the iterator is dropped
+// PUBLICAPI
+pub fn askStream<'a>(nar:&'a mut Nar, question:&Term, maxCycles:i64) -> AskStream<'a> {
+    askStream2(nar, question, EnumPunctation::QUESTION, maxCycles)
+}
+
+/// asks a quest, which is a question about how much the NAR wants something, and gives the NAR up to maxCycles cycles to answer it
+///
+/// returns the goal with the highest desire which was found, the quest is removed afterwards
+// PUBLICAPI
+pub fn askQuest(nar:&mut Nar, quest:&Term, maxCycles:i64) -> Option<Answer> {
+    let expThreshold:f64 = nar.cfg.askExpThreshold;
+    retGoodAnswer(askStream2(nar, quest, EnumPunctation::QUEST, maxCycles), expThreshold)
+}
+
+/// asks a question or quest and returns a iterator over the answers
+///
+/// /param punct is QUESTION or QUEST
+// PUBLICAPI
+pub fn askStream2<'a>(nar:&'a mut Nar, question:&Term, punct:EnumPunctation, maxCycles:i64) -> AskStream<'a> {
+    let stampId:i64 = nar.mem.read().shared.read().stampIdCounter.fetch_add(1, Ordering::SeqCst);
+    let sentence = newEternalSentenceByTv(&normalize(question), punct, &Tv{f:1.0,c:0.0}, newStamp(&vec![stampId]));
+
+    let handler:Arc<RwLock<AskHandler>> = Arc::new(RwLock::new(AskHandler{answers:vec![], shared:Arc::downgrade(&nar.mem.read().shared)}));
+    memAddQuestionTask(&nar.mem.read().shared, &sentence, 1.0, Some(Arc::clone(&handler) as Arc<RwLock<dyn QHandler>>));
+
+    AskStream{nar:nar, handler:handler, remainingCycles:maxCycles}
+}
+
+/// asks a question with query variables and gives the NAR up to maxCycles cycles to answer it
+///
+/// returns the best answer for each distinct binding of the query variables, ordered by expectation
+// PUBLICAPI
+pub fn askBindings(nar:&mut Nar, question:&Term, maxCycles:i64) -> Vec<QaBinding> {
+    let mut stream = askStream(nar, question, maxCycles);
+    for _iAnswer in &mut stream {} // give the NAR the cycles to find answers
+
+    // read bindings of question before the question is removed
+    let handlerPtr = Arc::as_ptr(&stream.handler) as *const ();
+    let memGuard = stream.nar.mem.read();
+    let sharedGuard = memGuard.shared.read();
+    let questionTasksGuard = sharedGuard.questionTasks.read();
+    for iTask in questionTasksGuard.iter() {
+        if let Some(iHandler) = &iTask.handler {
+            if Arc::as_ptr(iHandler) as *const () == handlerPtr {
+                return iTask.bindings.clone();
+            }
+        }
+    }
+    vec![] // question was removed by someone else
+}
+
+/// collects the answers to one question
+struct AskHandler {
+    /// answers with the value of the cycle counter when they were found
+    answers:Vec<(Sentence, i64)>,
+    /// is weak because the question task which owns the handler is stored in it
+    shared:Weak<RwLock<DeclarativeShared>>,
+}
+
+impl QHandler for AskHandler {
+    fn answer(&mut self, _question:&Term, answer:&Sentence) {
+        let cycleCounter:i64 = self.shared.upgrade().map_or(0, |shared| shared.read().cycleCounter.load(Ordering::SeqCst));
+        self.answers.push((answer.clone(), cycleCounter));
+    }
+}
+
+/// iterator over the answers to a question, is returned by askStream()
+// PUBLICAPI
+pub struct AskStream<'a> {
+    nar:&'a mut Nar,
+    handler:Arc<RwLock<AskHandler>>,
+    remainingCycles:i64,
+}
+
+impl<'a> Iterator for AskStream<'a> {
+    type Item = Answer;
+
+    fn next(&mut self) -> Option<Answer> {
+        loop {
+            // return answers which were already found first
+            let found:Option<(Sentence, i64)> = {
+                let mut handlerGuard = self.handler.write();
+                if handlerGuard.answers.len() > 0 {Some(handlerGuard.answers.remove(0))} else {None}
+            };
+            if let Some((sentence, cycleCounter)) = found {
+                return Some(Answer{tv:retTv(&sentence).unwrap(), stamp:sentence.stamp.clone(), sentence:sentence, cycle:cycleCounter});
+            }
+
+            if self.remainingCycles <= 0 {
+                return None; // budget is exhausted
+            }
+            self.remainingCycles -= 1;
+            cycle(self.nar);
+        }
+    }
+}
+
+impl<'a> Drop for AskStream<'a> {
+    fn drop(&mut self) {
+        // remove the question, so the handler only lives as long as the question was asked
+        let handlerPtr = Arc::as_ptr(&self.handler) as *const ();
+        let memGuard = self.nar.mem.read();
+        let sharedGuard = memGuard.shared.read();
+        sharedGuard.questionTasks.write().retain(|iTask| match &iTask.handler {
+            Some(iHandler) => Arc::as_ptr(iHandler) as *const () != handlerPtr,
+            None => true
+        });
     }
 }
 
 /// input for eternal
 // PUBLICAPI
-pub fn inputT(nar:&mut Nar, term:&Term, punct:EnumPunctation, tv:&Tv) {
-    inputT2(nar, term, punct, tv, false);
+pub fn inputT(nar:&mut Nar, term:&Term, punct:EnumPunctation, tv:&Tv) -> Result<(), NarInputError> {
+    inputT2(nar, term, punct, tv, false)
 }
 
 /// input for event or eternal
 // PUBLICAPI
-pub fn inputT2(nar:&mut Nar, term:&Term, punct:EnumPunctation, tv:&Tv, isEvent:bool) {
+pub fn inputT2(nar:&mut Nar, term:&Term, punct:EnumPunctation, tv:&Tv, isEvent:bool) -> Result<(), NarInputError> {
+    let tense = if isEvent {EnumTense::PRESENT} else {EnumTense::ETERNAL};
+    inputT3(nar, term, punct, tv, tense)
+}
+
+/// input with tense
+// PUBLICAPI
+pub fn inputT3(nar:&mut Nar, term:&Term, punct:EnumPunctation, tv:&Tv, tense:EnumTense) -> Result<(), NarInputError> {
+    inputT4(nar, term, punct, tv, tense, None)
+}
+
+/// input with tense and budget
+///
+/// priority of the budget is the credit of judgements, the priority of questions and the desirability of goals,
+/// durability is the multiplier of the credit of judgements,
+/// quality is the fraction of the credit of judgements which doesn't decay
+// PUBLICAPI
+pub fn inputT4(nar:&mut Nar, term:&Term, punct:EnumPunctation, tv:&Tv, tense:EnumTense, budget:Option<Budget>) -> Result<(), NarInputError> {
+    inputT5(nar, term, punct, tv, tense, budget, None)
+}
+
+/// input with tense, budget and explicit exponential time delta of =/>
+///
+/// /param expDt index into the table of the exponential intervals of the procedural reasoner, overrides the interval at the end of the sequence of =/>
+///
+/// returns error if the input can't be stored, the input is dropped in this case
+// PUBLICAPI
+pub fn inputT5(nar:&mut Nar, term:&Term, punct:EnumPunctation, tv:&Tv, tense:EnumTense, budget:Option<Budget>, expDt:Option<i64>) -> Result<(), NarInputError> {
+    let budget:Budget = budget.unwrap_or(Budget{p:1.0,d:1.0,q:0.0});
+    let term:&Term = &normalize(term); // bring into canonical form so equal terms end up in the same concept
+
+    // reject input which can't be stored before it is reported as accepted
+    if !(tv.c >= 0.0 && tv.c < 1.0) {
+        return Err(NarInputError::BadConfidence(tv.c)); // amount of evidence would be infinite, revision would break
+    }
+    let isPredImplJudgement:bool = punct == EnumPunctation::JUGEMENT && match term {Term::Stmt(Copula::PREDIMPL, _, _) => true, _ => false};
+    if isPredImplJudgement && stripIntervalsOfPredImpl(term).is_none() {
+        return Err(NarInputError::NoEvent);
+    }
+    if let Some(expDt) = expDt {
+        if expDt < 0 || expDt as usize >= nar.procNar.expIntervalsTable.len() {
+            return Err(NarInputError::BadExpDt(expDt));
+        }
+    }
+
     let stampId:i64 = nar.mem.read().shared.read().stampIdCounter.fetch_add(1, Ordering::SeqCst); // TODO< is this ordering ok? >
     let stamp = newStamp(&vec![stampId]);
     let mut sentence = newEternalSentenceByTv(&term,punct,&tv,stamp);
 
-    if nar.cfgVerbosityInput >= 1 {
+    // compute time of occurence
+    let occT:Option<i64> = match tense {
+        EnumTense::ETERNAL => None,
+        EnumTense::PRESENT => Some(nar.procNar.t),
+        EnumTense::FUTURE => Some(nar.procNar.t + nar.cfg.tenseDuration),
+        EnumTense::PAST => Some(nar.procNar.t - nar.cfg.tenseDuration),
+        EnumTense::ABSOLUTE(t) => Some(t),
+    };
+    sentence.t = occT;
+
+    if nar.cfg.verbosityInput >= 1 {
         println!("[v] input {}", convSentenceTermPunctToStr(&sentence, true));
     }
+    notify(&nar.procNar.observers, |o| o.inputAccepted(&sentence));
 
-    if isEvent {
+    if punct == EnumPunctation::QUEST { // quests are answered by the goals of the declarative and procedural reasoner
+        memAddQuestionTask(&nar.mem.read().shared, &sentence, budget.p, None);
+        return Ok(());
+    }
+
+    if let Some(occT) = occT {
         if punct == EnumPunctation::GOAL {
             // add to goals
-            NarGoalSystem::addEntry(&mut nar.procNar.goalSystem, &nar.mem.read(), nar.procNar.t, Arc::new(sentence), None, 0);
+            NarGoalSystem::addEntry3(&mut nar.procNar.goalSystem, &nar.mem.read(), nar.procNar.t, Arc::new(sentence), None, 0, budget.p as f32);
         }
         else if punct == EnumPunctation::QUESTION {
-            memAddTask(Arc::clone(&nar.mem.read().shared), &sentence, true, nar.cfg__maxComplexity, nar.cfg__nConceptBeliefs, 1.0, nar.procNar.t);
+            memAddTask2(Arc::clone(&nar.mem.read().shared), &sentence, true, nar.cfg.maxComplexity, nar.cfg.nConceptBeliefs, budget.p, budget.d, budget.q, budget.p, nar.procNar.t);
         }
         else {
             // add event
-            nar.procNar.trace.event_happened( Rc::new(NarProc::SimpleSentence {name:term.clone(),evi:nar.procNar.t,occT:nar.procNar.t}) );
+            nar.procNar.trace.event_happened( Rc::new(NarProc::SimpleSentence {name:term.clone(),evi:nar.procNar.t,occT:occT}) );
         }
 
-        return;
+        return Ok(());
     }
 
     // compute if the term is a temporal term
@@ -96,15 +420,16 @@ pub fn inputT2(nar:&mut Nar, term:&Term, punct:EnumPunctation, tv:&Tv, isEvent:b
 
     if isTemporal {
         if punct == EnumPunctation::QUESTION { // temporal Q&A
-            // quesion was already added
-            //memAddTask(Arc::clone(&nar.mem.read().shared), &sentence, true, nar.cfg__maxComplexity, nar.cfg__nConceptBeliefs, 1.0, nar.procNar.t);
+            memAddQuestionTask(&nar.mem.read().shared, &sentence, budget.p, None); // is answered with the procedural knowledge
         }
         else if punct == EnumPunctation::JUGEMENT {
             // add to temporal knowledge
-            sentence.evi = Some(Evidence::CNT{pos:10,cnt:10}); // we need to transcribe TV
-                                                            // TODO< transcribe TV in a better way, we need to approximate freq and conf! >
+            let (implSeq, dt) = stripIntervalsOfPredImpl(term).unwrap(); // was checked before
+            sentence.term = Arc::new(implSeq);
+            sentence.expDt = expDt.or(dt.map(|dt| NarProc::findMinTableIdx(dt, &nar.procNar.expIntervalsTable)));
+            sentence.evi = Some(convTvToCnt(tv)); // procedural knowledge needs count based evidence
             
-            NarProc::mem_add_evidence(Arc::clone(&nar.procNar.evidenceMem), &sentence, nar.cfg__nConceptBeliefs, nar.procNar.t, &mut nar.procNar.rng);
+            NarProc::mem_add_evidence(Arc::clone(&nar.procNar.evidenceMem), &sentence, nar.procNar.cfg__nConceptBeliefs, nar.procNar.t, &mut nar.procNar.rng);
         }
         else {
             println!("ERR : form is not supported!");
@@ -112,32 +437,55 @@ pub fn inputT2(nar:&mut Nar, term:&Term, punct:EnumPunctation, tv:&Tv, isEvent:b
     }
     else {
         if punct == EnumPunctation::GOAL {
-            println!("ERR : eternal goals are not supported!");
+            // eternal goals are desires of the declarative reasoner and standing goals of the procedural reasoner
+            memAddTask2(Arc::clone(&nar.mem.read().shared), &sentence, true, nar.cfg.maxComplexity, nar.cfg.nConceptBeliefs, budget.p, budget.d, budget.q, budget.p, nar.procNar.t);
+            NarGoalSystem::addStandingGoal(&nar.procNar.goalSystem, &nar.mem.read(), nar.procNar.t, Arc::new(sentence), budget.p as f32);
         }
         else if punct == EnumPunctation::JUGEMENT {
-            memAddTask(Arc::clone(&nar.mem.read().shared), &sentence, true, nar.cfg__maxComplexity, nar.cfg__nConceptBeliefs, 1.0, nar.procNar.t);
+            memAddTask2(Arc::clone(&nar.mem.read().shared), &sentence, true, nar.cfg.maxComplexity, nar.cfg.nConceptBeliefs, budget.p, budget.d, budget.q, budget.p, nar.procNar.t);
         }
         else {
             println!("ERR : form is not supported!");
         }
     }
+
+    Ok(())
+}
+
+/// removes intervals from the sequence of the subject of =/>
+///
+/// returns the term without intervals and the interval between the sequence and the predicate
+/// intervals between the events of the sequence are dropped because procedural knowledge only stores the last interval
+/// returns None if the sequence doesn't contain any event
+fn stripIntervalsOfPredImpl(term:&Term) -> Option<(Term, Option<i64>)> {
+    match term {
+        Term::Stmt(Copula::PREDIMPL, subj, pred) => {
+            match &**subj {
+                Term::Seq(seq) => {
+                    let dt:Option<i64> = match seq.last().map(|iv| &**iv) {
+                        Some(Term::Interval(dt)) => Some(*dt),
+                        _ => None
+                    };
+                    let events:Vec<Box<Term>> = seq.iter().filter(|iv| match &***iv {Term::Interval(_) => false, _ => true}).map(|iv| iv.clone()).collect();
+                    if events.len() == 0 {
+                        return None; // sequence of only intervals is not a valid subject
+                    }
+                    let subj2:Term = if events.len() == 1 {*events[0].clone()} else {Term::Seq(events)};
+                    Some((Term::Stmt(Copula::PREDIMPL, Box::new(subj2), pred.clone()), dt))
+                },
+                _ => Some((term.clone(), None))
+            }
+        },
+        _ => Some((term.clone(), None))
+    }
 }
 
 /// input narsese
-/// return if narsese was parsed and had no error
+/// returns error if narsese couldn't get parsed or the input can't be stored
 // PUBLICAPI
-pub fn inputN(nar:&mut Nar, narsese:&String) -> bool {
-    match parseNarsese(narsese) {
-        Some((term, tv, punct, isEvent)) => {
-            inputT2(nar, &term, punct, &tv, isEvent);
-            true
-        },
-        None => {
-            // TODO< handle error correctly by returning a error >
-            println!("ERR - couldn't parse!");
-            false
-        }
-    }
+pub fn inputN(nar:&mut Nar, narsese:&String) -> Result<(), NarInputError> {
+    let parsed = parseNarsese2(narsese)?;
+    inputT5(nar, &parsed.term, parsed.punct, &parsed.tv, parsed.tense, parsed.budget, parsed.expDt)
 }
 
 
@@ -147,4 +495,225 @@ pub fn inputN(nar:&mut Nar, narsese:&String) -> bool {
 // PUBLICAPI
 pub fn cycle(nar:&mut Nar) {
     reasonCycle(Arc::clone(&nar.mem), &nar.currentTime);
+    NarGoalSystem::answerQuests(&nar.procNar.goalSystem.read(), &nar.mem.read());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn createTestNar() -> Nar {
+        createNarWithConfig(NarConfigBuilder::new().seed(3).syncDerivation(true).build().unwrap())
+    }
+
+    fn parseTerm(narsese:&str) -> Term {
+        crate::NarseseParser::parseNarsese(&narsese.to_string()).ok().unwrap().0
+    }
+
+    // ask() must return the answer and stop cycling when the answer is good enough
+    #[test]
+    pub fn ask_a() {
+        let mut nar = createTestNar();
+        for iNarsese in &["<a --> b>.", "<b --> c>."] {
+            inputN(&mut nar, &iNarsese.to_string()).unwrap();
+        }
+
+        let answer = ask(&mut nar, &parseTerm("<a --> c>."), 500).unwrap();
+        assert_eq!(convTermToStr(&answer.sentence.term), "<a --> c>");
+        assert_eq!((answer.tv.c - 0.81).abs() < 0.001, true);
+
+        let cycleCounter:i64 = nar.mem.read().shared.read().cycleCounter.load(Ordering::SeqCst);
+        assert_eq!(cycleCounter < 500, true); // stopped early
+        assert_eq!(answer.cycle <= cycleCounter, true);
+        assert_eq!(nar.mem.read().shared.read().questionTasks.read().len(), 0); // question was removed
+
+        assert_eq!(ask(&mut nar, &parseTerm("<c --> x>."), 20).is_none(), true);
+    }
+
+    // askStream() must return every improved answer with the cycle when it was found
+    #[test]
+    pub fn askStream_a() {
+        let mut nar = createTestNar();
+        for iNarsese in &["<a --> c>. {1.0 0.3}", "<a --> b>.", "<b --> c>."] {
+            inputN(&mut nar, &iNarsese.to_string()).unwrap();
+        }
+
+        let answers:Vec<Answer> = askStream(&mut nar, &parseTerm("<a --> c>."), 300).collect();
+        assert_eq!(answers.len() >= 2, true);
+        assert_eq!((answers[0].tv.c - 0.3).abs() < 0.001, true);
+        for iIdx in 1..answers.len() {
+            assert_eq!(calcExp(&answers[iIdx].tv) > calcExp(&answers[iIdx-1].tv), true);
+            assert_eq!(answers[iIdx].cycle >= answers[iIdx-1].cycle, true);
+        }
+        assert_eq!(answers[0].cycle < answers[answers.len()-1].cycle, true);
+    }
+
+    // askBindings() must return the best answer for each binding, limited by qaNMaxBindings
+    #[test]
+    pub fn askBindings_a() {
+        let mut nar = createTestNar();
+        for iNarsese in &["<dog --> animal>.", "<cat --> animal>. {0.8 0.9}", "<dog --> animal>. {1.0 0.5}"] {
+            inputN(&mut nar, &iNarsese.to_string()).unwrap();
+        }
+
+        let bindings:Vec<QaBinding> = askBindings(&mut nar, &parseTerm("<?x --> animal>."), 50);
+        let boundNames:Vec<String> = bindings.iter().map(|iv| convTermToStr(&iv.binding[0].val)).collect();
+        let idxOf = |name:&str| boundNames.iter().position(|iv| iv == name).unwrap();
+        assert_eq!(idxOf("dog") < idxOf("cat"), true); // ordered by exp
+        assert_eq!(bindings[idxOf("dog")].exp >= 0.95 - 0.001, true); // weaker answer for dog didn't replace the best one
+        assert_eq!(nar.mem.read().shared.read().questionTasks.read().len(), 0); // question was removed
+
+        let mut nar = createNarWithConfig(NarConfigBuilder::new().seed(3).syncDerivation(true).qaNMaxBindings(1).build().unwrap());
+        for iNarsese in &["<dog --> animal>.", "<cat --> animal>. {0.8 0.9}"] {
+            inputN(&mut nar, &iNarsese.to_string()).unwrap();
+        }
+        let bindings:Vec<QaBinding> = askBindings(&mut nar, &parseTerm("<?x --> animal>."), 50);
+        assert_eq!(bindings.len(), 1);
+        assert_eq!(convTermToStr(&bindings[0].binding[0].val), "dog");
+    }
+
+    // observer which records the answers
+    struct AnswerObserver {
+        answers: Vec<String>,
+    }
+
+    impl NarObserver for AnswerObserver {
+        fn answered(&mut self, _question:&Sentence, answer:&Sentence) {
+            self.answers.push(convTermToStr(&answer.term));
+        }
+    }
+
+    // quests must get answered by the goals which are still desired
+    #[test]
+    pub fn quest_a() {
+        let mut nar = createTestNar();
+        let observer = Arc::new(RwLock::new(AnswerObserver{answers:vec![]}));
+        addObserver(&nar, Arc::clone(&observer) as Arc<RwLock<dyn NarObserver>>);
+
+        for iNarsese in &["<a --> b>! :|:", "<a --> ?1>@"] {
+            inputN(&mut nar, &iNarsese.to_string()).unwrap();
+        }
+        cycle(&mut nar);
+        assert_eq!(observer.read().answers, vec!["<a --> b>".to_string()]);
+
+        // the answer got satisfied, so the weaker goal is the new answer
+        NarGoalSystem::event_occurred(&mut nar.procNar.goalSystem.write(), &parseTerm("<a --> b>."));
+        inputN(&mut nar, &"<a --> c>! :|: {1.0 0.5}".to_string()).unwrap();
+        cycle(&mut nar);
+        assert_eq!(observer.read().answers, vec!["<a --> b>".to_string(), "<a --> c>".to_string()]);
+    }
+
+    // observer which records the executed ops
+    struct ExecObserver {
+        ops: Vec<String>,
+    }
+
+    impl NarObserver for ExecObserver {
+        fn opExecuted(&mut self, op:&Term, _exp:Option<f64>) {
+            self.ops.push(crate::TermUtils::decodeOp(op).unwrap().1);
+        }
+    }
+
+    // eternal goal must become a standing goal which drives the execution again after it was satisfied
+    #[test]
+    pub fn standingGoal_a() {
+        let mut nar = createTestNar();
+        nar.procNar.cfgEnBabbling = false;
+        nar.procNar.ops.push(Rc::new(Box::new(crate::OpLib::OpNop{name:"^x".to_string()})));
+        let observer = Arc::new(RwLock::new(ExecObserver{ops:vec![]}));
+        addObserver(&nar, Arc::clone(&observer) as Arc<RwLock<dyn NarObserver>>);
+
+        for iNarsese in &["<(a,^x(y)) =/> b>.", "b!"] {
+            inputN(&mut nar, &iNarsese.to_string()).unwrap();
+        }
+        assert_eq!(nar.procNar.goalSystem.read().standingGoals.len(), 1);
+
+        for iEvent in &["a", "b", "c", "a"] {
+            NarProc::narStep0(&mut nar.procNar);
+            inputN(&mut nar, &format!("{}. :|:", iEvent)).unwrap();
+            NarProc::narStep1(&mut nar.procNar, &Some(Arc::clone(&nar.mem)));
+        }
+        assert_eq!(observer.read().ops.iter().filter(|iv| iv.as_str() == "^x").count(), 2);
+
+        let entry = NarGoalSystem::query(&nar.procNar.goalSystem.read(), &parseTerm("b.")).unwrap();
+        assert_eq!(NarGoalSystem::is_desired(&entry.read()), true); // b satisfied the goal, but it is still standing
+    }
+
+    // event must be generalized with the declarative knowledge to fire procedural knowledge about the generalization
+    // {tim}. :|:  <{tim} --> cat>.  <(cat,^pet) =/> purr>.  purr! :|:  |- ^pet
+    #[test]
+    pub fn declBridgeExec_a() {
+        let mut nar = createTestNar();
+        nar.procNar.cfgEnBabbling = false;
+        nar.procNar.ops.push(Rc::new(Box::new(crate::OpLib::OpNop{name:"^pet".to_string()})));
+        let observer = Arc::new(RwLock::new(ExecObserver{ops:vec![]}));
+        addObserver(&nar, Arc::clone(&observer) as Arc<RwLock<dyn NarObserver>>);
+
+        for iNarsese in &["<{tim} --> cat>.", "<(cat,^pet(y)) =/> purr>."] {
+            inputN(&mut nar, &iNarsese.to_string()).unwrap();
+        }
+
+        inputN(&mut nar, &"purr! :|:".to_string()).unwrap();
+        for iEvent in &["dog", "{tim}"] { // goal (cat,^pet)! is derived in the first step
+            NarProc::narStep0(&mut nar.procNar);
+            inputN(&mut nar, &format!("{}. :|:", iEvent)).unwrap();
+            NarProc::narStep1(&mut nar.procNar, &Some(Arc::clone(&nar.mem)));
+        }
+        assert_eq!(observer.read().ops, vec!["^pet".to_string()]);
+    }
+
+    // TV and explicit expDt of input temporal knowledge must end up in the evidence of the procedural memory
+    #[test]
+    pub fn predImplEvidence_a() {
+        let mut nar = createTestNar();
+        for iNarsese in &["<(a,^x) =/> b>. +EXPDT3 {0.8 0.9}", "<(c,^x) =/> b>. {1.0 0.9}"] {
+            inputN(&mut nar, &iNarsese.to_string()).unwrap();
+        }
+        // infinite evidence and unknown intervals are rejected
+        assert_eq!(inputT5(&mut nar, &parseTerm("<(d,^x) =/> b>."), EnumPunctation::JUGEMENT, &Tv{f:1.0,c:1.0}, EnumTense::ETERNAL, None, None), Err(NarInputError::BadConfidence(1.0)));
+        assert_eq!(inputN(&mut nar, &"<(d,^x) =/> b>. +EXPDT100000".to_string()), Err(NarInputError::BadExpDt(100000)));
+
+        let mut evidence:Vec<(String, Option<i64>, i64, i64)> = NarProc::mem_ret_evidence_all_nonunique(&nar.procNar).iter().map(|iv| {
+            let iv = iv.read();
+            match iv.evi.as_ref().unwrap() {
+                Evidence::CNT{pos,cnt} => (convTermToStr(&iv.term), iv.expDt, *pos, *cnt),
+                _ => {panic!("expected CNT!");}
+            }
+        }).collect();
+        evidence.sort();
+        evidence.dedup(); // evidence is stored in the concepts of all events
+        assert_eq!(evidence, vec![("<( a , ^x ) =/> b>".to_string(), Some(3), 7, 9), ("<( c , ^x ) =/> b>".to_string(), None, 9, 9)]);
+    }
+
+    // confidence outside of [0, 1) must be rejected for all sentences, not only for temporal knowledge
+    #[test]
+    pub fn badConfidence_a() {
+        let mut nar = createTestNar();
+        assert_eq!(inputT(&mut nar, &parseTerm("<a --> b>."), EnumPunctation::JUGEMENT, &Tv{f:1.0,c:1.0}), Err(NarInputError::BadConfidence(1.0)));
+        assert_eq!(inputT(&mut nar, &parseTerm("<a --> b>."), EnumPunctation::GOAL, &Tv{f:1.0,c:-0.5}), Err(NarInputError::BadConfidence(-0.5)));
+        assert_eq!(inputN(&mut nar, &"<a --> b>. {1.0 0.9}".to_string()), Ok(()));
+        assert_eq!(inputN(&mut nar, &"<a --> b>?".to_string()), Ok(()));
+    }
+
+    // sequence of =/> which contains only intervals must be rejected
+    #[test]
+    pub fn predImplNoEvent() {
+        let mut nar = createTestNar();
+        let term = Term::Stmt(Copula::PREDIMPL, Box::new(Term::Seq(vec![Box::new(Term::Interval(5)), Box::new(Term::Interval(3))])), Box::new(Term::Name("b".to_string())));
+        assert_eq!(inputT(&mut nar, &term, EnumPunctation::JUGEMENT, &Tv{f:1.0,c:0.9}), Err(NarInputError::NoEvent));
+        assert_eq!(NarProc::mem_ret_evidence_all_nonunique(&nar.procNar).len(), 0);
+    }
+
+    // temporal questions must get bound by the procedural knowledge
+    #[test]
+    pub fn askBindingsTemporal_a() {
+        let mut nar = createTestNar();
+        for iNarsese in &["<(a,^x) =/> b>.", "<(c,^x) =/> b>. {0.8 0.9}"] {
+            inputN(&mut nar, &iNarsese.to_string()).unwrap();
+        }
+
+        let bindings:Vec<QaBinding> = askBindings(&mut nar, &parseTerm("<(?1,^x) =/> b>."), 50);
+        let boundNames:Vec<String> = bindings.iter().map(|iv| convTermToStr(&iv.binding[0].val)).collect();
+        assert_eq!(boundNames, vec!["a", "c"]);
+    }
 }
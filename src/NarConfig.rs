@@ -0,0 +1,502 @@
+//! configuration of a NAR
+//!
+//! all tunable parameters of the declarative reasoner, the procedural reasoner and the goal system live here
+
+use std::fmt;
+use std::fs;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// kind of the error which happened while configuring a NAR
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum EnumNarConfigErrorKind {
+    IO, // file couldn't get read
+    SYNTAX, // line is not of the form key=value
+    UNKNOWN_KEY, // key is not known
+    BAD_VALUE, // value couldn't get parsed
+    INVALID, // value is out of range
+    NOT_RUNTIME, // key can't get changed while the NAR is running
+}
+
+/// error which describes why a configuration couldn't get applied
+// PUBLICAPI
+#[derive(Clone, Debug, PartialEq)]
+pub struct NarConfigError {
+    pub kind: EnumNarConfigErrorKind,
+    /// key which caused the error, can be empty
+    pub key: String,
+    /// human readable description
+    pub msg: String,
+    /// line in the configuration file, starts at 1
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for NarConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        if self.key.len() > 0 {
+            write!(f, "{}: ", self.key)?;
+        }
+        write!(f, "{}", self.msg)
+    }
+}
+
+impl std::error::Error for NarConfigError {}
+
+fn mkErr(kind:EnumNarConfigErrorKind, key:&str, msg:String) -> NarConfigError {
+    NarConfigError{kind:kind, key:key.to_string(), msg:msg, line:None}
+}
+
+/// configuration of a NAR
+// PUBLICAPI
+#[derive(Clone, Debug, PartialEq)]
+pub struct NarConfig {
+    // declarative reasoner
+    /// maximal complexity of terms of tasks
+    pub maxComplexity: i64,
+    /// number of beliefs of concept
+    pub nConceptBeliefs: usize,
+    /// how many concepts does it store at max (soft limit)
+    pub nConcepts: usize,
+    /// maximal number of judgement tasks
+    pub maxJudgementTasks: usize,
+    /// cycle counter to check for AIKR of tasks - should be prime
+    pub intervalCheckTasks: i64,
+    /// cycle counter to check for AIKR of concepts - should be prime
+    pub intervalCheckConcepts: i64,
+    /// maximal number of secondary premises for one primary premise
+    pub nMaxSecondary: usize,
+    /// minimal priority which a work item can have
+    pub minWorkItemPriority: f64,
+    /// time distance of events with future or past tense
+    pub tenseDuration: i64,
+    /// verbosity of input
+    pub verbosityInput: i32,
+    /// number of beliefs of concept for relations which are added by the NLP op ^nlp_rel_0
+    pub nlpNConceptBeliefs: usize,
+    /// ask() stops to cycle the NAR when a answer has at least this expectation
+    pub askExpThreshold: f64,
+    /// maximal number of bindings of query variables which are remembered per question, to keep it under AIKR
+    pub qaNMaxBindings: usize,
+
+    // procedural reasoner
+    /// base for the exponential intervals
+    pub procIntervalExpBase: f64,
+    /// maximal interval time
+    pub procIntervalMax: i64,
+    /// perception window for current events
+    pub procPerceptWindow: i64,
+    /// decision threshold for decision making
+    pub procDescnThreshold: f64,
+    /// maximal number of evidence
+    pub procNMaxEvidence: i64,
+    /// enable motor babbling?
+    pub procEnBabbling: bool,
+    /// how many ops can a impl seq maximally contain, values above 1 are considered as EXPERIMENTAL
+    pub procNOpsMax: i64,
+    /// how high is the proability to select multiple ops for seq impl candidates
+    pub procMultiOpProbability: f64,
+    /// is anticipation enabled? disable for specialized functionality
+    pub procEnAnticipation: bool,
+    /// how many concepts does it store at max (soft limit)
+    pub procNConcepts: i64,
+    /// how many beliefs are stored in a concept
+    pub procNConceptBeliefs: usize,
+    /// expected time for one derivation step, is the smallest time unit from the outside
+    pub procResourceExpectedTime: f32,
+    /// priority of (temporal) goal derivation
+    pub procResourceGoalDeriverPrio: f32,
+    /// how ofter should event-FIFO get sampled for perception in cycle?
+    pub procPerceptionSamplesPerStep: i64,
+    /// how verbose is the reasoner, mainly used for debugging
+    pub procVerbosity: i64,
+
+    // goal system
+    /// maximal number of goals
+    pub goalNMaxEntries: i64,
+    /// maximal depth of goals
+    pub goalNMaxDepth: i64,
+    /// are goals satisfied by events? disable for environments where goals have to persist
+    pub goalEnSatisfaction: bool,
+    /// how many times are goals sample in the sub-working-cycle?
+    pub goalSubworkingCycleRounds: i64,
+
+    // reproducibility
+    /// seed of all random number generators, None seeds them from entropy
+    pub seed: Option<u64>,
+    /// is derivation and storage of evidence done synchronously instead of on worker threads?
+    /// is necessary together with a seed to get reproducible runs
+    pub syncDerivation: bool,
+    /// number of goal derivations per procedural step if syncDerivation is enabled, replaces the budget by wall clock time
+    pub procSyncGoalDerivRounds: i64,
+}
+
+impl Default for NarConfig {
+    fn default() -> NarConfig {
+        NarConfig {
+            // declarative reasoner
+            maxComplexity: 64,
+            nConceptBeliefs: 20,
+            nConcepts: 3000,
+            maxJudgementTasks: 30,
+            intervalCheckTasks: 111,
+            intervalCheckConcepts: 173,
+            nMaxSecondary: 300,
+            minWorkItemPriority: 0.001,
+            tenseDuration: 5,
+            verbosityInput: 0,
+            nlpNConceptBeliefs: 60,
+            askExpThreshold: 0.9,
+            qaNMaxBindings: 100,
+
+            // procedural reasoner
+            procIntervalExpBase: 1.3,
+            procIntervalMax: 20,
+            procPerceptWindow: 2,
+            procDescnThreshold: 0.58,
+            procNMaxEvidence: 5000,
+            procEnBabbling: true,
+            procNOpsMax: 1,
+            procMultiOpProbability: 0.2,
+            procEnAnticipation: true,
+            procNConcepts: 1000,
+            procNConceptBeliefs: 100,
+            procResourceExpectedTime: 0.08,
+            procResourceGoalDeriverPrio: 1.05,
+            procPerceptionSamplesPerStep: 4,
+            procVerbosity: 0,
+
+            // goal system
+            goalNMaxEntries: 20,
+            goalNMaxDepth: 8,
+            goalEnSatisfaction: true,
+            goalSubworkingCycleRounds: 15,
+
+            // reproducibility
+            seed: None,
+            syncDerivation: false,
+            procSyncGoalDerivRounds: 3,
+        }
+    }
+}
+
+/// checks if the values of the configuration are in a valid range
+// PUBLICAPI
+pub fn validateNarConfig(cfg:&NarConfig) -> Result<(), NarConfigError> {
+    let checks:Vec<(&str, bool, &str)> = vec![
+        ("maxComplexity", cfg.maxComplexity > 0, "must be greater than 0"),
+        ("nConceptBeliefs", cfg.nConceptBeliefs > 0, "must be greater than 0"),
+        ("nConcepts", cfg.nConcepts > 0, "must be greater than 0"),
+        ("maxJudgementTasks", cfg.maxJudgementTasks > 0, "must be greater than 0"),
+        ("intervalCheckTasks", cfg.intervalCheckTasks > 0, "must be greater than 0"),
+        ("intervalCheckConcepts", cfg.intervalCheckConcepts > 0, "must be greater than 0"),
+        ("nMaxSecondary", cfg.nMaxSecondary > 0, "must be greater than 0"),
+        ("minWorkItemPriority", cfg.minWorkItemPriority >= 0.0, "must not be negative"),
+        ("tenseDuration", cfg.tenseDuration >= 0, "must not be negative"),
+        ("nlpNConceptBeliefs", cfg.nlpNConceptBeliefs > 0, "must be greater than 0"),
+        ("askExpThreshold", cfg.askExpThreshold >= 0.0 && cfg.askExpThreshold <= 1.0, "must be in [0, 1]"),
+        ("qaNMaxBindings", cfg.qaNMaxBindings > 0, "must be greater than 0"),
+        ("procIntervalExpBase", cfg.procIntervalExpBase > 1.0, "must be greater than 1"),
+        ("procIntervalMax", cfg.procIntervalMax >= 1, "must be at least 1"),
+        ("procPerceptWindow", cfg.procPerceptWindow >= 1, "must be at least 1"),
+        ("procDescnThreshold", cfg.procDescnThreshold >= 0.0 && cfg.procDescnThreshold <= 1.0, "must be in [0, 1]"),
+        ("procNMaxEvidence", cfg.procNMaxEvidence > 0, "must be greater than 0"),
+        ("procNOpsMax", cfg.procNOpsMax >= 1, "must be at least 1"),
+        ("procMultiOpProbability", cfg.procMultiOpProbability >= 0.0 && cfg.procMultiOpProbability <= 1.0, "must be in [0, 1]"),
+        ("procNConcepts", cfg.procNConcepts > 0, "must be greater than 0"),
+        ("procNConceptBeliefs", cfg.procNConceptBeliefs > 0, "must be greater than 0"),
+        ("procResourceExpectedTime", cfg.procResourceExpectedTime > 0.0, "must be greater than 0"),
+        ("procResourceGoalDeriverPrio", cfg.procResourceGoalDeriverPrio >= 0.0, "must not be negative"),
+        ("procPerceptionSamplesPerStep", cfg.procPerceptionSamplesPerStep >= 0, "must not be negative"),
+        ("goalNMaxEntries", cfg.goalNMaxEntries > 0, "must be greater than 0"),
+        ("goalNMaxDepth", cfg.goalNMaxDepth > 0, "must be greater than 0"),
+        ("goalSubworkingCycleRounds", cfg.goalSubworkingCycleRounds >= 0, "must not be negative"),
+        ("procSyncGoalDerivRounds", cfg.procSyncGoalDerivRounds >= 0, "must not be negative"),
+    ];
+    for (key, isValid, msg) in checks {
+        if !isValid {
+            return Err(mkErr(EnumNarConfigErrorKind::INVALID, key, msg.to_string()));
+        }
+    }
+    Ok(())
+}
+
+fn parseValue<T:std::str::FromStr>(key:&str, value:&str) -> Result<T, NarConfigError> {
+    value.trim().parse::<T>().map_err(|_| mkErr(EnumNarConfigErrorKind::BAD_VALUE, key, format!("couldn't parse value `{}`", value.trim())))
+}
+
+/// sets a value of the configuration by the name of the key
+///
+/// the value is not validated, use validateNarConfig() after setting all values
+// PUBLICAPI
+pub fn setNarConfigValue(cfg:&mut NarConfig, key:&str, value:&str) -> Result<(), NarConfigError> {
+    match key {
+        "maxComplexity" => {cfg.maxComplexity = parseValue(key, value)?;},
+        "nConceptBeliefs" => {cfg.nConceptBeliefs = parseValue(key, value)?;},
+        "nConcepts" => {cfg.nConcepts = parseValue(key, value)?;},
+        "maxJudgementTasks" => {cfg.maxJudgementTasks = parseValue(key, value)?;},
+        "intervalCheckTasks" => {cfg.intervalCheckTasks = parseValue(key, value)?;},
+        "intervalCheckConcepts" => {cfg.intervalCheckConcepts = parseValue(key, value)?;},
+        "nMaxSecondary" => {cfg.nMaxSecondary = parseValue(key, value)?;},
+        "minWorkItemPriority" => {cfg.minWorkItemPriority = parseValue(key, value)?;},
+        "tenseDuration" => {cfg.tenseDuration = parseValue(key, value)?;},
+        "verbosityInput" => {cfg.verbosityInput = parseValue(key, value)?;},
+        "nlpNConceptBeliefs" => {cfg.nlpNConceptBeliefs = parseValue(key, value)?;},
+        "askExpThreshold" => {cfg.askExpThreshold = parseValue(key, value)?;},
+        "qaNMaxBindings" => {cfg.qaNMaxBindings = parseValue(key, value)?;},
+        "procIntervalExpBase" => {cfg.procIntervalExpBase = parseValue(key, value)?;},
+        "procIntervalMax" => {cfg.procIntervalMax = parseValue(key, value)?;},
+        "procPerceptWindow" => {cfg.procPerceptWindow = parseValue(key, value)?;},
+        "procDescnThreshold" => {cfg.procDescnThreshold = parseValue(key, value)?;},
+        "procNMaxEvidence" => {cfg.procNMaxEvidence = parseValue(key, value)?;},
+        "procEnBabbling" => {cfg.procEnBabbling = parseValue(key, value)?;},
+        "procNOpsMax" => {cfg.procNOpsMax = parseValue(key, value)?;},
+        "procMultiOpProbability" => {cfg.procMultiOpProbability = parseValue(key, value)?;},
+        "procEnAnticipation" => {cfg.procEnAnticipation = parseValue(key, value)?;},
+        "procNConcepts" => {cfg.procNConcepts = parseValue(key, value)?;},
+        "procNConceptBeliefs" => {cfg.procNConceptBeliefs = parseValue(key, value)?;},
+        "procResourceExpectedTime" => {cfg.procResourceExpectedTime = parseValue(key, value)?;},
+        "procResourceGoalDeriverPrio" => {cfg.procResourceGoalDeriverPrio = parseValue(key, value)?;},
+        "procPerceptionSamplesPerStep" => {cfg.procPerceptionSamplesPerStep = parseValue(key, value)?;},
+        "procVerbosity" => {cfg.procVerbosity = parseValue(key, value)?;},
+        "goalNMaxEntries" => {cfg.goalNMaxEntries = parseValue(key, value)?;},
+        "goalNMaxDepth" => {cfg.goalNMaxDepth = parseValue(key, value)?;},
+        "goalEnSatisfaction" => {cfg.goalEnSatisfaction = parseValue(key, value)?;},
+        "goalSubworkingCycleRounds" => {cfg.goalSubworkingCycleRounds = parseValue(key, value)?;},
+        "seed" => {cfg.seed = if value.trim() == "none" {None} else {Some(parseValue(key, value)?)};},
+        "syncDerivation" => {cfg.syncDerivation = parseValue(key, value)?;},
+        "procSyncGoalDerivRounds" => {cfg.procSyncGoalDerivRounds = parseValue(key, value)?;},
+        _ => {return Err(mkErr(EnumNarConfigErrorKind::UNKNOWN_KEY, key, "unknown key".to_string()));}
+    }
+    Ok(())
+}
+
+/// returns the value of the configuration by the name of the key
+// PUBLICAPI
+pub fn retNarConfigValue(cfg:&NarConfig, key:&str) -> Option<String> {
+    match key {
+        "maxComplexity" => Some(cfg.maxComplexity.to_string()),
+        "nConceptBeliefs" => Some(cfg.nConceptBeliefs.to_string()),
+        "nConcepts" => Some(cfg.nConcepts.to_string()),
+        "maxJudgementTasks" => Some(cfg.maxJudgementTasks.to_string()),
+        "intervalCheckTasks" => Some(cfg.intervalCheckTasks.to_string()),
+        "intervalCheckConcepts" => Some(cfg.intervalCheckConcepts.to_string()),
+        "nMaxSecondary" => Some(cfg.nMaxSecondary.to_string()),
+        "minWorkItemPriority" => Some(cfg.minWorkItemPriority.to_string()),
+        "tenseDuration" => Some(cfg.tenseDuration.to_string()),
+        "verbosityInput" => Some(cfg.verbosityInput.to_string()),
+        "nlpNConceptBeliefs" => Some(cfg.nlpNConceptBeliefs.to_string()),
+        "askExpThreshold" => Some(cfg.askExpThreshold.to_string()),
+        "qaNMaxBindings" => Some(cfg.qaNMaxBindings.to_string()),
+        "procIntervalExpBase" => Some(cfg.procIntervalExpBase.to_string()),
+        "procIntervalMax" => Some(cfg.procIntervalMax.to_string()),
+        "procPerceptWindow" => Some(cfg.procPerceptWindow.to_string()),
+        "procDescnThreshold" => Some(cfg.procDescnThreshold.to_string()),
+        "procNMaxEvidence" => Some(cfg.procNMaxEvidence.to_string()),
+        "procEnBabbling" => Some(cfg.procEnBabbling.to_string()),
+        "procNOpsMax" => Some(cfg.procNOpsMax.to_string()),
+        "procMultiOpProbability" => Some(cfg.procMultiOpProbability.to_string()),
+        "procEnAnticipation" => Some(cfg.procEnAnticipation.to_string()),
+        "procNConcepts" => Some(cfg.procNConcepts.to_string()),
+        "procNConceptBeliefs" => Some(cfg.procNConceptBeliefs.to_string()),
+        "procResourceExpectedTime" => Some(cfg.procResourceExpectedTime.to_string()),
+        "procResourceGoalDeriverPrio" => Some(cfg.procResourceGoalDeriverPrio.to_string()),
+        "procPerceptionSamplesPerStep" => Some(cfg.procPerceptionSamplesPerStep.to_string()),
+        "procVerbosity" => Some(cfg.procVerbosity.to_string()),
+        "goalNMaxEntries" => Some(cfg.goalNMaxEntries.to_string()),
+        "goalNMaxDepth" => Some(cfg.goalNMaxDepth.to_string()),
+        "goalEnSatisfaction" => Some(cfg.goalEnSatisfaction.to_string()),
+        "goalSubworkingCycleRounds" => Some(cfg.goalSubworkingCycleRounds.to_string()),
+        "seed" => Some(cfg.seed.map(|v| v.to_string()).unwrap_or("none".to_string())),
+        "syncDerivation" => Some(cfg.syncDerivation.to_string()),
+        "procSyncGoalDerivRounds" => Some(cfg.procSyncGoalDerivRounds.to_string()),
+        _ => None
+    }
+}
+
+/// can the value of the key get changed while the NAR is running?
+///
+/// keys which are used to build memory or which are captured by worker threads can only get set at creation
+pub fn isRuntimeKey(key:&str) -> bool {
+    match key {
+        "maxComplexity" | "nConceptBeliefs" | "qaNMaxBindings" | "procIntervalExpBase" | "procIntervalMax" | "procNConceptBeliefs" | "goalNMaxDepth" | "seed" | "syncDerivation" => false,
+        _ => true
+    }
+}
+
+/// streams of random numbers, every user of random numbers gets its own stream so they don't influence each other
+pub const RNG_STREAM_DECL: u64 = 1;
+pub const RNG_STREAM_DECL_WORKER: u64 = 2;
+pub const RNG_STREAM_PROC: u64 = 3;
+pub const RNG_STREAM_PROC_TRACE: u64 = 4;
+pub const RNG_STREAM_PROC_WORKER: u64 = 5;
+pub const RNG_STREAM_ENV: u64 = 6;
+
+/// creates the random number generator for a stream
+///
+/// the generator is derived from the seed of the configuration, it is seeded from entropy if no seed is set
+// PUBLICAPI
+pub fn createRng(cfg:&NarConfig, stream:u64) -> StdRng {
+    match cfg.seed {
+        Some(seed) => StdRng::seed_from_u64(seed ^ stream.wrapping_mul(0x9E3779B97F4A7C15)), // mix stream into seed
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// loads configuration from a file with lines of the form key=value
+///
+/// empty lines and lines starting with # or // are ignored, keys which are not set keep their default value
+// PUBLICAPI
+pub fn loadNarConfigFile(path:&String) -> Result<NarConfig, NarConfigError> {
+    let content:String = fs::read_to_string(path).map_err(|e| mkErr(EnumNarConfigErrorKind::IO, "", format!("couldn't read {}: {}", path, e)))?;
+    parseNarConfig(&content)
+}
+
+/// parses configuration from lines of the form key=value
+// PUBLICAPI
+pub fn parseNarConfig(content:&str) -> Result<NarConfig, NarConfigError> {
+    let mut cfg = NarConfig::default();
+    for (lineIdx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.len() == 0 || line.starts_with("#") || line.starts_with("//") {
+            continue; // ignore empty lines and comments
+        }
+
+        let withLine = |mut err:NarConfigError| {err.line = Some(lineIdx+1); err};
+        match line.find('=') {
+            Some(idx) => {
+                setNarConfigValue(&mut cfg, line[..idx].trim(), &line[idx+1..]).map_err(withLine)?;
+            },
+            None => {
+                return Err(withLine(mkErr(EnumNarConfigErrorKind::SYNTAX, "", format!("expected key=value, found `{}`", line))));
+            }
+        }
+    }
+    validateNarConfig(&cfg)?;
+    Ok(cfg)
+}
+
+/// builder to create a validated configuration
+// PUBLICAPI
+pub struct NarConfigBuilder {
+    cfg: NarConfig,
+}
+
+impl NarConfigBuilder {
+    /// starts with the default configuration
+    pub fn new() -> NarConfigBuilder {
+        NarConfigBuilder{cfg:NarConfig::default()}
+    }
+
+    /// starts with a existing configuration
+    pub fn fromConfig(cfg:NarConfig) -> NarConfigBuilder {
+        NarConfigBuilder{cfg:cfg}
+    }
+
+    /// validates and returns the configuration
+    pub fn build(self) -> Result<NarConfig, NarConfigError> {
+        validateNarConfig(&self.cfg)?;
+        Ok(self.cfg)
+    }
+
+    // declarative reasoner
+    pub fn maxComplexity(mut self, v:i64) -> NarConfigBuilder {self.cfg.maxComplexity = v; self}
+    pub fn nConceptBeliefs(mut self, v:usize) -> NarConfigBuilder {self.cfg.nConceptBeliefs = v; self}
+    pub fn nConcepts(mut self, v:usize) -> NarConfigBuilder {self.cfg.nConcepts = v; self}
+    pub fn maxJudgementTasks(mut self, v:usize) -> NarConfigBuilder {self.cfg.maxJudgementTasks = v; self}
+    pub fn intervalCheckTasks(mut self, v:i64) -> NarConfigBuilder {self.cfg.intervalCheckTasks = v; self}
+    pub fn intervalCheckConcepts(mut self, v:i64) -> NarConfigBuilder {self.cfg.intervalCheckConcepts = v; self}
+    pub fn nMaxSecondary(mut self, v:usize) -> NarConfigBuilder {self.cfg.nMaxSecondary = v; self}
+    pub fn minWorkItemPriority(mut self, v:f64) -> NarConfigBuilder {self.cfg.minWorkItemPriority = v; self}
+    pub fn tenseDuration(mut self, v:i64) -> NarConfigBuilder {self.cfg.tenseDuration = v; self}
+    pub fn verbosityInput(mut self, v:i32) -> NarConfigBuilder {self.cfg.verbosityInput = v; self}
+    pub fn nlpNConceptBeliefs(mut self, v:usize) -> NarConfigBuilder {self.cfg.nlpNConceptBeliefs = v; self}
+    pub fn askExpThreshold(mut self, v:f64) -> NarConfigBuilder {self.cfg.askExpThreshold = v; self}
+    pub fn qaNMaxBindings(mut self, v:usize) -> NarConfigBuilder {self.cfg.qaNMaxBindings = v; self}
+
+    // procedural reasoner
+    pub fn procIntervalExpBase(mut self, v:f64) -> NarConfigBuilder {self.cfg.procIntervalExpBase = v; self}
+    pub fn procIntervalMax(mut self, v:i64) -> NarConfigBuilder {self.cfg.procIntervalMax = v; self}
+    pub fn procPerceptWindow(mut self, v:i64) -> NarConfigBuilder {self.cfg.procPerceptWindow = v; self}
+    pub fn procDescnThreshold(mut self, v:f64) -> NarConfigBuilder {self.cfg.procDescnThreshold = v; self}
+    pub fn procNMaxEvidence(mut self, v:i64) -> NarConfigBuilder {self.cfg.procNMaxEvidence = v; self}
+    pub fn procEnBabbling(mut self, v:bool) -> NarConfigBuilder {self.cfg.procEnBabbling = v; self}
+    pub fn procNOpsMax(mut self, v:i64) -> NarConfigBuilder {self.cfg.procNOpsMax = v; self}
+    pub fn procMultiOpProbability(mut self, v:f64) -> NarConfigBuilder {self.cfg.procMultiOpProbability = v; self}
+    pub fn procEnAnticipation(mut self, v:bool) -> NarConfigBuilder {self.cfg.procEnAnticipation = v; self}
+    pub fn procNConcepts(mut self, v:i64) -> NarConfigBuilder {self.cfg.procNConcepts = v; self}
+    pub fn procNConceptBeliefs(mut self, v:usize) -> NarConfigBuilder {self.cfg.procNConceptBeliefs = v; self}
+    pub fn procResourceExpectedTime(mut self, v:f32) -> NarConfigBuilder {self.cfg.procResourceExpectedTime = v; self}
+    pub fn procResourceGoalDeriverPrio(mut self, v:f32) -> NarConfigBuilder {self.cfg.procResourceGoalDeriverPrio = v; self}
+    pub fn procPerceptionSamplesPerStep(mut self, v:i64) -> NarConfigBuilder {self.cfg.procPerceptionSamplesPerStep = v; self}
+    pub fn procVerbosity(mut self, v:i64) -> NarConfigBuilder {self.cfg.procVerbosity = v; self}
+
+    // goal system
+    pub fn goalNMaxEntries(mut self, v:i64) -> NarConfigBuilder {self.cfg.goalNMaxEntries = v; self}
+    pub fn goalNMaxDepth(mut self, v:i64) -> NarConfigBuilder {self.cfg.goalNMaxDepth = v; self}
+    pub fn goalEnSatisfaction(mut self, v:bool) -> NarConfigBuilder {self.cfg.goalEnSatisfaction = v; self}
+    pub fn goalSubworkingCycleRounds(mut self, v:i64) -> NarConfigBuilder {self.cfg.goalSubworkingCycleRounds = v; self}
+
+    // reproducibility
+    pub fn seed(mut self, v:u64) -> NarConfigBuilder {self.cfg.seed = Some(v); self}
+    pub fn syncDerivation(mut self, v:bool) -> NarConfigBuilder {self.cfg.syncDerivation = v; self}
+    pub fn procSyncGoalDerivRounds(mut self, v:i64) -> NarConfigBuilder {self.cfg.procSyncGoalDerivRounds = v; self}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn parse_a() {
+        let cfg = parseNarConfig("# comment\nmaxComplexity = 32\n\n// comment\nseed=5\nprocEnBabbling=false\n").unwrap();
+        assert_eq!(cfg.maxComplexity, 32);
+        assert_eq!(cfg.seed, Some(5));
+        assert_eq!(cfg.procEnBabbling, false);
+        assert_eq!(cfg.nConceptBeliefs, NarConfig::default().nConceptBeliefs); // keys which are not set keep the default
+
+        let err = parseNarConfig("maxComplexity=32\nfoo=1\n").err().unwrap();
+        assert_eq!((err.kind, err.key.as_str(), err.line), (EnumNarConfigErrorKind::UNKNOWN_KEY, "foo", Some(2)));
+        let err = parseNarConfig("maxComplexity\n").err().unwrap();
+        assert_eq!((err.kind, err.line), (EnumNarConfigErrorKind::SYNTAX, Some(1)));
+        let err = parseNarConfig("maxComplexity=abc\n").err().unwrap();
+        assert_eq!(err.kind, EnumNarConfigErrorKind::BAD_VALUE);
+    }
+
+    #[test]
+    pub fn validate_a() {
+        assert_eq!(validateNarConfig(&NarConfig::default()).is_ok(), true);
+        let err = parseNarConfig("procDescnThreshold=1.5\n").err().unwrap();
+        assert_eq!((err.kind, err.key.as_str()), (EnumNarConfigErrorKind::INVALID, "procDescnThreshold"));
+        let err = NarConfigBuilder::new().nConceptBeliefs(0).build().err().unwrap();
+        assert_eq!((err.kind, err.key.as_str()), (EnumNarConfigErrorKind::INVALID, "nConceptBeliefs"));
+    }
+
+    #[test]
+    pub fn builder_a() {
+        let cfg = NarConfigBuilder::new().maxComplexity(16).seed(7).syncDerivation(true).build().unwrap();
+        assert_eq!(cfg, NarConfig{maxComplexity:16, seed:Some(7), syncDerivation:true, ..NarConfig::default()});
+        assert_eq!(retNarConfigValue(&cfg, "seed"), Some("7".to_string()));
+        assert_eq!(retNarConfigValue(&cfg, "foo"), None);
+    }
+
+    // !cfg command of the facade
+    #[test]
+    pub fn cfgCommand_a() {
+        let mut nar = crate::Nar::createNar();
+        let mut quit = false;
+        let mut cmd = |nar:&mut crate::Nar::Nar, line:&str| -> Vec<String> {crate::NarInputFacade::input(nar, &line.to_string(), &mut quit).ok().unwrap()};
+
+        assert_eq!(cmd(&mut nar, "!cfg procDescnThreshold 0.7"), vec!["procDescnThreshold=0.7".to_string()]);
+        assert_eq!(nar.cfg.procDescnThreshold, 0.7);
+        assert_eq!(nar.mem.read().cfg.procDescnThreshold, 0.7); // is applied to the running reasoner
+        assert_eq!(cmd(&mut nar, "!cfg procDescnThreshold"), vec!["procDescnThreshold=0.7".to_string()]);
+
+        assert_eq!(cmd(&mut nar, "!cfg procDescnThreshold 2")[0].starts_with("ERR"), true); // invalid value isn't applied
+        assert_eq!(nar.cfg.procDescnThreshold, 0.7);
+        assert_eq!(cmd(&mut nar, "!cfg maxComplexity 10")[0].starts_with("ERR"), true); // can only get set at creation
+        assert_eq!(nar.cfg.maxComplexity, NarConfig::default().maxComplexity);
+    }
+}
@@ -24,16 +24,15 @@ use crate::NarSentence::EnumPunctation;
 use crate::NarSentence::Sentence;
 use crate::NarSentence::retTv;
 use crate::NarSentence::newEternalSentenceByTv;
-use crate::NarSentence::convSentenceTermPunctToStr;
-use crate::NarSentence::shallowCopySentence;
 use crate::NarMem;
 use crate::NarUnify;
 use crate::NarInfProcedural;
 use crate::Utils::{enforce};
 use crate::NarStamp::newStamp;
 use crate::NarWorkingCycle::Task2;
-use crate::NarWorkingCycle::QHandler;
 use crate::NarWorkingCycle::Mem2;
+use crate::NarWorkingCycle::{qaTryAnswer, qaNotifyAnswers, QaAnswer};
+use crate::NarWorkingCycle::infGoalDed;
 
 pub struct ActiveSet {
 	pub set: Vec< Arc<RwLock<Entry>> >,
@@ -42,8 +41,6 @@ pub struct ActiveSet {
 
 /// structure for the goal system
 pub struct GoalSystem {
-    pub queuedProcQaBridgeAnswers: Vec<QueuedProcQaBridgeAnswer>,
-
     /// we store op goals seperatly as a optimization
     pub activeSet: ActiveSet,
 
@@ -57,6 +54,12 @@ pub struct GoalSystem {
     /// used to speed up queries
     pub entriesByTerm: HashMap<Term, RwLock<Vec< Arc<RwLock<Entry>> > > >,
 
+    /// eternal goals with their desirability, they are re-activated every procedural step so they don't get forgotten or stay satisfied
+    ///
+    /// only input goals are standing goals, eternal goals which are derived by the goal system are not added:
+    /// they are derived again from the standing goals, so they can get forgotten like all derived goals instead of piling up
+    pub standingGoals: Vec<(Arc<Sentence>, f32)>,
+
     /// is a goal satisfied if a event happens when the terms match up?
     ///
     /// is "true" for natural environments, but can be set to "false" to specialize the reasoner for "crisp" tasks such as playing board games or solving logic problems(?)
@@ -111,12 +114,6 @@ pub struct BatchByDepth {
     pub depth: i64,
 }
 
-/// answer of procedural / Q&A bridge - which is queued for processing in NarProc.rs
-pub struct QueuedProcQaBridgeAnswer {
-    pub entry: Arc<RwLock<Entry>>,
-    pub answer: Sentence,
-}
-
 pub fn makeGoalSystem(nMaxEntries:i64, nMaxDepth: i64) -> GoalSystem {
     let mut batchesByDepth: Vec<Arc<RwLock<BatchByDepth>>> = vec![];
     for iDepth in 0..nMaxDepth {
@@ -124,8 +121,6 @@ pub fn makeGoalSystem(nMaxEntries:i64, nMaxDepth: i64) -> GoalSystem {
     }
     
     GoalSystem {
-        queuedProcQaBridgeAnswers: vec![],
-
         activeSet: ActiveSet{set: vec![]},
 
         batchesByDepth: batchesByDepth,
@@ -134,6 +129,8 @@ pub fn makeGoalSystem(nMaxEntries:i64, nMaxDepth: i64) -> GoalSystem {
 
         entriesByTerm:HashMap::new(),
 
+        standingGoals: vec![],
+
         cfg__enGoalSatisfaction: true, // enable for natural environments
         cfg__dbg_enAddEntry: true, // for debugging
 
@@ -161,6 +158,11 @@ pub fn retEntries(goalSystem: &GoalSystem) -> Vec<Arc<RwLock<Entry>>> {
 
 /// /param t is the procedural reasoner NAR time
 pub fn addEntry(goalSystem: &Arc<RwLock<GoalSystem>>, mem2: &Mem2, t:i64, goal: Arc<Sentence>, evidence: Option<Arc<RwLock<Sentence>>>, depth:i64) {
+    addEntry3(goalSystem, mem2, t, goal, evidence, depth, 1.0);
+}
+
+/// add goal with a explicit initial desirability
+pub fn addEntry3(goalSystem: &Arc<RwLock<GoalSystem>>, mem2: &Mem2, t:i64, goal: Arc<Sentence>, evidence: Option<Arc<RwLock<Sentence>>>, depth:i64, desirability:f32) {
     enforce(goal.punct == EnumPunctation::GOAL); // must be a goal!
     
     if goalSystem.read().cfg__dbg_enAddEntry { // print goal which is tried to put into system
@@ -201,45 +203,13 @@ pub fn addEntry(goalSystem: &Arc<RwLock<GoalSystem>>, mem2: &Mem2, t:i64, goal:
         }
     }
 
-    addEntry2(goalSystem, mem2, Arc::new(RwLock::new(Entry{sentence:Arc::clone(&goal), utility:1.0, evidence:evidence, createTime:t, depth:depth, desirability:1.0, accDesirability:0.0})));
+    addEntry2(goalSystem, mem2, Arc::new(RwLock::new(Entry{sentence:Arc::clone(&goal), utility:1.0, evidence:evidence, createTime:t, depth:depth, desirability:desirability, accDesirability:0.0})));
 }
 
 /// helper to add goal
 // private because it is a helper
-fn addEntry2(goalSystem: &Arc<RwLock<GoalSystem>>, mem2: &Mem2, e: Arc<RwLock<Entry>>) {
+fn addEntry2(goalSystem: &Arc<RwLock<GoalSystem>>, _mem2: &Mem2, e: Arc<RwLock<Entry>>) {
     
-    { // procedural / Q&A bridge
-        match &*e.read().sentence.term {
-            Term::Seq(seq) if seq.len() > 0 => {
-                let seqCondTerm: Term = (*seq[0]).clone();
-
-                let seqCondQaTerm: Term = convProcTermToQaTerm(&seqCondTerm); // translate variables to Question variables
-
-                // add question to Q&A system
-                let sharedGuard = mem2.shared.read();
-                let questionTasksGuard = sharedGuard.questionTasks.write();
-                
-                /*
-                24.04.2021 commented this because
-                           * it's not useful
-                           * it leaks memory because it's not kept under AIKR
-                           * the functionality to reason about declarative stuff is better solved by a more unified reasoner where we reason on events directly like in ONA
-
-                questionTasksGuard.push(Box::new(Task2 {
-                    sentence:newEternalSentenceByTv(&seqCondQaTerm,EnumPunctation::QUESTION,&Tv::Tv{f:1.0,c:0.0},newStamp(&vec![])),
-                    handler:Some(Arc::new(RwLock::new(QaProcHandlerImpl{entry:Arc::clone(&e), goalSystem:Arc::clone(goalSystem)}))),
-                    bestAnswerExp:0.0, // because has no answer yet
-                    prio:1.0,
-                }));
-
-                // keep under AIKR (hard)
-                questionTasksGuard = questionTasksGuard[..questionTasksGuard.len().min(50)].to_vec();
-                */
-            }
-            _ => {}
-        }
-    }
-
     // decide if we add the goal to the active set
     use crate::TermUtils::decodeOp;
     let isOp = decodeOp(&e.read().sentence.term).is_some();
@@ -314,118 +284,13 @@ fn addEntry2(goalSystem: &Arc<RwLock<GoalSystem>>, mem2: &Mem2, e: Arc<RwLock<En
 }
 
 
-/// handler to build a procedural pseudo-event for proc / Q&A bridge
-pub struct QaProcHandlerImpl {
-    pub entry: Arc<RwLock<Entry>>,
-    pub goalSystem: Arc<RwLock<GoalSystem>>,
-}
-
-impl QHandler for QaProcHandlerImpl {
-    fn answer(&mut self, question:&Term, answer:&Sentence) {
-        // print question and send answer
-        let msg = "TRACE proc/Q&A bridge answer: ".to_owned() + &convTermToStr(&question) + "? " + &convSentenceTermPunctToStr(&answer, true);
-        println!("{}", &msg);
-
-        // queue up answer to be processed in NarProc.rs
-        self.goalSystem.write().queuedProcQaBridgeAnswers.push(QueuedProcQaBridgeAnswer{
-            entry: Arc::clone(&self.entry),
-            answer: shallowCopySentence(&answer),
-        });
-
-    }
-}
-
-
-
-
-fn convProcTermToQaTerm(term: &Term) -> Term {
-    match term {
-        Term::QVar(name) => Term::QVar("Q_".to_owned()+name),
-        Term::DepVar(name)=> Term::QVar("D_".to_owned()+name),
-        Term::IndepVar(name)=> Term::QVar("I_".to_owned()+name),
-
-
-        Term::Stmt(cop, subj, pred) => 
-            Term::Stmt(*cop, Box::new(convProcTermToQaTerm(subj)), Box::new(convProcTermToQaTerm(pred)))
-        ,
-        Term::Name(name) => term.clone(),
-        Term::Seq(arr) => {
-            let mut arr2 = vec![];
-            for i in arr {
-                arr2.push(Box::new(convProcTermToQaTerm(i)));
-            }
-            Term::Seq(arr2)
-        }, // sequence
-        Term::SetInt(arr) => {
-            let mut arr2 = vec![];
-            for i in arr {
-                arr2.push(Box::new(convProcTermToQaTerm(i)));
-            }
-            Term::SetInt(arr2)
-        },
-        Term::SetExt(arr) => {
-            let mut arr2 = vec![];
-            for i in arr {
-                arr2.push(Box::new(convProcTermToQaTerm(i)));
-            }
-            Term::SetExt(arr2)
-        },
-        Term::Conj(arr) => {
-            let mut arr2 = vec![];
-            for i in arr {
-                arr2.push(Box::new(convProcTermToQaTerm(i)));
-            }
-            Term::Conj(arr2)
-        },
-        Term::Prod(arr) => {
-            let mut arr2 = vec![];
-            for i in arr {
-                arr2.push(Box::new(convProcTermToQaTerm(i)));
-            }
-            Term::Prod(arr2)
-        },
-        Term::Img(rel, idx, arr) => {
-            let rel2:Term = convProcTermToQaTerm(rel);
-
-            let mut arr2 = vec![];
-            for i in arr {
-                arr2.push(Box::new(convProcTermToQaTerm(i)));
-            }
-
-            Term::Img(Box::new(rel2), *idx, arr2)
-        },
-        Term::IntInt(arr) => {
-            let mut arr2 = vec![];
-            for i in arr {
-                arr2.push(Box::new(convProcTermToQaTerm(i)));
-            }
-            Term::IntInt(arr2)
-        },
-        Term::ExtInt(arr) => {
-            let mut arr2 = vec![];
-            for i in arr {
-                arr2.push(Box::new(convProcTermToQaTerm(i)));
-            }
-            Term::ExtInt(arr2)
-        },
-        Term::Par(arr) => {
-            let mut arr2 = vec![];
-            for i in arr {
-                arr2.push(Box::new(convProcTermToQaTerm(i)));
-            }
-            Term::Par(arr2)
-        },
-        Term::Neg(t) => Term::Neg(Box::new(convProcTermToQaTerm(t))),
-    }
-}
-
 // privte because helper
 fn checkSetContains(set: &Vec< Arc<RwLock<Entry>> >, s: &Sentence) -> bool {
     // we check for same stamp - ignore it if the goal is exactly the same, because we don't need to store same goals
     for iv in set {
         if 
             // optimization< checking term first is faster! >
-            checkEqTerm(&iv.read().sentence.term, &s.term) && // is necessary, else we don't accept detached goals!
+            alphaEq(&iv.read().sentence.term, &s.term) && // is necessary, else we don't accept detached goals!
             NarStamp::checkSame(&iv.read().sentence.stamp, &s.stamp)
         {
             return true;
@@ -484,7 +349,7 @@ pub fn limitMemory(goalSystem: &Arc<RwLock<GoalSystem>>, mem2: &Mem2, t: i64) {
 
 /// sample a goal from the goal table of the goal system
 /// returns (sentence, depth)
-pub fn sample(goalSystem: &GoalSystem, rng: &mut rand::rngs::ThreadRng) -> Option<(Arc<Sentence>, i64)> {
+pub fn sample(goalSystem: &GoalSystem, rng: &mut rand::rngs::StdRng) -> Option<(Arc<Sentence>, i64)> {
     // select batch (or return)
     let selBatchRef = {
 
@@ -616,6 +481,8 @@ pub fn query_by_antecedent(queryTerm: &Term, procMem:&NarMem::Mem) -> Vec<Arc<Rw
 }
 
 /// filters belief candidates which can be used for inference with the goal
+///
+/// the predicate of the belief has to unify with the goal
 pub fn query_by_consequence(queryTerm: &Term, procMem:&NarMem::Mem) -> Vec<Arc<RwLock<Sentence>>> {
     let mut res = Vec::new();
 
@@ -625,8 +492,8 @@ pub fn query_by_consequence(queryTerm: &Term, procMem:&NarMem::Mem) -> Vec<Arc<R
     // filter
     for iBelief in &potentialEvidence {
         match &*(iBelief.read()).term {
-            Term::Stmt(Copula::PREDIMPL, _subj, pred) => {
-                if checkEqTerm(&queryTerm, &pred) {
+            predImpl @ Term::Stmt(Copula::PREDIMPL, _, _) => {
+                if NarInfProcedural::unifyPredImplByPred(predImpl, &queryTerm).is_some() {
                     res.push(Arc::clone(iBelief));
                 }
             },
@@ -678,6 +545,55 @@ pub fn selHighestExpGoalByState(goalSystem: &GoalSystem, state:&Term) -> (f64, O
     res
 }
 
+/// procedural / declarative bridge, derives goals with the declarative knowledge
+///
+/// B!  <A ==> B>.  |- A!
+/// B!  <A <-> B>.  |- A!
+pub fn deriveGoalsByDecl(goal: &Sentence, declMem:&NarMem::Mem) -> Vec<Sentence> {
+    let mut concls:Vec<Sentence> = vec![];
+    for iBelief in &NarMem::ret_beliefs_by_terms_nonunique(declMem, &[(*goal.term).clone()]) {
+        let belief = iBelief.read();
+        if NarStamp::checkOverlap(&goal.stamp, &belief.stamp) {
+            continue; // overlap -> can't derive anything
+        }
+
+        let stamp = NarStamp::merge(&goal.stamp, &belief.stamp);
+        for (conclTerm, conclTv, _punct) in infGoalDed(&goal.term, EnumPunctation::GOAL, &retTv(&goal), &belief.term, belief.punct, &retTv(&belief)) {
+            if concls.iter().any(|iConcl| checkEqTerm(&iConcl.term, &conclTerm) && NarStamp::checkSame(&iConcl.stamp, &stamp)) {
+                continue; // belief is stored multiple times
+            }
+            concls.push(newEternalSentenceByTv(&conclTerm, EnumPunctation::GOAL, &conclTv, stamp.clone()));
+        }
+    }
+    concls
+}
+
+/// procedural / declarative bridge, generalizes a event with the declarative knowledge
+///
+/// {tim}  <{tim} --> cat>.  |- cat
+///
+/// returns the generalized events with the expectation of the belief which was used
+pub fn retGeneralizedEvents(eventTerm: &Term, declMem:&NarMem::Mem) -> Vec<(Term, f64)> {
+    let mut res:Vec<(Term, f64)> = vec![];
+    for iBelief in NarMem::ret_beliefs_of_concept(declMem, eventTerm).into_iter().flatten() {
+        let belief = iBelief.read();
+        match &*belief.term {
+            Term::Stmt(Copula::INH, subj, pred) if checkEqTerm(subj, eventTerm) => {
+                let exp:f64 = Tv::calcExp(&retTv(&belief).unwrap());
+                if exp <= 0.5 {
+                    continue; // event isn't a instance of pred
+                }
+                match res.iter_mut().find(|iv| checkEqTerm(&iv.0, pred)) {
+                    Some(existing) => {existing.1 = existing.1.max(exp);},
+                    None => {res.push(((**pred).clone(), exp));}
+                }
+            },
+            _ => {}
+        }
+    }
+    res
+}
+
 /// helper struct for deriver
 /// carries goal with evidence and the depth of the goal
 struct H {
@@ -697,7 +613,7 @@ enum EnumGoalDerivationStrategy {
 
 // private because helper for sampleAndInference()
 /// sampledDepth: depth of sampled goal
-fn deriveGoalsHelper(sampledGoal: &Sentence, sampledDepth:i64, strategy:EnumGoalDerivationStrategy, procMem:&NarMem::Mem, rng: &mut rand::rngs::ThreadRng)->Vec<H> {
+fn deriveGoalsHelper(sampledGoal: &Sentence, sampledDepth:i64, strategy:EnumGoalDerivationStrategy, procMem:&NarMem::Mem, declMem:&NarMem::Mem, rng: &mut rand::rngs::StdRng)->Vec<H> {
     let mut concls:Vec<H> = Vec::new(); // conclusions
 
     match NarInfProcedural::infGoalDetach(&sampledGoal) {
@@ -720,6 +636,12 @@ fn deriveGoalsHelper(sampledGoal: &Sentence, sampledDepth:i64, strategy:EnumGoal
                             concls.push(H{goal:Arc::new(conclOpt.unwrap()), evidence:Some(Arc::clone(iBelief)), depth:sampledDepth+1});
                         }
                     }
+
+                    // * derive with declarative knowledge
+                    // only done here because it uses all beliefs, the sampled goal is processed with both strategies
+                    for iConcl in deriveGoalsByDecl(&sampledGoal, declMem) {
+                        concls.push(H{goal:Arc::new(iConcl), evidence:None, depth:sampledDepth+1});
+                    }
                 },
                 SAMPLE_2 => {
                     for _it in 0..2 {
@@ -733,7 +655,6 @@ fn deriveGoalsHelper(sampledGoal: &Sentence, sampledDepth:i64, strategy:EnumGoal
                     }
                 }
             }
-
         }
     }
 
@@ -742,7 +663,7 @@ fn deriveGoalsHelper(sampledGoal: &Sentence, sampledDepth:i64, strategy:EnumGoal
 
 
 /// /param t is the procedural reasoner NAR time
-pub fn sampleAndInference(goalSystem: &Arc<RwLock<GoalSystem>>, mem2: &Mem2, t:i64, procMem:&NarMem::Mem, rng: &mut rand::rngs::ThreadRng) {
+pub fn sampleAndInference(goalSystem: &Arc<RwLock<GoalSystem>>, mem2: &Mem2, t:i64, procMem:&NarMem::Mem, rng: &mut rand::rngs::StdRng) {
     // * sample goal from set of goals
     let sampledGoalOpt: Option<(Arc<Sentence>, i64)> = sample(&goalSystem.write(), rng);
 
@@ -790,7 +711,7 @@ pub fn sampleAndInference(goalSystem: &Arc<RwLock<GoalSystem>>, mem2: &Mem2, t:i
             };
 
             // do actual derivations!
-            for iconcl in deriveGoalsHelper(&sampledWsEntry.goal, sampledWsEntry.sampledDepth, /* pick in deriveGoalsHelper() only two beliefs */ EnumGoalDerivationStrategy::SAMPLE_2, &procMem, rng) {
+            for iconcl in deriveGoalsHelper(&sampledWsEntry.goal, sampledWsEntry.sampledDepth, /* pick in deriveGoalsHelper() only two beliefs */ EnumGoalDerivationStrategy::SAMPLE_2, &procMem, &mem2.shared.read().mem.read(), rng) {
                 workingSet.push(Rc::new(WsEntry{goal:iconcl.goal.clone(), sampledDepth:iconcl.depth})); // add to working set for processing
                 concls.push((iconcl.goal, iconcl.evidence, iconcl.depth)); // add to conclusions
             }
@@ -805,7 +726,7 @@ pub fn sampleAndInference(goalSystem: &Arc<RwLock<GoalSystem>>, mem2: &Mem2, t:i
     { // old mechanism
       // process only sampled goal
       // we need to do this additional to the other mechanism, because the other mechanism doesn't process all belief candidates!
-        for iconcl in deriveGoalsHelper(&sampledGoal, sampledDepth, EnumGoalDerivationStrategy::ALL_BELIEFS, procMem, rng) {
+        for iconcl in deriveGoalsHelper(&sampledGoal, sampledDepth, EnumGoalDerivationStrategy::ALL_BELIEFS, procMem, &mem2.shared.read().mem.read(), rng) {
             concls.push((iconcl.goal, iconcl.evidence, iconcl.depth));
         }
     }
@@ -823,12 +744,19 @@ pub fn sampleAndInference(goalSystem: &Arc<RwLock<GoalSystem>>, mem2: &Mem2, t:i
 }
 
 /// called from outside when event happened
-pub fn event_occurred(goalSystem: &mut GoalSystem, eventTerm:&Term) {
+///
+/// returns the goals which were satisfied by the event
+pub fn event_occurred(goalSystem: &mut GoalSystem, eventTerm:&Term) -> Vec<Arc<Sentence>> {
+    let mut satisfied:Vec<Arc<Sentence>> = vec![];
+
     for iEntityRc in retEntries(goalSystem) {
         let mut iEntity = iEntityRc.write();
         if goalSystem.cfg__enGoalSatisfaction && // do we want to satisfy goals?
            checkEqTerm(&iEntity.sentence.term, eventTerm) // terms must of course to match up that a event can satify the goal
         {
+            if iEntity.desirability > 0.0 { // was the goal not already satisfied?
+                satisfied.push(Arc::clone(&iEntity.sentence));
+            }
             iEntity.desirability = 0.0; // set desirability to 0.0 because it happened
         }
     }
@@ -838,9 +766,84 @@ pub fn event_occurred(goalSystem: &mut GoalSystem, eventTerm:&Term) {
         if goalSystem.cfg__enGoalSatisfaction && // do we want to satisfy goals?
            checkEqTerm(&iEntity.sentence.term, eventTerm) // terms must of course to match up that a event can satify the goal
         {
+            if iEntity.desirability > 0.0 { // was the goal not already satisfied?
+                satisfied.push(Arc::clone(&iEntity.sentence));
+            }
             iEntity.desirability = 0.0; // set desirability to 0.0 because it happened
         }
     }
+
+    satisfied
+}
+
+/// adds a eternal goal as a standing goal, replaces the standing goal with the same term
+/// /param t is the procedural reasoner NAR time
+pub fn addStandingGoal(goalSystem: &Arc<RwLock<GoalSystem>>, mem2: &Mem2, t: i64, goal: Arc<Sentence>, desirability:f32) {
+    {
+        let mut goalSystemGuard = goalSystem.write();
+        goalSystemGuard.standingGoals.retain(|(iGoal, _)| !checkEqTerm(&iGoal.term, &goal.term));
+        goalSystemGuard.standingGoals.push((Arc::clone(&goal), desirability));
+    }
+    addEntry3(goalSystem, mem2, t, goal, None, 0, desirability);
+}
+
+/// re-activates the standing goals
+///
+/// restores the desirability of satisfied standing goals and adds the forgotten standing goals again
+/// /param t is the procedural reasoner NAR time
+pub fn reactivateStandingGoals(goalSystem: &Arc<RwLock<GoalSystem>>, mem2: &Mem2, t: i64) {
+    let standingGoals: Vec<(Arc<Sentence>, f32)> = goalSystem.read().standingGoals.clone();
+    if standingGoals.len() == 0 {
+        return; // OPT
+    }
+
+    let entries: Vec<Arc<RwLock<Entry>>> = retEntries(&goalSystem.read());
+    for (iGoal, iDesirability) in &standingGoals {
+        let entry: Option<&Arc<RwLock<Entry>>> = entries.iter().find(|iEntry| {
+            let entryGuard = iEntry.read();
+            checkEqTerm(&entryGuard.sentence.term, &iGoal.term) && NarStamp::checkSame(&entryGuard.sentence.stamp, &iGoal.stamp)
+        });
+        match entry {
+            Some(entry) => {
+                let mut entryGuard = entry.write();
+                entryGuard.desirability = *iDesirability;
+                entryGuard.createTime = t; // refresh so it doesn't decay
+            },
+            None => { // was forgotten
+                addEntry3(goalSystem, mem2, t, Arc::clone(iGoal), None, 0, *iDesirability);
+            }
+        }
+    }
+}
+
+/// tries to answer the quests of the declarative reasoner with the goals
+pub fn answerQuests(goalSystem: &GoalSystem, mem2: &Mem2) {
+    let mut answers:Vec<QaAnswer> = vec![];
+    {
+        let sharedGuard = mem2.shared.read();
+        let mut questionTasksGuard = sharedGuard.questionTasks.write();
+        if !questionTasksGuard.iter().any(|iTask| iTask.sentence.punct == EnumPunctation::QUEST) {
+            return; // no quests, don't waste time
+        }
+
+        let entries: Vec<Arc<RwLock<Entry>>> = retEntries(goalSystem).into_iter().filter(|iEntry| is_desired(&iEntry.read())).collect(); // satisfied goals don't answer quests
+        for iTask in questionTasksGuard.iter_mut().filter(|iTask| iTask.sentence.punct == EnumPunctation::QUEST) {
+            // the best answer may not be desired anymore, then the best of the goals which are still desired is the new answer
+            let bestDesiredExp:f64 = entries.iter()
+                .map(|iEntry| iEntry.read())
+                .filter(|iEntry| NarUnify::unify(&iTask.sentence.term, &iEntry.sentence.term).is_some())
+                .map(|iEntry| Tv::calcExp(&retTv(&iEntry.sentence).unwrap()))
+                .fold(0.0, f64::max);
+            if bestDesiredExp < iTask.bestAnswerExp {
+                iTask.bestAnswerExp = 0.0;
+            }
+
+            for iEntry in &entries {
+                qaTryAnswer(iTask, &iEntry.read().sentence, mem2.cfg.qaNMaxBindings, &mut answers);
+            }
+        }
+    }
+    qaNotifyAnswers(answers, &mem2.observers);
 }
 
 /// returns the goal which matches with the term
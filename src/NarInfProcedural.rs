@@ -5,6 +5,7 @@ use rand::Rng;
 
 use crate::Tv;
 use crate::NarStamp;
+use crate::NarUnify;
 use crate::Term::*;
 use crate::NarSentence::EnumPunctation;
 use crate::NarSentence::Sentence;
@@ -12,6 +13,21 @@ use crate::NarSentence::retTv;
 use crate::NarSentence::newEternalSentenceByTv;
 use crate::NarSentence::shallowCopySentence;
 
+/// unifies the predicate of the =/> with the term, the variables of the predicate get bound
+///
+/// returns the =/> with the substituted variables if the predicate unifies
+///
+/// ex: <(a,^x({SELF},$1)) =/> <$1 --> b>> unified with <c --> b> is <(a,^x({SELF},c)) =/> <c --> b>>
+pub fn unifyPredImplByPred(predImpl: &Term, term: &Term) -> Option<Term> {
+    match predImpl {
+        Term::Stmt(Copula::PREDIMPL, _subj, pred) => {
+            let asgnmts:Vec<NarUnify::Asgnment> = NarUnify::unify(&pred, &term)?;
+            Some(NarUnify::unifySubst(predImpl, &asgnmts))
+        },
+        _ => None
+    }
+}
+
 /// does inference of goal with a belief
 ///
 /// we need to derive goals from matching implSeqs by goal deduction
@@ -20,20 +36,17 @@ use crate::NarSentence::shallowCopySentence;
 /// |-dedGoal
 /// a!
 ///
+/// the predicate of the belief is unified with the goal, the substitution is applied to the subject
+///
 /// returns derivation
 pub fn infGoalBelief(goal: &Sentence, belief: &Sentence)-> Option<Sentence> {
-    // check if term is same and inference can be done
-    match &*belief.term {
-        Term::Stmt(Copula::PREDIMPL, _subj, pred) => {
-            if !checkEqTerm(&goal.term, &pred) {
-                return None; // can't do inference because terms have to be equal
-            }
-        },
-        _ => {
-            // don't do anything here
-            return None;
+    // check if predicate unifies with goal and inference can be done
+    let unifiedBeliefTerm:Term = match unifyPredImplByPred(&belief.term, &goal.term) {
+        Some(unifiedBeliefTerm) => unifiedBeliefTerm,
+        None => {
+            return None; // can't do inference because predicate has to unify with goal
         }
-    }
+    };
     
     if NarStamp::checkOverlap(&goal.stamp, &belief.stamp) {
         return None; // overlap -> can't derive anything
@@ -49,7 +62,7 @@ pub fn infGoalBelief(goal: &Sentence, belief: &Sentence)-> Option<Sentence> {
     
     let stamp = NarStamp::merge(&goal.stamp, &belief.stamp);
 
-    match &*belief.term {
+    match &unifiedBeliefTerm {
         Term::Stmt(Copula::PREDIMPL, subj, _) => {
             return Some(newEternalSentenceByTv(&subj,EnumPunctation::GOAL,&tvConcl,stamp));
         },
@@ -61,7 +74,7 @@ pub fn infGoalBelief(goal: &Sentence, belief: &Sentence)-> Option<Sentence> {
 }
 
 /// var intro for procedural (of sentence)
-pub fn matchandintro_var1_sentence(s: &Sentence,  rng: &mut rand::rngs::ThreadRng) -> Vec<Sentence> {
+pub fn matchandintro_var1_sentence(s: &Sentence,  rng: &mut rand::rngs::StdRng) -> Vec<Sentence> {
     let mut res = vec![];
     for i_term in &matchandintro_var1(&s.term, rng) {
         let mut i_sentence: Sentence = shallowCopySentence(&s);
@@ -72,7 +85,7 @@ pub fn matchandintro_var1_sentence(s: &Sentence,  rng: &mut rand::rngs::ThreadRn
 }
 
 /// var intro for procedural
-pub fn matchandintro_var1(s: &Term,  rng: &mut rand::rngs::ThreadRng) -> Vec<Term> {
+pub fn matchandintro_var1(s: &Term,  rng: &mut rand::rngs::StdRng) -> Vec<Term> {
     let mut concl: Vec<Term> = vec![];
 
     // match < ( x --> [y] , ... ) =/> ... >  where x will be var
@@ -112,7 +125,7 @@ pub fn matchandintro_var1(s: &Term,  rng: &mut rand::rngs::ThreadRng) -> Vec<Ter
 }
 
 /// helper to introduce variables, counts possible terms and doesn't intro if count < 2
-fn intro_vars(t: &Term, repl: &Term,  rng: &mut rand::rngs::ThreadRng) -> Vec<Term> {
+fn intro_vars(t: &Term, repl: &Term,  rng: &mut rand::rngs::StdRng) -> Vec<Term> {
     if count_occurence_subterms(t, repl) < 2 {
         return vec![]; // is not worth to itro vars because it occurs only once!
     }
@@ -124,89 +137,7 @@ fn intro_vars(t: &Term, repl: &Term,  rng: &mut rand::rngs::ThreadRng) -> Vec<Te
             return with.clone();
         }
 
-        return match t {
-            Term::Stmt(cop, subj, pred) => {
-                let res_subj = helper(subj, repl, with);
-                let res_pred = helper(pred, repl, with);
-                Term::Stmt(*cop, Box::new(res_subj), Box::new(res_pred))
-            }
-            Term::Name(name) => Term::Name(name.clone()),
-            Term::Seq(seq) => {
-                let mut arr = vec![];
-                for i in seq {
-                    arr.push(Box::new(helper(i, repl, with)));
-                }
-                Term::Seq(arr)
-            },
-            Term::SetInt(set) => {
-                let mut arr = vec![];
-                for i in set {
-                    arr.push(Box::new(helper(i, repl, with)));
-                }
-                Term::SetInt(arr)
-            },
-            Term::SetExt(set) => {
-                let mut arr = vec![];
-                for i in set {
-                    arr.push(Box::new(helper(i, repl, with)));
-                }
-                Term::SetExt(arr)
-            },
-            Term::QVar(name) => {
-                Term::QVar(name.clone())
-            },
-            Term::DepVar(name) => {
-                Term::DepVar(name.clone())
-            },
-            Term::IndepVar(name) => {
-                Term::IndepVar(name.clone())
-            },
-            Term::Conj(elements) => {
-                let mut arr = vec![];
-                for i in elements {
-                    arr.push(Box::new(helper(i, repl, with)));
-                }
-                Term::Conj(arr)
-            },
-            Term::Prod(elements) => {
-                let mut arr = vec![];
-                for i in elements {
-                    arr.push(Box::new(helper(i, repl, with)));
-                }
-                Term::Prod(arr)
-            },
-            Term::Img(rel,idx,elements) => {
-                let mut arr = vec![];
-                for i in elements {
-                    arr.push(Box::new(helper(i, repl, with)));
-                }
-                Term::Img(Box::new((**rel).clone()),*idx,arr)
-            },
-            Term::IntInt(elements) => {
-                let mut arr = vec![];
-                for i in elements {
-                    arr.push(Box::new(helper(i, repl, with)));
-                }
-                Term::IntInt(arr)
-            },
-            Term::ExtInt(elements) => {
-                let mut arr = vec![];
-                for i in elements {
-                    arr.push(Box::new(helper(i, repl, with)));
-                }
-                Term::ExtInt(arr)
-            },
-            Term::Par(seq) => {
-                let mut arr = vec![];
-                for i in seq {
-                    arr.push(Box::new(helper(i, repl, with)));
-                }
-                Term::Par(arr)
-            },
-            Term::Neg(term) => {
-                Term::Neg(Box::new(helper(term, repl, with)))
-            },
-        }
+        t.map_children(|iChild| helper(iChild, repl, with))
     }
 
     // generate random variable name
@@ -215,7 +146,7 @@ fn intro_vars(t: &Term, repl: &Term,  rng: &mut rand::rngs::ThreadRng) -> Vec<Te
     vec![helper(t, repl, &with)] // do actual substitutation
 }
 
-fn create_random_varname(rng: &mut rand::rngs::ThreadRng) -> Term {
+fn create_random_varname(rng: &mut rand::rngs::StdRng) -> Term {
     let mut name: String = "Q".to_string();
     for _i in 0..24 {
         let v = "012456789ABCDEF".chars().nth(rng.gen_range(0..16)).unwrap();
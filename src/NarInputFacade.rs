@@ -4,19 +4,22 @@ use std::rc::Rc;
 use std::sync::Arc;
 
 use crate::Nar::*;
+use crate::NarConfig::retNarConfigValue;
 use crate::NarWorkingCycle::{debugCreditsOfTasks, flushTasks};
 //use crate::NarModuleNlp;
 //use crate::NarModuleNlp2;
 use crate::NarModuleNlp3;
 use crate::NarProc;
+use crate::NarUtilReadn::writeNarseseFile;
 use crate::OpLib;
 
 /// gives facade a new line, which can be narsese or a command
 ///
 /// /param quit is used to signal if program has to get terminated
 /// returns requested information as strings!
+/// returns error if the line is narsese which couldn't get parsed
 // PUBLICAPI
-pub fn input(nar:&mut Nar, line: &String, quit: &mut bool) -> Vec<String> {
+pub fn input(nar:&mut Nar, line: &String, quit: &mut bool) -> Result<Vec<String>, NarInputError> {
     let retInfo = Vec::new();
     
     *quit = false;
@@ -25,11 +28,11 @@ pub fn input(nar:&mut Nar, line: &String, quit: &mut bool) -> Vec<String> {
     trimNewline(&mut input);
 
     // TODO< trim comment by // away   >
-    if input.len() >= 2 && &input[..2] == "//" { // is commented line?
+    if input.starts_with("//") { // is commented line?
         // ignore
     }
     
-    else if input.len() >= 3 && &input[..3] == "!sp" { // step procedural
+    else if input.starts_with("!sp") { // step procedural
         //let mut nCycles = 1;
         //if input.len() > 5 && false { // parse number of cycles
         //    // TODO< check if it was parsed fine! >
@@ -45,11 +48,51 @@ pub fn input(nar:&mut Nar, line: &String, quit: &mut bool) -> Vec<String> {
     else if input == "!spB" { // step procedural B
         NarProc::narStep1(&mut nar.procNar, &Some(Arc::clone(&nar.mem)));
     }
-    else if input.len() >= 2 && &input[..2] == "!s" {
+    else if input.starts_with("!save ") { // save state of NAR to SQLite database, argument is path
+        let path:String = input[6..].trim().to_string();
+        return match save(nar, &path) {
+            Ok(()) => Ok(vec![format!("saved to {}", path)]),
+            Err(err) => Ok(vec![format!("ERR - {}", err)]),
+        };
+    }
+    else if input.starts_with("!load ") { // load state of NAR from SQLite database, argument is path
+        let path:String = input[6..].trim().to_string();
+        return match load(nar, &path) {
+            Ok(()) => Ok(vec![format!("loaded from {}", path)]),
+            Err(err) => Ok(vec![format!("ERR - {}", err)]),
+        };
+    }
+    else if input.starts_with("!dump ") { // write beliefs as narsese to file, argument is path
+        let path:String = input[6..].trim().to_string();
+        return match writeNarseseFile(nar, &path) {
+            Ok(()) => Ok(vec![format!("dumped to {}", path)]),
+            Err(err) => Ok(vec![format!("ERR - {}", err)]),
+        };
+    }
+    else if input == "!rules" { // show rules with their statistics
+        return Ok(retRuleStats(nar).iter().map(|iv| format!("{} enabled={} tried={} fired={} concls={}", iv.name, iv.enabled, iv.nTried, iv.nFired, iv.nConclusions)).collect());
+    }
+    else if input.starts_with("!rules ") { // load rules from rule file, argument is path
+        let path:String = input[7..].trim().to_string();
+        return match loadRules(nar, &path) {
+            Ok(nRules) => Ok(vec![format!("loaded {} rules from {}", nRules, path)]),
+            Err(err) => Ok(vec![format!("ERR - {}", err)]),
+        };
+    }
+    else if input.starts_with("!rule+ ") || input.starts_with("!rule- ") { // enable or disable rule, argument is name of rule
+        let name:&str = input[7..].trim();
+        return match setRuleEnabled(nar, name, input.starts_with("!rule+")) {
+            true => Ok(vec![]),
+            false => Ok(vec![format!("ERR - unknown rule {}", name)]),
+        };
+    }
+    else if input.starts_with("!s") {
         let mut nCycles = 1;
         if input.len() > 2 { // parse number of cycles
-            // TODO< check if it was parsed fine! >
-            nCycles = input[2..].parse::<i64>().unwrap();
+            nCycles = match input[2..].parse::<i64>() {
+                Ok(n) => n,
+                Err(_) => {return Ok(vec![format!("ERR - couldn't parse number of cycles!")]);}
+            };
         }
         for _i in 0..nCycles {
             cycle(nar);
@@ -58,7 +101,7 @@ pub fn input(nar:&mut Nar, line: &String, quit: &mut bool) -> Vec<String> {
     else if input == "!dtf" { // delcarative tasks flush
         flushTasks(&*nar.mem.read());
     }
-    else if input.len() >= 5 && &input[..5] == "!por " { // procedural op register --- register op, argument is type/name of op, 2nd argument is name of op
+    else if input.starts_with("!por ") { // procedural op register --- register op, argument is type/name of op, 2nd argument is name of op
         let args:Vec<&str> = input[5..].split_whitespace().collect();
         if args.len() == 1 {
             let argOpType:String = args[0].to_string();
@@ -89,22 +132,40 @@ pub fn input(nar:&mut Nar, line: &String, quit: &mut bool) -> Vec<String> {
         NarProc::debugEvidence(&nar.procNar);
     }
     else if input == "!peb 0" { // procedural enable babbling
-        nar.procNar.cfgEnBabbling = false;
+        setConfigValue(nar, "procEnBabbling", "false").unwrap();
     }
     else if input == "!peb 1" {
-        nar.procNar.cfgEnBabbling = true;
+        setConfigValue(nar, "procEnBabbling", "true").unwrap();
+    }
+    else if input.starts_with("!cfg ") { // show or change configuration, arguments are key and optional value
+        let args:Vec<&str> = input[5..].split_whitespace().collect();
+        if args.len() == 1 {
+            return match retNarConfigValue(&nar.cfg, args[0]) {
+                Some(value) => Ok(vec![format!("{}={}", args[0], value)]),
+                None => Ok(vec![format!("ERR - unknown key {}", args[0])]),
+            };
+        }
+        else if args.len() == 2 {
+            return match setConfigValue(nar, args[0], args[1]) {
+                Ok(()) => Ok(vec![format!("{}={}", args[0], args[1])]),
+                Err(err) => Ok(vec![format!("ERR - {}", err)]),
+            };
+        }
+        else {
+            return Ok(vec![format!("ERR - expected !cfg key [value]")]);
+        }
     }
 
-    else if input.len() > 6 && &input[..6] == "!.nlp " {
+    else if input.len() > 6 && input.starts_with("!.nlp ") {
         let natural = &input[6..].to_string();
         NarModuleNlp3::process(nar, &natural);
     }
     /*
-    else if input.len() > 7 && &input[..7] == "!.nlp2 " {
+    else if input.len() > 7 && input.starts_with("!.nlp2 ") {
         let natural = &input[7..].to_string();
         NarModuleNlp2::process(nar, &natural);
     }
-    else if input.len() > 6 && &input[..6] == "!.nlp " { // command to stuff nlp input into nlp module
+    else if input.len() > 6 && input.starts_with("!.nlp ") { // command to stuff nlp input into nlp module
         let natural = &input[6..].to_string();
         let mut isQuestion = false;
         let resTermOpt:Option<Sentence> = NarModuleNlp::process(&natural, &mut isQuestion);
@@ -157,11 +218,11 @@ pub fn input(nar:&mut Nar, line: &String, quit: &mut bool) -> Vec<String> {
                     match &**pred {
                         Term::Name(name) if name == "relIs" => {
                             // translate to inheritance
-                            inputT(nar, &s(Copula::INH, &prod0, &prod1), punct, &Tv{f:1.0,c:0.9});
+                            inputT(nar, &s(Copula::INH, &prod0, &prod1), punct, &Tv{f:1.0,c:0.9})?;
                         },
                         Term::Name(name) if name == "relIs2" => {
                             // translate to inheritance
-                            inputT(nar, &s(Copula::INH, &prod0, &prod1), punct, &Tv{f:1.0,c:0.9});
+                            inputT(nar, &s(Copula::INH, &prod0, &prod1), punct, &Tv{f:1.0,c:0.9})?;
                         },
                         Term::Name(name) if name == "relGENERIC" => {
                             let a = prod2.unwrap();
@@ -171,12 +232,12 @@ pub fn input(nar:&mut Nar, line: &String, quit: &mut bool) -> Vec<String> {
                                 Term::Name(name) if name == "is" => { // we ave special case for is relation, translate to inheritance
                                     // translate to inheritance
                                     // subj is always a SetInt
-                                    inputT(nar, &s(Copula::INH, &prod0, &Term::SetInt(vec![Box::new(prod1)])), punct, &Tv{f:1.0,c:0.9});
+                                    inputT(nar, &s(Copula::INH, &prod0, &Term::SetInt(vec![Box::new(prod1)])), punct, &Tv{f:1.0,c:0.9})?;
                                 },
                                 _ => {
                                     // pass on to NAR
-                                    inputT(nar, &resTerm.clone(), punct, &Tv{f:1.0,c:0.9}); // as raw relation
-                                    inputT(nar, &s(Copula::INH, &p2(&prod0, &prod1), &prod223), punct, &Tv{f:1.0,c:0.9}); // pass as inheritance
+                                    inputT(nar, &resTerm.clone(), punct, &Tv{f:1.0,c:0.9})?; // as raw relation
+                                    inputT(nar, &s(Copula::INH, &p2(&prod0, &prod1), &prod223), punct, &Tv{f:1.0,c:0.9})?; // pass as inheritance
                                 }
                             }
                         },
@@ -184,7 +245,7 @@ pub fn input(nar:&mut Nar, line: &String, quit: &mut bool) -> Vec<String> {
                         
                         Term::Name(name) if name == "relIsQuery" => {
                             // translate to inheritance question
-                            inputT(nar, &s(Copula::INH, &prod0, &prod1), EnumPunctation::QUESTION, &Tv{f:1.0,c:0.9});
+                            inputT(nar, &s(Copula::INH, &prod0, &prod1), EnumPunctation::QUESTION, &Tv{f:1.0,c:0.9})?;
                         },
                         
                         _ => {
@@ -203,21 +264,21 @@ pub fn input(nar:&mut Nar, line: &String, quit: &mut bool) -> Vec<String> {
     }
     */
     else if input == "!dt" { // debug tasks
-        return debugCreditsOfTasks(&*nar.mem.read());
+        return Ok(debugCreditsOfTasks(&*nar.mem.read()));
     }
     else if input == "!dmd" { // debug memory declarative
         // TODO< put into function and call it here >
-        return vec![format!("concept count = {}", nar.mem.read().shared.read().mem.read().concepts.len())];
+        return Ok(vec![format!("concept count = {}", nar.mem.read().shared.read().mem.read().concepts.len())]);
     }
     else if input == "!QQ" { // quit
         *quit = true;
     }
     else if input == "" {}
     else {
-        inputN(nar, &input);
+        inputN(nar, &input)?;
     }
 
-    retInfo
+    Ok(retInfo)
 }
 
 fn trimNewline(s: &mut String) {
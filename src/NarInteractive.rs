@@ -1,14 +1,20 @@
 // interactive NAR
 
 use std::io;
+use std::sync::Arc;
+use parking_lot::RwLock;
 use crate::Nar::*;
 use crate::NarInputFacade;
+use crate::NarObserver::NarObserver;
+use crate::NarSentence::{Sentence, convSentenceTermPunctToStr};
 
 /// run interactive loop until process is terminated by user or until the interactive session is closed
 pub fn runInteractive(nar:&mut Nar) {
     let repeatLastInput = true; // repeat command by just pressing enter with empty input?
 
     let mut lastInput = "".to_string(); // used to repeat command by just pressing enter with empty input
+
+    addObserver(nar, Arc::new(RwLock::new(ObserverImpl{}))); // register observer to print events of the procedural reasoner
     loop {
         let mut input2 = String::new();
         match io::stdin().read_line(&mut input2) {
@@ -28,9 +34,13 @@ pub fn runInteractive(nar:&mut Nar) {
                 println!("{}", input);
 
                 let mut quit = false;
-                let resLines: Vec<String> = NarInputFacade::input(nar, &input, &mut quit);
-                for iLine in &resLines {
-                    println!("{}", iLine);
+                match NarInputFacade::input(nar, &input, &mut quit) {
+                    Ok(resLines) => {
+                        for iLine in &resLines {
+                            println!("{}", iLine);
+                        }
+                    },
+                    Err(err) => println!("ERR - {}", err),
                 }
                 
                 if quit {
@@ -42,6 +52,24 @@ pub fn runInteractive(nar:&mut Nar) {
     }
 }
 
+/// observer to print events which are not printed by the reasoner itself
+pub struct ObserverImpl {
+}
+
+impl NarObserver for ObserverImpl {
+    fn goalSatisfied(&mut self, goal:&Sentence) {
+        println!("TRACE goal satisfied: {}", convSentenceTermPunctToStr(&goal, true));
+    }
+
+    fn anticipationConfirmed(&mut self, evidence:&Sentence) {
+        println!("TRACE anticipation confirmed: {}", convSentenceTermPunctToStr(&evidence, true));
+    }
+
+    fn anticipationFailed(&mut self, evidence:&Sentence) {
+        println!("TRACE anticipation failed: {}", convSentenceTermPunctToStr(&evidence, true));
+    }
+}
+
 fn trimNewline(s: &mut String) {
     // from https://blog.v-gar.de/2019/04/rust-remove-trailing-newline-after-input/
     while s.ends_with('\n') || s.ends_with('\r') {
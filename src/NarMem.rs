@@ -1,14 +1,17 @@
 // memory system for NAR
 
 use std::sync::{Arc};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::BuildHasherDefault;
 use parking_lot::RwLock;
 
-use crate::Term::Term;
-use crate::Term::checkEqTerm;
+use crate::Term::{Term, Copula};
 use crate::Term::retSubterms;
+use crate::Term::{alphaEq, checkHasVar, standardizeVars};
+use crate::TermIntern::{TermId, retByTerm, retByTermMut};
 
-use crate::Tv::calcExp;
+use crate::Tv::{calcExp, rev};
 
 use crate::NarStamp;
 
@@ -16,12 +19,14 @@ use crate::NarSentence::EnumPunctation;
 use crate::NarSentence::Sentence;
 use crate::NarSentence::retTv;
 use crate::NarSentence::shallowCopySentence;
+use crate::NarSentence::convSentenceTermPunctToStr2;
 
 use crate::NarSentence::calcUsageUsefulness;
+use crate::NarSentence::{Evidence, Usage};
 
 /// memory system
 pub struct Concept {
-    pub name:Term,
+    pub name:TermId,
 
     pub payload: Payload,
 }
@@ -31,17 +36,24 @@ pub struct Payload {
     pub beliefsByExp:Vec<Arc<RwLock<Sentence>>>,
     /// beliefs ordered only by usage as in ONA
     pub beliefsByUsage:Vec<Arc<RwLock<Sentence>>>,
+    /// eternal goals of the term of the concept ordered by exp()
+    pub desires:Vec<Arc<RwLock<Sentence>>>,
 }
 
 
 
+/// map of concepts by interned name
+///
+/// uses a hasher without random state so the iteration order is the same in every run
+pub type ConceptMap = HashMap<TermId, Arc<Concept>, BuildHasherDefault<DefaultHasher>>;
+
 /// memory
 pub struct Mem {
-    pub concepts:HashMap<Term, Arc<Concept>>,
+    pub concepts:ConceptMap,
 }
 
 pub fn make() -> Mem {
-    Mem{concepts:HashMap::new(),}
+    Mem{concepts:ConceptMap::default(),}
 }
 
 pub fn storeInConcepts(mem: &mut Mem, s:&Sentence, nBeliefs: usize, currentTime: i64) {
@@ -55,7 +67,7 @@ pub fn storeInConcepts2(mem: &mut Mem, s:&Sentence, subterms: &Vec<Term>, nBelie
     }
     
     for iTerm in subterms {
-        match mem.concepts.get_mut(&iTerm.clone()) {
+        match retConceptMut(mem, iTerm) {
             Some(arcConcept) => {
                 match Arc::get_mut(arcConcept) {
                     Some(concept) => {
@@ -63,7 +75,7 @@ pub fn storeInConcepts2(mem: &mut Mem, s:&Sentence, subterms: &Vec<Term>, nBelie
                             let mut exists = false;
                             for iBelief in &concept.payload.beliefsByExp {
                                 let iBeliefGuard = iBelief.read();
-                                if checkEqTerm(&iBeliefGuard.term, &s.term) && NarStamp::checkOverlap(&iBeliefGuard.stamp, &s.stamp) {
+                                if alphaEq(&iBeliefGuard.term, &s.term) && NarStamp::checkOverlap(&iBeliefGuard.stamp, &s.stamp) {
                                     exists = true;
                                     break; // OPT
                                 }
@@ -89,7 +101,7 @@ pub fn storeInConcepts2(mem: &mut Mem, s:&Sentence, subterms: &Vec<Term>, nBelie
                             let mut exists = false;
                             for iBelief in &concept.payload.beliefsByUsage {
                                 let iBeliefGuard = iBelief.read();
-                                if checkEqTerm(&iBeliefGuard.term, &s.term) && NarStamp::checkOverlap(&iBeliefGuard.stamp, &s.stamp) {
+                                if alphaEq(&iBeliefGuard.term, &s.term) && NarStamp::checkOverlap(&iBeliefGuard.stamp, &s.stamp) {
                                     exists = true;
                                     break; // OPT
                                 }
@@ -121,23 +133,105 @@ pub fn storeInConcepts2(mem: &mut Mem, s:&Sentence, subterms: &Vec<Term>, nBelie
             None => { // concept doesn't exist
                 // * insert new concept if we are here
                 
+                let iConceptKey:TermId = retConceptKey(iTerm); // alpha-equivalent terms share the concept
                 let concept = Arc::new(Concept {
-                    name:iTerm.clone(),
+                    name:iConceptKey.clone(),
                     payload:Payload{
                         beliefsByExp:vec![Arc::new(RwLock::new(shallowCopySentence(&(*s))))],
                         beliefsByUsage:vec![Arc::new(RwLock::new(shallowCopySentence(&(*s))))],
+                        desires:vec![],
                     },
                 });
                 
-                mem.concepts.insert(iTerm.clone(), concept); // add concept to memory
+                mem.concepts.insert(iConceptKey, concept); // add concept to memory
             }
         }
     }
 }
 
 
+/// stores a eternal goal as a desire in the concept of the term of the goal
+///
+/// the desire is revised with a desire of the same term if the stamps don't overlap
+///
+/// returns the revised desire if it was revised
+pub fn storeDesire(mem: &mut Mem, s:&Sentence, nDesires: usize) -> Option<Sentence> {
+    if s.punct != EnumPunctation::GOAL {
+        return None; // ignore everything else than GOAL
+    }
+
+    if retConcept(mem, &s.term).is_none() { // insert new concept if it doesn't exist
+        let conceptKey:TermId = retConceptKey(&s.term); // alpha-equivalent terms share the concept
+        mem.concepts.insert(conceptKey.clone(), Arc::new(Concept {
+            name:conceptKey.clone(),
+            payload:Payload{
+                beliefsByExp:vec![],
+                beliefsByUsage:vec![],
+                desires:vec![],
+            },
+        }));
+    }
+
+    let concept:&mut Concept = match Arc::get_mut(retConceptMut(mem, &s.term).unwrap()) {
+        Some(concept) => concept,
+        None => {
+            println!("INTERNAL ERROR - couldn't aquire arc!");
+            return None;
+        }
+    };
+
+    let mut res:Option<Sentence> = None;
+    let mut exists = false;
+    for iDesireIdx in 0..concept.payload.desires.len() {
+        let revised:Option<Sentence> = {
+            let iDesire = concept.payload.desires[iDesireIdx].read();
+            if !alphaEq(&iDesire.term, &s.term) {
+                continue;
+            }
+            if NarStamp::checkOverlap(&iDesire.stamp, &s.stamp) {
+                exists = true;
+                break;
+            }
+            
+            Some(Sentence{
+                term:iDesire.term.clone(),
+                t:None,
+                punct:EnumPunctation::GOAL,
+                stamp:NarStamp::merge(&iDesire.stamp, &s.stamp),
+                expDt:None,
+                evi:Some(Evidence::TV(rev(&retTv(&iDesire).unwrap(), &retTv(&s).unwrap()))),
+                usage:Arc::new(RwLock::new(Usage{lastUsed:0, useCount:0})),
+            })
+        };
+        
+        concept.payload.desires[iDesireIdx] = Arc::new(RwLock::new(shallowCopySentence(revised.as_ref().unwrap()))); // replace by revised desire
+        res = revised;
+        exists = true;
+        break; // breaking here is fine, because desire should be just once in table!
+    }
+
+    if !exists { // add desire only if it doesn't already exist!
+        concept.payload.desires.push(Arc::new(RwLock::new(shallowCopySentence(&(*s)))));
+    }
+
+    // order by importance
+    let mut temp:Vec<(f64,Arc<RwLock<Sentence>>)> = concept.payload.desires.iter().map(|iv| {
+            let ivGuard = iv.read();
+            (calcExp(&retTv(&ivGuard).unwrap()), Arc::clone(iv))
+        }).collect(); // compute exp for each element, necessary because else we have a deadlock
+    temp.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap()); // do actual sorting by exp
+    concept.payload.desires = temp.iter().map(|v| Arc::clone(&v.1)).collect(); // extract Arc back
+
+    // keep under AIKR
+    concept.payload.desires = concept.payload.desires[..concept.payload.desires.len().min(nDesires)].to_vec();
+
+    res
+}
+
 /// limit size of memory
-pub fn limitMemory(mem: &mut Mem, nConcepts: usize) {
+///
+/// returns the names of the forgotten concepts
+pub fn limitMemory(mem: &mut Mem, nConcepts: usize) -> Vec<Term> {
     if true { // DEBUG - count concepts and beliefs
         let mut cnt_concepts = 0; // count of concepts
         let mut cnt_beliefs = 0; // count of beliefs
@@ -149,6 +243,7 @@ pub fn limitMemory(mem: &mut Mem, nConcepts: usize) {
                     cnt_concepts+=1;
                     cnt_beliefs+=concept.payload.beliefsByExp.len();
                     cnt_beliefs+=concept.payload.beliefsByUsage.len();
+                    cnt_beliefs+=concept.payload.desires.len();
                 }
                 None => {
                     println!("INTERNAL ERROR - couldn't aquire arc!");
@@ -160,7 +255,7 @@ pub fn limitMemory(mem: &mut Mem, nConcepts: usize) {
     }
     
     if mem.concepts.len() <= nConcepts {
-        return; // not enough concepts to limit
+        return vec![]; // not enough concepts to limit
     }
 
     let mut concepts: Vec<(Arc<Concept>, f64)> = Vec::new(); // concept with rating
@@ -173,6 +268,10 @@ pub fn limitMemory(mem: &mut Mem, nConcepts: usize) {
                     let iBeliefGuard = iBelief.read();
                     rating = rating.max(calcExp(&retTv(&iBeliefGuard).unwrap()));
                 }
+                for iDesire in &concept.payload.desires {
+                    let iDesireGuard = iDesire.read();
+                    rating = rating.max(calcExp(&retTv(&iDesireGuard).unwrap()));
+                }
             }
             None => {
                 println!("INTERNAL ERROR - couldn't aquire arc!");
@@ -181,26 +280,47 @@ pub fn limitMemory(mem: &mut Mem, nConcepts: usize) {
 
         concepts.push((Arc::clone(&iConcept), rating));
     }
-    mem.concepts = HashMap::new();
+    mem.concepts = ConceptMap::default();
 
     // sort
     concepts.sort_by(|(_, aRating), (_, bRating)| bRating.partial_cmp(aRating).unwrap());
 
     // limit
+    let forgotten:Vec<Term> = concepts[concepts.len().min(nConcepts)..].iter().map(|(iConcept, _rating)| iConcept.name.term().clone()).collect();
     concepts = concepts[..concepts.len().min(nConcepts)].to_vec();
 
     // put back
     for (iConcept, _rating) in &concepts {
-        let name:Term = iConcept.name.clone();
-        mem.concepts.insert(name.clone(), Arc::clone(&iConcept));
+        mem.concepts.insert(iConcept.name.clone(), Arc::clone(&iConcept));
     }
+
+    forgotten
+}
+
+/// returns the key of the concept of the term, is used to insert a new concept
+///
+/// variables are standardized, so alpha-equivalent terms return the same key
+pub fn retConceptKey(t:&Term) -> TermId {
+    if checkHasVar(t) {TermId::new(&standardizeVars(t))} else {TermId::new(t)}
+}
+
+/// returns the concept of the term
+///
+/// variables are standardized, so alpha-equivalent terms return the same concept
+pub fn retConcept<'a>(mem:&'a Mem, t:&Term) -> Option<&'a Arc<Concept>> {
+    if checkHasVar(t) {retByTerm(&mem.concepts, &standardizeVars(t))} else {retByTerm(&mem.concepts, t)}
+}
+
+/// returns the concept of the term for modification
+pub fn retConceptMut<'a>(mem:&'a mut Mem, t:&Term) -> Option<&'a mut Arc<Concept>> {
+    if checkHasVar(t) {retByTermMut(&mut mem.concepts, &standardizeVars(t))} else {retByTermMut(&mut mem.concepts, t)}
 }
 
 /// return beliefs of concept by term
 ///
 /// doesn't examine memory for subterms!
 pub fn ret_beliefs_of_concept<'a>(mem: &'a Mem, selTerm: &'a Term) -> Option< std::iter::Chain<std::slice::Iter<'a, Arc<parking_lot::lock_api::RwLock<parking_lot::RawRwLock, crate::NarSentence::Sentence>>>, std::slice::Iter<'a, Arc<parking_lot::lock_api::RwLock<parking_lot::RawRwLock, crate::NarSentence::Sentence>>>> > {
-    match mem.concepts.get(&selTerm) {
+    match retConcept(mem, selTerm) {
         Some(concept) => {
             Some(concept.payload.beliefsByExp.iter().chain(
                 concept.payload.beliefsByUsage.iter()
@@ -212,6 +332,16 @@ pub fn ret_beliefs_of_concept<'a>(mem: &'a Mem, selTerm: &'a Term) -> Option< st
     } 
 }
 
+/// return desires of concept by term
+///
+/// doesn't examine memory for subterms!
+pub fn ret_desires_of_concept(mem: &Mem, selTerm: &Term) -> Vec<Arc<RwLock<Sentence>>> {
+    match retConcept(mem, selTerm) {
+        Some(concept) => concept.payload.desires.clone(),
+        None => vec![], // concept doesn't exist
+    }
+}
+
 /// return non-unique beliefs by terms and it's subterms
 pub fn ret_beliefs_by_terms_nonunique(narMem:&Mem, terms:&[Term]) -> Vec<Arc<RwLock<Sentence>>> {
     let mut res:Vec<Arc<RwLock<Sentence>>> = vec![];
@@ -233,3 +363,41 @@ pub fn ret_beliefs_by_terms_nonunique(narMem:&Mem, terms:&[Term]) -> Vec<Arc<RwL
     
     res
 }
+
+/// exports all beliefs in beliefsByExp as narsese with TV, one line per belief
+///
+/// beliefs which are stored in multiple concepts are only exported once, lines are sorted
+pub fn exportNarsese(mem:&Mem) -> Vec<String> {
+    exportNarsese2(mem, &[])
+}
+
+/// exports all beliefs in beliefsByExp as narsese with TV, one line per belief
+///
+/// /param expIntervalsTable table of the intervals of procedural evidence, the interval of =/> is written as the last element of the sequence if it is given
+pub fn exportNarsese2(mem:&Mem, expIntervalsTable:&[i64]) -> Vec<String> {
+    let mut visited:HashSet<usize> = HashSet::new(); // usage of beliefs which were already exported, is shared between the copies of a belief
+    let mut res:Vec<String> = vec![];
+    for iConcept in mem.concepts.values() {
+        for iBelief in &iConcept.payload.beliefsByExp {
+            let belief:Sentence = iBelief.read().clone();
+            if !visited.insert(Arc::as_ptr(&belief.usage) as usize) {
+                continue; // was already exported
+            }
+
+            let mut belief2:Sentence = belief.clone();
+            if let (Some(expDt), Term::Stmt(Copula::PREDIMPL, subj, pred)) = (belief.expDt, &*belief.term) {
+                if (expDt as usize) < expIntervalsTable.len() {
+                    let mut events:Vec<Box<Term>> = match &**subj {
+                        Term::Seq(seq) => seq.clone(),
+                        _ => vec![subj.clone()]
+                    };
+                    events.push(Box::new(Term::Interval(expIntervalsTable[expDt as usize])));
+                    belief2.term = Arc::new(Term::Stmt(Copula::PREDIMPL, Box::new(Term::Seq(events)), pred.clone()));
+                }
+            }
+            res.push(convSentenceTermPunctToStr2(&belief2, true, true));
+        }
+    }
+    res.sort();
+    res
+}
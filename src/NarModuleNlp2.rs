@@ -48,7 +48,7 @@ pub fn processInternal(natural:&String, isQuestion:&mut bool)->Option<Sentence>
                 }
 
                 let term:Term = s(Copula::INH, &Term::SetExt( vec![Box::new(p2(&Term::Name(format!("WORDEN{}", iToken)), &term))]), &Term::Name("RELrepresent".to_string()));
-                inputT(&mut workerNar, &term, EnumPunctation::JUGEMENT, &Tv{f:1.0,c:0.998});
+                inputT(&mut workerNar, &term, EnumPunctation::JUGEMENT, &Tv{f:1.0,c:0.998}).unwrap();
             }
 
             let iTokenReplaced = iToken.replace("'","_"); // make parsable
@@ -126,7 +126,7 @@ pub fn process(parentNar: &mut Nar, natural:&String) {
                     }
                 }
 
-                inputT(parentNar, &prod1, punct, &Tv{f:1.0,c:0.9});
+                inputT(parentNar, &prod1, punct, &Tv{f:1.0,c:0.9}).unwrap();
             },
             _ => {
                 // term doesn't fit expected structure!
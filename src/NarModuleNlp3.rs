@@ -28,6 +28,8 @@ pub fn process(parentNar: &mut Nar, natural:&String) {
     for i_relation in &relations {
         let i_narsese:String = ModNlpA::conv_rel_to_narsese(i_relation);
         println!("// NLP.DBG {}", i_narsese);
-        inputN(parentNar, &i_narsese);
+        if let Err(err) = inputN(parentNar, &i_narsese) {
+            println!("// NLP.DBG ERR - {}", err);
+        }
     }
 }
@@ -0,0 +1,61 @@
+//! observer of events of the reasoner
+//!
+//! allows embedders to listen to what the NAR is doing without parsing printed output
+
+use std::sync::Arc;
+use parking_lot::RwLock;
+
+use crate::Term::*;
+use crate::NarSentence::*;
+
+/// receives events of the reasoner
+///
+/// all methods have a empty default implementation so implementations only have to implement the events they are interested in
+// PUBLICAPI
+pub trait NarObserver: Sync + Send {
+    /// called when a sentence was accepted as input
+    fn inputAccepted(&mut self, _sentence:&Sentence) {}
+
+    /// called when the declarative reasoner derived a conclusion
+    /// /param premises are the premises which were used to derive the conclusion, the first one is the primary premise
+    fn derived(&mut self, _conclusion:&Sentence, _premises:&[Sentence]) {}
+
+    /// called when a better answer to a question was found
+    fn answered(&mut self, _question:&Sentence, _answer:&Sentence) {}
+
+    /// called when a op was executed
+    /// /param exp is the expectation of the goal which lead to the decision, is None for motor babbling
+    fn opExecuted(&mut self, _op:&Term, _exp:Option<f64>) {}
+
+    /// called when a anticipated event did happen
+    /// /param evidence is the evidence which lead to the anticipation
+    fn anticipationConfirmed(&mut self, _evidence:&Sentence) {}
+
+    /// called when a anticipated event didn't happen before the deadline
+    /// /param evidence is the evidence which lead to the anticipation
+    fn anticipationFailed(&mut self, _evidence:&Sentence) {}
+
+    /// called when a event satisfied a goal
+    fn goalSatisfied(&mut self, _goal:&Sentence) {}
+
+    /// called when a concept was removed from memory under AIKR
+    fn conceptForgotten(&mut self, _name:&Term) {}
+}
+
+/// shared list of all registered observers
+pub type Observers = Arc<RwLock< Vec<Arc<RwLock< dyn NarObserver>>> >>;
+
+/// creates a empty list of observers
+pub fn makeObservers() -> Observers {
+    Arc::new(RwLock::new(vec![]))
+}
+
+/// calls the function for every registered observer
+///
+/// the list isn't locked while the observers are called, so observers can register other observers
+pub fn notify<F>(observers:&Observers, mut f:F) where F: FnMut(&mut dyn NarObserver) {
+    let observers2:Vec<Arc<RwLock<dyn NarObserver>>> = observers.read().clone();
+    for iObserver in observers2.iter() {
+        f(&mut *iObserver.write());
+    }
+}
@@ -0,0 +1,660 @@
+//! persistence of the state of a NAR to a SQLite database
+//!
+//! stores the concepts of the declarative and procedural memory, the goals, the names of the registered ops and the counters
+
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use parking_lot::RwLock;
+use rusqlite::{Connection, params};
+
+use crate::Term::*;
+use crate::TermIntern::TermId;
+use crate::Tv::Tv;
+use crate::NarSentence::*;
+use crate::NarStamp::*;
+use crate::NarMem;
+use crate::NarProc;
+use crate::NarGoalSystem;
+use crate::NarWorkingCycle::flushTasks;
+use crate::Nar::Nar;
+use crate::OpLib;
+
+/// version of the layout of the database, is incremented when the layout of a released version changes
+const VERSION:i64 = 1;
+
+/// error which happened while saving or loading
+// PUBLICAPI
+#[derive(Debug)]
+pub enum NarPersistError {
+    Sql(rusqlite::Error), // error of the database
+    BadTerm(String), // stored term couldn't get decoded
+    BadVersion(i64), // database was written with a different layout
+}
+
+impl fmt::Display for NarPersistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NarPersistError::Sql(err) => write!(f, "database error: {}", err),
+            NarPersistError::BadTerm(term) => write!(f, "couldn't decode stored term `{}`", term),
+            NarPersistError::BadVersion(version) => write!(f, "unsupported version {} of database, expected {}", version, VERSION),
+        }
+    }
+}
+
+impl std::error::Error for NarPersistError {}
+
+impl From<rusqlite::Error> for NarPersistError {
+    fn from(err: rusqlite::Error) -> NarPersistError {
+        NarPersistError::Sql(err)
+    }
+}
+
+/// saves the state of the NAR to the database at the path, existing state in the database is overwritten
+// PUBLICAPI
+pub fn save(nar:&Nar, path:&String) -> Result<(), NarPersistError> {
+    let mut conn = Connection::open(path)?;
+
+    let tx = conn.transaction()?;
+    for iTable in &["meta", "beliefs", "goals", "standingGoals", "ops"] {
+        tx.execute(&format!("DROP TABLE IF EXISTS {}", iTable), [])?;
+    }
+    tx.execute("CREATE TABLE meta (key TEXT PRIMARY KEY, value INTEGER NOT NULL)", [])?;
+    // mem: "decl" or "proc", list: "exp", "usage" or "desire", idx: index in the list of the concept
+    tx.execute(
+        "CREATE TABLE beliefs (
+            mem TEXT NOT NULL, concept TEXT NOT NULL, list TEXT NOT NULL, idx INTEGER NOT NULL,
+            term TEXT NOT NULL, punct TEXT NOT NULL, t INTEGER, expDt INTEGER,
+            eviType TEXT, f REAL, c REAL, pos INTEGER, cnt INTEGER,
+            stamp TEXT NOT NULL, usageId INTEGER NOT NULL, lastUsed INTEGER NOT NULL, useCount INTEGER NOT NULL
+        )", [])?;
+    // columns prefixed with "ev" are the evidence of the goal, they are NULL if the goal has no evidence
+    tx.execute(
+        "CREATE TABLE goals (
+            term TEXT NOT NULL, t INTEGER, f REAL NOT NULL, c REAL NOT NULL, stamp TEXT NOT NULL,
+            createTime INTEGER NOT NULL, depth INTEGER NOT NULL, desirability REAL NOT NULL,
+            evTerm TEXT, evExpDt INTEGER, evPos INTEGER, evCnt INTEGER, evStamp TEXT
+        )", [])?;
+    tx.execute(
+        "CREATE TABLE standingGoals (
+            term TEXT NOT NULL, f REAL NOT NULL, c REAL NOT NULL, stamp TEXT NOT NULL, desirability REAL NOT NULL
+        )", [])?;
+    tx.execute("CREATE TABLE ops (name TEXT NOT NULL)", [])?;
+
+    { // counters
+        let memGuard = nar.mem.read();
+        let sharedGuard = memGuard.shared.read();
+        let counters:Vec<(&str, i64)> = vec![
+            ("version", VERSION),
+            ("stampIdCounter", sharedGuard.stampIdCounter.load(Ordering::SeqCst)),
+            ("taskIdCounter", sharedGuard.taskIdCounter.load(Ordering::SeqCst)),
+            ("cycleCounter", sharedGuard.cycleCounter.load(Ordering::SeqCst)),
+            ("currentTime", nar.currentTime.load(Ordering::SeqCst)),
+            ("procT", nar.procNar.t),
+        ];
+        for (key, value) in counters {
+            tx.execute("INSERT INTO meta (key, value) VALUES (?1, ?2)", params![key, value])?;
+        }
+    }
+
+    let mut usageIds:HashMap<usize, i64> = HashMap::new(); // id of usage by address, usage can be shared between beliefs
+    saveMem(&tx, "decl", &nar.mem.read().shared.read().mem.read(), &mut usageIds)?;
+    saveMem(&tx, "proc", &nar.procNar.evidenceMem.read(), &mut usageIds)?;
+
+    for iEntry in &NarGoalSystem::retEntries(&nar.procNar.goalSystem.read()) {
+        let entry = iEntry.read();
+        let tv:Tv = retTv(&entry.sentence).unwrap();
+        let evidence:Option<Sentence> = entry.evidence.as_ref().map(|e| e.read().clone());
+        let (evPos, evCnt) = match evidence.as_ref().and_then(|e| e.evi.clone()) {
+            Some(Evidence::CNT{pos,cnt}) => (Some(pos), Some(cnt)),
+            _ => (None, None)
+        };
+        tx.execute(
+            "INSERT INTO goals (term, t, f, c, stamp, createTime, depth, desirability, evTerm, evExpDt, evPos, evCnt, evStamp)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![
+                encodeTerm(&entry.sentence.term), entry.sentence.t, tv.f, tv.c, encodeStamp(&entry.sentence.stamp),
+                entry.createTime, entry.depth, entry.desirability as f64,
+                evidence.as_ref().map(|e| encodeTerm(&e.term)), evidence.as_ref().and_then(|e| e.expDt), evPos, evCnt, evidence.as_ref().map(|e| encodeStamp(&e.stamp)),
+            ])?;
+    }
+
+    for (iGoal, iDesirability) in &nar.procNar.goalSystem.read().standingGoals {
+        let tv:Tv = retTv(&iGoal).unwrap();
+        tx.execute(
+            "INSERT INTO standingGoals (term, f, c, stamp, desirability) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![encodeTerm(&iGoal.term), tv.f, tv.c, encodeStamp(&iGoal.stamp), *iDesirability as f64])?;
+    }
+
+    for iOp in &nar.procNar.ops {
+        tx.execute("INSERT INTO ops (name) VALUES (?1)", params![iOp.retName()])?;
+    }
+
+    tx.commit()?;
+    Ok(())
+}
+
+fn saveMem(conn:&Connection, memName:&str, mem:&NarMem::Mem, usageIds:&mut HashMap<usize, i64>) -> Result<(), NarPersistError> {
+    let mut stmt = conn.prepare(
+        "INSERT INTO beliefs (mem, concept, list, idx, term, punct, t, expDt, eviType, f, c, pos, cnt, stamp, usageId, lastUsed, useCount)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)")?;
+
+    for (iName, iConcept) in &mem.concepts {
+        let conceptStr:String = encodeTerm(iName);
+        for (listName, list) in &[("exp", &iConcept.payload.beliefsByExp), ("usage", &iConcept.payload.beliefsByUsage), ("desire", &iConcept.payload.desires)] {
+            for (idx, iBelief) in list.iter().enumerate() {
+                let belief = iBelief.read();
+
+                let nextUsageId = usageIds.len() as i64;
+                let usageId:i64 = *usageIds.entry(Arc::as_ptr(&belief.usage) as usize).or_insert(nextUsageId);
+                let usage = belief.usage.read();
+
+                let (eviType, f, c, pos, cnt) = match &belief.evi {
+                    Some(Evidence::TV(tv)) => (Some("tv"), Some(tv.f), Some(tv.c), None, None),
+                    Some(Evidence::CNT{pos,cnt}) => (Some("cnt"), None, None, Some(*pos), Some(*cnt)),
+                    None => (None, None, None, None, None),
+                };
+
+                stmt.execute(params![
+                    memName, conceptStr, listName, idx as i64,
+                    encodeTerm(&belief.term), encodePunct(belief.punct), belief.t, belief.expDt,
+                    eviType, f, c, pos, cnt,
+                    encodeStamp(&belief.stamp), usageId, usage.lastUsed, usage.useCount,
+                ])?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// loads the state of the NAR from the database at the path, replaces the knowledge and goals of the NAR
+///
+/// ops should be registered before loading, ops which are stored but not registered are registered as NOP ops
+// PUBLICAPI
+pub fn load(nar:&mut Nar, path:&String) -> Result<(), NarPersistError> {
+    let conn = Connection::open(path)?;
+
+    let mut meta:HashMap<String, i64> = HashMap::new();
+    {
+        let mut stmt = conn.prepare("SELECT key, value FROM meta")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?;
+        for iRow in rows {
+            let (key, value) = iRow?;
+            meta.insert(key, value);
+        }
+    }
+    let version:i64 = *meta.get("version").unwrap_or(&0);
+    if version != VERSION {
+        return Err(NarPersistError::BadVersion(version));
+    }
+
+    // read everything before the state of the NAR is changed, so the NAR is unchanged if the database is broken
+    let mut usages:HashMap<i64, Arc<RwLock<Usage>>> = HashMap::new();
+    let declMem:NarMem::Mem = loadMem(&conn, "decl", &mut usages)?;
+    let procMem:NarMem::Mem = loadMem(&conn, "proc", &mut usages)?;
+
+    let mut goals:Vec<(Sentence, Option<Sentence>, i64, i64, f32)> = vec![]; // goal, evidence, createTime, depth, desirability
+    {
+        let mut stmt = conn.prepare("SELECT term, t, f, c, stamp, createTime, depth, desirability, evTerm, evExpDt, evPos, evCnt, evStamp FROM goals")?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let mut goal:Sentence = newEternalSentenceByTv(&decodeTerm(&row.get::<_, String>(0)?)?, EnumPunctation::GOAL, &Tv{f:row.get(2)?, c:row.get(3)?}, decodeStamp(&row.get::<_, String>(4)?)?);
+            goal.t = row.get(1)?;
+
+            let evidence:Option<Sentence> = match row.get::<_, Option<String>>(8)? {
+                Some(evTerm) => {
+                    let mut evidence:Sentence = newEternalSentenceByTv(&decodeTerm(&evTerm)?, EnumPunctation::JUGEMENT, &Tv{f:1.0, c:0.0}, decodeStamp(&row.get::<_, String>(12)?)?);
+                    evidence.expDt = row.get(9)?;
+                    evidence.evi = Some(Evidence::CNT{pos:row.get(10)?, cnt:row.get(11)?});
+                    Some(evidence)
+                },
+                None => None
+            };
+
+            goals.push((goal, evidence, row.get(5)?, row.get(6)?, row.get::<_, f64>(7)? as f32));
+        }
+    }
+
+    let mut standingGoals:Vec<(Arc<Sentence>, f32)> = vec![]; // goal, desirability
+    {
+        let mut stmt = conn.prepare("SELECT term, f, c, stamp, desirability FROM standingGoals")?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let goal:Sentence = newEternalSentenceByTv(&decodeTerm(&row.get::<_, String>(0)?)?, EnumPunctation::GOAL, &Tv{f:row.get(1)?, c:row.get(2)?}, decodeStamp(&row.get::<_, String>(3)?)?);
+            standingGoals.push((Arc::new(goal), row.get::<_, f64>(4)? as f32));
+        }
+    }
+
+    let mut opNames:Vec<String> = vec![];
+    {
+        let mut stmt = conn.prepare("SELECT name FROM ops")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        for iRow in rows {
+            opNames.push(iRow?);
+        }
+    }
+
+    // replace state of NAR
+    {
+        let memGuard = nar.mem.read();
+        flushTasks(&memGuard);
+        let sharedGuard = memGuard.shared.read();
+        *sharedGuard.mem.write() = declMem;
+        sharedGuard.stampIdCounter.store(*meta.get("stampIdCounter").unwrap_or(&0), Ordering::SeqCst);
+        sharedGuard.taskIdCounter.store(*meta.get("taskIdCounter").unwrap_or(&0), Ordering::SeqCst);
+        sharedGuard.cycleCounter.store(*meta.get("cycleCounter").unwrap_or(&0), Ordering::SeqCst);
+    }
+    nar.currentTime.store(*meta.get("currentTime").unwrap_or(&0), Ordering::SeqCst);
+    nar.procNar.t = *meta.get("procT").unwrap_or(&0);
+    *nar.procNar.evidenceMem.write() = procMem;
+    nar.procNar.anticipatedEvents.clear(); // anticipations point to evidence which doesn't exist anymore
+
+    { // flush goal system
+        let mut goalSystem = nar.procNar.goalSystem.write();
+        goalSystem.activeSet.set.clear();
+        for iBatch in &goalSystem.batchesByDepth {
+            iBatch.write().groups.clear();
+        }
+        goalSystem.entriesByTerm.clear();
+        goalSystem.standingGoals = standingGoals;
+    }
+    for (goal, evidence, createTime, depth, desirability) in goals {
+        // link evidence of goal to evidence in memory so that anticipation revises the evidence in memory
+        let evidence2:Option<Arc<RwLock<Sentence>>> = evidence.map(|evidence| {
+            let evidenceMemGuard = nar.procNar.evidenceMem.read();
+            for iBelief in &NarMem::ret_beliefs_by_terms_nonunique(&evidenceMemGuard, &[(*evidence.term).clone()]) {
+                let belief = iBelief.read();
+                if checkEqTerm(&belief.term, &evidence.term) && belief.expDt == evidence.expDt && checkSame(&belief.stamp, &evidence.stamp) {
+                    return Arc::clone(iBelief);
+                }
+            }
+            Arc::new(RwLock::new(evidence))
+        });
+        NarGoalSystem::addEntry3(&nar.procNar.goalSystem, &nar.mem.read(), createTime, Arc::new(goal), evidence2, depth, desirability);
+    }
+
+    for iOpName in &opNames {
+        if NarProc::ret_op_by_name(&nar.procNar, iOpName).is_none() {
+            nar.procNar.ops.push(Rc::new(Box::new(OpLib::OpNop{name:iOpName.clone()})));
+        }
+    }
+
+    Ok(())
+}
+
+fn loadMem(conn:&Connection, memName:&str, usages:&mut HashMap<i64, Arc<RwLock<Usage>>>) -> Result<NarMem::Mem, NarPersistError> {
+    let mut mem:NarMem::Mem = NarMem::make();
+
+    let mut stmt = conn.prepare(
+        "SELECT concept, list, term, punct, t, expDt, eviType, f, c, pos, cnt, stamp, usageId, lastUsed, useCount
+         FROM beliefs WHERE mem = ?1 ORDER BY concept, list, idx")?;
+    let mut rows = stmt.query(params![memName])?;
+    let mut conceptTerms:HashMap<String, TermId> = HashMap::new(); // cache to not decode the name for every belief
+    while let Some(row) = rows.next()? {
+        let conceptStr:String = row.get(0)?;
+        if !conceptTerms.contains_key(&conceptStr) {
+            conceptTerms.insert(conceptStr.clone(), TermId::new(&decodeTerm(&conceptStr)?));
+        }
+        let conceptName:&TermId = &conceptTerms[&conceptStr];
+
+        let usageId:i64 = row.get(12)?;
+        let (lastUsed, useCount):(i64, i64) = (row.get(13)?, row.get(14)?);
+        let usage = usages.entry(usageId).or_insert_with(|| Arc::new(RwLock::new(Usage{lastUsed:lastUsed, useCount:useCount})));
+
+        let evi:Option<Evidence> = match row.get::<_, Option<String>>(6)?.as_deref() {
+            Some("tv") => Some(Evidence::TV(Tv{f:row.get(7)?, c:row.get(8)?})),
+            Some("cnt") => Some(Evidence::CNT{pos:row.get(9)?, cnt:row.get(10)?}),
+            _ => None,
+        };
+
+        let belief = Sentence {
+            term:Arc::new(decodeTerm(&row.get::<_, String>(2)?)?),
+            t:row.get(4)?,
+            punct:decodePunct(&row.get::<_, String>(3)?),
+            stamp:decodeStamp(&row.get::<_, String>(11)?)?,
+            expDt:row.get(5)?,
+            evi:evi,
+            usage:Arc::clone(usage),
+        };
+
+        if !mem.concepts.contains_key(conceptName) {
+            mem.concepts.insert(conceptName.clone(), Arc::new(NarMem::Concept{
+                name:conceptName.clone(),
+                payload:NarMem::Payload{beliefsByExp:vec![], beliefsByUsage:vec![], desires:vec![]},
+            }));
+        }
+        let concept = Arc::get_mut(mem.concepts.get_mut(conceptName).unwrap()).unwrap(); // concept was just created and isn't shared
+        let list:String = row.get(1)?;
+        if list == "exp" {
+            concept.payload.beliefsByExp.push(Arc::new(RwLock::new(belief)));
+        }
+        else if list == "desire" {
+            concept.payload.desires.push(Arc::new(RwLock::new(belief)));
+        }
+        else {
+            concept.payload.beliefsByUsage.push(Arc::new(RwLock::new(belief)));
+        }
+    }
+
+    Ok(mem)
+}
+
+fn encodePunct(punct:EnumPunctation) -> &'static str {
+    match punct {
+        EnumPunctation::JUGEMENT => ".",
+        EnumPunctation::QUESTION => "?",
+        EnumPunctation::GOAL => "!",
+        EnumPunctation::QUEST => "@",
+    }
+}
+
+fn decodePunct(punct:&str) -> EnumPunctation {
+    match punct {
+        "?" => EnumPunctation::QUESTION,
+        "!" => EnumPunctation::GOAL,
+        "@" => EnumPunctation::QUEST,
+        _ => EnumPunctation::JUGEMENT,
+    }
+}
+
+fn encodeStamp(stamp:&Stamp) -> String {
+    stamp.ids.iter().map(|id| id.to_string()).collect::<Vec<String>>().join(",")
+}
+
+fn decodeStamp(stamp:&str) -> Result<Stamp, NarPersistError> {
+    if stamp.len() == 0 {
+        return Ok(newStamp(&vec![]));
+    }
+    let ids:Result<Vec<i64>, _> = stamp.split(',').map(|id| id.parse::<i64>()).collect();
+    match ids {
+        Ok(ids) => Ok(newStamp(&ids)),
+        Err(_) => Err(NarPersistError::BadTerm(stamp.to_string())),
+    }
+}
+
+/// encodes term as s-expression, is unambiguous in contrast to narsese which is printed for humans
+///
+/// ex: <a --> (b * c)> is encoded as (inh "a" (prod "b" "c"))
+fn encodeTerm(term:&Term) -> String {
+    // tag and the values which are not stored in the components
+    let head:String = match term {
+        Term::Name(name) => {return encodeName(name);},
+        Term::Stmt(copula, _, _) => match copula {Copula::SIM=>"sim", Copula::INH=>"inh", Copula::PREDIMPL=>"predimpl", Copula::IMPL=>"impl", Copula::EQUIV=>"equiv"}.to_string(),
+        Term::Seq(_) => "seq".to_string(),
+        Term::SetInt(_) => "setint".to_string(),
+        Term::SetExt(_) => "setext".to_string(),
+        Term::QVar(name) => format!("qvar {}", encodeName(name)),
+        Term::DepVar(name) => format!("depvar {}", encodeName(name)),
+        Term::IndepVar(name) => format!("indepvar {}", encodeName(name)),
+        Term::Conj(_) => "conj".to_string(),
+        Term::Prod(_) => "prod".to_string(),
+        Term::Img(_, idx, _) => format!("img {}", idx),
+        Term::IntInt(_) => "intint".to_string(),
+        Term::ExtInt(_) => "extint".to_string(),
+        Term::Par(_) => "par".to_string(),
+        Term::Neg(_) => "neg".to_string(),
+        Term::DiffExt(_, _) => "diffext".to_string(),
+        Term::DiffInt(_, _) => "diffint".to_string(),
+        Term::Interval(dt) => format!("interval {}", dt),
+    };
+
+    let mut res = format!("({}", head);
+    for iChild in term.children() {
+        res += " ";
+        res += &encodeTerm(iChild);
+    }
+    res + ")"
+}
+
+fn encodeName(name:&str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// decodes term which was encoded with encodeTerm()
+fn decodeTerm(encoded:&str) -> Result<Term, NarPersistError> {
+    let chars:Vec<char> = encoded.chars().collect();
+    let mut idx:usize = 0;
+    match decodeTermAt(&chars, &mut idx) {
+        Some(term) if idx == chars.len() => Ok(term),
+        _ => Err(NarPersistError::BadTerm(encoded.to_string())),
+    }
+}
+
+fn decodeTermAt(chars:&[char], idx:&mut usize) -> Option<Term> {
+    if *idx >= chars.len() {
+        return None;
+    }
+    if chars[*idx] == '"' {
+        return decodeNameAt(chars, idx).map(|name| Term::Name(name));
+    }
+    if chars[*idx] != '(' {
+        return None;
+    }
+    *idx += 1;
+
+    // read tag
+    let mut tag = String::new();
+    while *idx < chars.len() && chars[*idx].is_ascii_alphabetic() {
+        tag.push(chars[*idx]);
+        *idx += 1;
+    }
+
+    let res:Term = match tag.as_str() {
+        "qvar" | "depvar" | "indepvar" => {
+            skipSpace(chars, idx)?;
+            let name = decodeNameAt(chars, idx)?;
+            match tag.as_str() {"qvar"=>Term::QVar(name), "depvar"=>Term::DepVar(name), _=>Term::IndepVar(name)}
+        },
+        "interval" => {
+            skipSpace(chars, idx)?;
+            Term::Interval(decodeIntAt(chars, idx)?)
+        },
+        _ => {
+            let imgIdx:Option<usize> = if tag == "img" {
+                skipSpace(chars, idx)?;
+                Some(decodeIntAt(chars, idx)? as usize)
+            } else {None};
+            let mut children:Vec<Box<Term>> = decodeElementsAt(chars, idx)?;
+
+            // helper to split the components of compounds with a fixed number of components
+            let mut retFixed = |n:usize| -> Option<Vec<Box<Term>>> {
+                if children.len() == n {Some(std::mem::take(&mut children))} else {None}
+            };
+
+            match tag.as_str() {
+                "sim" | "inh" | "predimpl" | "impl" | "equiv" => {
+                    let copula = match tag.as_str() {"sim"=>Copula::SIM, "inh"=>Copula::INH, "predimpl"=>Copula::PREDIMPL, "impl"=>Copula::IMPL, _=>Copula::EQUIV};
+                    let mut it = retFixed(2)?.into_iter();
+                    Term::Stmt(copula, it.next()?, it.next()?)
+                },
+                "diffext" | "diffint" => {
+                    let mut it = retFixed(2)?.into_iter();
+                    let (a, b) = (it.next()?, it.next()?);
+                    if tag == "diffext" {Term::DiffExt(a, b)} else {Term::DiffInt(a, b)}
+                },
+                "neg" => Term::Neg(retFixed(1)?.into_iter().next()?),
+                "img" => {
+                    if children.len() == 0 {
+                        return None; // relation is missing
+                    }
+                    let rel = children.remove(0);
+                    Term::Img(rel, imgIdx?, children)
+                },
+                "seq" => Term::Seq(children),
+                "setint" => Term::SetInt(children),
+                "setext" => Term::SetExt(children),
+                "conj" => Term::Conj(children),
+                "prod" => Term::Prod(children),
+                "intint" => Term::IntInt(children),
+                "extint" => Term::ExtInt(children),
+                "par" => Term::Par(children),
+                _ => {return None;}
+            }
+        },
+    };
+
+    if *idx < chars.len() && chars[*idx] == ')' {
+        *idx += 1;
+        Some(res)
+    }
+    else {
+        None
+    }
+}
+
+/// decodes the elements of a compound until the closing bracket
+fn decodeElementsAt(chars:&[char], idx:&mut usize) -> Option<Vec<Box<Term>>> {
+    let mut res = vec![];
+    while *idx < chars.len() && chars[*idx] == ' ' {
+        *idx += 1;
+        res.push(Box::new(decodeTermAt(chars, idx)?));
+    }
+    Some(res)
+}
+
+fn decodeNameAt(chars:&[char], idx:&mut usize) -> Option<String> {
+    if *idx >= chars.len() || chars[*idx] != '"' {
+        return None;
+    }
+    *idx += 1;
+    let mut res = String::new();
+    while *idx < chars.len() {
+        match chars[*idx] {
+            '"' => {
+                *idx += 1;
+                return Some(res);
+            },
+            '\\' if *idx+1 < chars.len() => {
+                res.push(chars[*idx+1]);
+                *idx += 2;
+            },
+            c => {
+                res.push(c);
+                *idx += 1;
+            }
+        }
+    }
+    None // name isn't terminated
+}
+
+fn decodeIntAt(chars:&[char], idx:&mut usize) -> Option<i64> {
+    let start = *idx;
+    if *idx < chars.len() && chars[*idx] == '-' {
+        *idx += 1;
+    }
+    while *idx < chars.len() && chars[*idx].is_ascii_digit() {
+        *idx += 1;
+    }
+    chars[start..*idx].iter().collect::<String>().parse::<i64>().ok()
+}
+
+fn skipSpace(chars:&[char], idx:&mut usize) -> Option<()> {
+    if *idx < chars.len() && chars[*idx] == ' ' {
+        *idx += 1;
+        Some(())
+    }
+    else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // describes the beliefs and desires of the memory as sorted lines
+    fn describeMem(mem:&NarMem::Mem) -> Vec<String> {
+        let mut res:Vec<String> = vec![];
+        for (iName, iConcept) in &mem.concepts {
+            for (listName, list) in &[("exp", &iConcept.payload.beliefsByExp), ("usage", &iConcept.payload.beliefsByUsage), ("desire", &iConcept.payload.desires)] {
+                for iBelief in list.iter() {
+                    let belief = iBelief.read();
+                    res.push(format!("{} {} {} {:?} {} {:?}", convTermToStr(iName), listName, convSentenceTermPunctToStr(&belief, true), belief.t, encodeStamp(&belief.stamp), belief.expDt));
+                }
+            }
+        }
+        res.sort();
+        res
+    }
+
+    // describes the state of the NAR which gets persisted
+    fn describeNar(nar:&Nar) -> Vec<String> {
+        let mut res:Vec<String> = vec![];
+        res.extend(describeMem(&nar.mem.read().shared.read().mem.read()).iter().map(|iv| format!("decl {}", iv)));
+        res.extend(describeMem(&nar.procNar.evidenceMem.read()).iter().map(|iv| format!("proc {}", iv)));
+        let mut goals:Vec<String> = NarGoalSystem::retEntries(&nar.procNar.goalSystem.read()).iter().map(|iEntry| {
+            let entry = iEntry.read();
+            format!("goal {} {:?} {} {} {:?}", convSentenceTermPunctToStr(&entry.sentence, true), entry.sentence.t, entry.depth, entry.desirability, entry.evidence.as_ref().map(|e| convTermToStr(&e.read().term)))
+        }).collect();
+        goals.sort();
+        res.extend(goals);
+        for (iGoal, iDesirability) in &nar.procNar.goalSystem.read().standingGoals {
+            res.push(format!("standing {} {}", convSentenceTermPunctToStr(iGoal, true), iDesirability));
+        }
+        res.push(format!("ops {:?}", nar.procNar.ops.iter().map(|iOp| iOp.retName()).collect::<Vec<String>>()));
+        res.push(format!("counters {} {}", nar.mem.read().shared.read().stampIdCounter.load(Ordering::SeqCst), nar.procNar.t));
+        res
+    }
+
+    // state of a NAR must be the same after saving and loading it into a new NAR
+    #[test]
+    pub fn saveLoadRoundtrip() {
+        let cfg = crate::NarConfig::NarConfigBuilder::new().syncDerivation(true).build().unwrap(); // memory must not change while it is saved
+        let mut nar = crate::Nar::createNarWithConfig(cfg);
+        for iNarsese in &["<a --> b>.", "<b --> c>. {0.8 0.7}", "<(x,^left()) =/> y>. {0.9 0.6}", "z!", "<a --> b>!", "y! :|:"] {
+            crate::Nar::inputN(&mut nar, &iNarsese.to_string()).unwrap();
+        }
+        for _i in 0..20 {
+            crate::Nar::cycle(&mut nar);
+        }
+
+        let path:String = std::env::temp_dir().join(format!("nar_persist_test_{}.db", std::process::id())).to_str().unwrap().to_string();
+        save(&nar, &path).unwrap();
+        let mut nar2 = crate::Nar::createNar();
+        let res = load(&mut nar2, &path);
+        let _ = std::fs::remove_file(&path);
+        res.unwrap();
+
+        let description = describeNar(&nar);
+        assert_eq!(description.iter().any(|iv| iv.starts_with("decl <a --> b> desire")), true);
+        assert_eq!(description.iter().any(|iv| iv.starts_with("proc ")), true);
+        assert_eq!(description.iter().any(|iv| iv.starts_with("goal y!")), true);
+        assert_eq!(description.iter().any(|iv| iv.starts_with("standing z!")), true);
+        assert_eq!(describeNar(&nar2), description);
+    }
+
+    // every type of term must be decoded to the same term
+    #[test]
+    pub fn termRoundtrip() {
+        for iNarsese in &["<(a * b) --> c>.", "<{a b} <-> [c]>.", "<(a;b) =/> (a,+5,b)>.", "<(a&&(!b)) ==> c>.", "<(a|b) <=> (a&b)>.",
+                          "<(r /2 a) --> (a - b)>.", "<(a ~ b) --> c>.", "<($x * #y) --> ?z>."] {
+            let term:Term = crate::NarseseParser::parseNarsese(&iNarsese.to_string()).ok().unwrap().0;
+            let encoded:String = encodeTerm(&term);
+            assert_eq!(checkEqTerm(&decodeTerm(&encoded).unwrap(), &term), true, "{} was decoded wrong", encoded);
+        }
+        let quoted = Term::Name("a\"b\\".to_string());
+        assert_eq!(checkEqTerm(&decodeTerm(&encodeTerm(&quoted)).unwrap(), &quoted), true);
+        assert_eq!(decodeTerm("(inh \"a\")").is_err(), true); // statement needs two components
+        assert_eq!(decodeTerm("(img 1)").is_err(), true); // image needs the relation
+    }
+
+    // database with a different layout must be rejected
+    #[test]
+    pub fn badVersion() {
+        let path:String = std::env::temp_dir().join(format!("nar_persist_test_version_{}.db", std::process::id())).to_str().unwrap().to_string();
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute("DROP TABLE IF EXISTS meta", []).unwrap();
+            conn.execute("CREATE TABLE meta (key TEXT PRIMARY KEY, value INTEGER NOT NULL)", []).unwrap();
+            conn.execute("INSERT INTO meta (key, value) VALUES ('version', ?1)", params![VERSION+1]).unwrap();
+        }
+        let res = load(&mut crate::Nar::createNar(), &path);
+        let _ = std::fs::remove_file(&path);
+        match res {
+            Err(NarPersistError::BadVersion(version)) => {assert_eq!(version, VERSION+1);},
+            _ => {panic!("expected BadVersion");}
+        }
+    }
+}
@@ -19,6 +19,8 @@ use crate::Tv;
 use crate::NarInfProcedural;
 use crate::Utils::{enforce};
 use crate::NarWorkingCycle::Mem2;
+use crate::NarObserver::{Observers, makeObservers, notify};
+use crate::NarConfig::{NarConfig, createRng, RNG_STREAM_PROC, RNG_STREAM_PROC_TRACE, RNG_STREAM_PROC_WORKER};
 
 /// contains all necessary variables of a procedural NAR
 pub struct ProcNar {
@@ -66,6 +68,10 @@ pub struct ProcNar {
     pub cfg__resourceGoalDeriverPrio: f32,
     /// how ofter should event-FIFO get sampled for perception in cycle?
     pub cfg__perceptionSamplesPerStep:i64,
+    /// is storage and resource allocation independent of threads and wall clock time?
+    pub cfg__syncDerivation:bool,
+    /// number of goal derivations per step if cfg__syncDerivation is enabled
+    pub cfg__syncGoalDerivRounds:i64,
 
 
     /// how verbose is the reasoner, mainly used for debugging
@@ -87,7 +93,7 @@ pub struct ProcNar {
     pub t:i64,
 
 
-    pub rng: rand::rngs::ThreadRng,
+    pub rng: rand::rngs::StdRng,
 
     /// table with exponential intervals
     pub expIntervalsTable:Vec<i64>,
@@ -95,6 +101,9 @@ pub struct ProcNar {
     /// "goal system" - manages goals of the procedural reasoner
     pub goalSystem: Arc<RwLock<NarGoalSystem::GoalSystem>>,
 
+    /// observers which get notified about events of the reasoner
+    pub observers: Observers,
+
 
 
     // internals which are still public
@@ -140,7 +149,7 @@ pub struct TraceDefault {
     /// trace of some past events under AIKR
     pub trace: Arc<RwLock<Vec<Rc<SimpleSentence>>>>,
 
-    pub rng: Arc<RwLock<rand::rngs::ThreadRng>>,
+    pub rng: Arc<RwLock<rand::rngs::StdRng>>,
 }
 
 /// standard implementation of "Trace"
@@ -160,7 +169,10 @@ impl Trace for TraceDefault {
                     last_events
                 };
                 for i_last_event in last_events {
-                    NarGoalSystem::event_occurred(&mut nar.goalSystem.write(), &i_last_event.name);
+                    let satisfiedGoals:Vec<Arc<Sentence>> = NarGoalSystem::event_occurred(&mut nar.goalSystem.write(), &i_last_event.name);
+                    for iGoal in &satisfiedGoals {
+                        notify(&nar.observers, |o| o.goalSatisfied(iGoal));
+                    }
                 }
             }
         }
@@ -273,13 +285,18 @@ impl Trace for TraceDefault {
                                 t:None,
                                 stamp:stamp,
                                 expDt:Some(expDt),
-                                term:Arc::new(candidateTerm.clone()), // ex: (e0 &/ e1) =/> e2
+                                term:Arc::new(normalize(&candidateTerm)), // ex: (e0 &/ e1) =/> e2
                                 evi:Some(Evidence::CNT{pos:cfg__eviCnt,cnt:cfg__eviCnt}),
                                 usage:Arc::new(RwLock::new(Usage{lastUsed: 0, useCount: 0})),
                             };
                             
-                            let workerIdx = self.rng.write().gen_range(0..nar.storeWorkersTx.len());
-                            nar.storeWorkersTx[workerIdx].send((evidenceSentence, nar.t)).unwrap(); // defer actual storage to worker
+                            if nar.storeWorkersTx.len() > 0 {
+                                let workerIdx = self.rng.write().gen_range(0..nar.storeWorkersTx.len());
+                                nar.storeWorkersTx[workerIdx].send((evidenceSentence, nar.t)).unwrap(); // defer actual storage to worker
+                            }
+                            else { // storage is synchronous
+                                storeEvidence(&nar.evidenceMem, &evidenceSentence, nar.t, nar.cfg__nConceptBeliefs, &mut self.rng.write());
+                            }
                         }
                     }
                 }
@@ -329,43 +346,52 @@ impl Trace for TraceDefault {
 
 /// init and set to default values
 pub fn narInit() -> ProcNar {
+    narInit2(&NarConfig::default())
+}
+
+/// init with values of the configuration
+pub fn narInit2(cfg:&NarConfig) -> ProcNar {
     let mut nar = ProcNar {
-        cfgIntervalExpBase: 1.3,
-        cfgIntervalMax: 20,
-        cfgPerceptWindow: 2,
-        cfgDescnThreshold: 0.58,
-        cfgNMaxEvidence: 5000,
-        cfg__perceptionSamplesPerStep:4,
-        cfgEnBabbling: true,
-        cfg__nOpsMax: 1,
-        cfg__multiOpProbability: 0.2,
+        cfgIntervalExpBase: cfg.procIntervalExpBase,
+        cfgIntervalMax: cfg.procIntervalMax,
+        cfgPerceptWindow: cfg.procPerceptWindow,
+        cfgDescnThreshold: cfg.procDescnThreshold,
+        cfgNMaxEvidence: cfg.procNMaxEvidence,
+        cfg__perceptionSamplesPerStep: cfg.procPerceptionSamplesPerStep,
+        cfgEnBabbling: cfg.procEnBabbling,
+        cfg__nOpsMax: cfg.procNOpsMax,
+        cfg__multiOpProbability: cfg.procMultiOpProbability,
         //cfg__eviCnt: 3, // non-axiomatic
-        cfg__enAnticipation: true, // by default
+        cfg__enAnticipation: cfg.procEnAnticipation,
 
-        cfg__nConcepts: 1000,
-        cfg__nConceptBeliefs: 100,
+        cfg__nConcepts: cfg.procNConcepts,
+        cfg__nConceptBeliefs: cfg.procNConceptBeliefs,
 
         // compute resources
         //cfg__nGoalDeriverSamples: 1, // 3 is enough for pong
-        cfg__resourceExpectedTime: 0.08, // expected time for one inference step
-        cfg__resourceGoalDeriverPrio: 1.05, // priority of (temporal) goal derivation
+        cfg__resourceExpectedTime: cfg.procResourceExpectedTime,
+        cfg__resourceGoalDeriverPrio: cfg.procResourceGoalDeriverPrio,
+        cfg__syncDerivation: cfg.syncDerivation,
+        cfg__syncGoalDerivRounds: cfg.procSyncGoalDerivRounds,
         
-        cfgVerbosity: 0, // be silent
+        cfgVerbosity: cfg.procVerbosity,
 
         //evidence: Vec::new(),
         evidenceMem: Arc::new(RwLock::new(NarMem::make())),
 
         //trace: Vec::new(),
-        trace: Box::new(TraceDefault{trace: Arc::new(RwLock::new(vec![])),rng: Arc::new(RwLock::new(rand::thread_rng())),}),
+        trace: Box::new(TraceDefault{trace: Arc::new(RwLock::new(vec![])),rng: Arc::new(RwLock::new(createRng(cfg, RNG_STREAM_PROC_TRACE))),}),
         anticipatedEvents: Vec::new(),
         ops: Vec::new(),
         t: 0,
 
-        rng: rand::thread_rng(),
+        rng: createRng(cfg, RNG_STREAM_PROC),
 
         expIntervalsTable: Vec::new(),
 
-        goalSystem: Arc::new(RwLock::new(NarGoalSystem::makeGoalSystem(20, 8))),
+        goalSystem: Arc::new(RwLock::new(NarGoalSystem::makeGoalSystem(cfg.goalNMaxEntries, cfg.goalNMaxDepth))),
+
+        observers: makeObservers(),
 
         storeWorkers: vec![],
         storeWorkersTx: vec![],
@@ -373,15 +399,14 @@ pub fn narInit() -> ProcNar {
 
 
     // create worker which stores evidence with revision
-    for _iWorker in 0..1 {
+    for _iWorker in 0..(if cfg.syncDerivation {0} else {1}) { // no worker if storage is synchronous
         let (tx, rx) = sync_channel(4); // create channel with fixed size, reason is that we want to limit backlog!
         nar.storeWorkersTx.push(tx);
 
         let evidenceMem = Arc::clone(&nar.evidenceMem);
         let cfg__nConceptBeliefs = nar.cfg__nConceptBeliefs;
+        let mut rng = createRng(cfg, RNG_STREAM_PROC_WORKER);
         nar.storeWorkers.push(thread::spawn(move|| {
-            let mut rng = rand::thread_rng();
-            
             loop {
                 let msgRes = rx.recv();
                 if !msgRes.is_ok() {
@@ -391,51 +416,7 @@ pub fn narInit() -> ProcNar {
                 let (evidenceSentence, currentTime) = msgRes.unwrap(); // receive message
                 //println!("[STORAGE WORKER] received MSG!");
 
-                /////////
-                // STORE
-                /////////
-                let addEvidenceFlag: Arc<AtomicBool> = Arc::new(AtomicBool::new(true)); // do we need to add new evidence?
-                
-                { // scope for guard
-                    let evidenceMemGuard = evidenceMem.read();
-                    for iEEArc in &NarMem::ret_beliefs_by_terms_nonunique(&evidenceMemGuard, &[retSeqCond(&evidenceSentence.term).clone(), retPred(&evidenceSentence.term).clone()]) { // iterate over evidence where seqCond and/or pred appear
-                        let mut iEE = iEEArc.write();
-                        
-                        if !checkOverlap(&iEE.stamp, &evidenceSentence.stamp) { // evidence must no overlap!
-                            if
-                                iEE.expDt.unwrap() >= evidenceSentence.expDt.unwrap() && // check for greater because we want to count evidence for longer intervals too, because longer ones are "included"
-                                
-                                // does impl seq match?
-                                checkEqTerm(&iEE.term, &evidenceSentence.term)
-                            {
-                                iEE.stamp = merge(&iEE.stamp, &evidenceSentence.stamp);
-
-                                let (otherCntPos, otherCnt) = match evidenceSentence.evi.as_ref().unwrap() {
-                                    Evidence::CNT{pos,cnt} => {
-                                        (pos, cnt)
-                                    },
-                                    _ => {panic!("expected CNT!");}
-                                };
-
-                                match iEE.evi.as_ref().unwrap() {
-                                    Evidence::CNT{pos,cnt} => {
-                                        iEE.evi = Some(Evidence::CNT{pos:pos+otherCntPos,cnt:cnt+otherCnt}); // bump positive counter
-                                    },
-                                    _ => {panic!("expected CNT!");}
-                                }
-                                
-                                if false {println!("dbg - REV")};
-                                
-                                addEvidenceFlag.store(false, Ordering::Relaxed); // because we revised
-                            }                                
-                        }
-                    }    
-                }
-                
-                if addEvidenceFlag.load(Ordering::Relaxed) {
-                    // add evidence
-                    mem_add_evidence(Arc::clone(&evidenceMem), &evidenceSentence, cfg__nConceptBeliefs, currentTime, &mut rng);
-                }
+                storeEvidence(&evidenceMem, &evidenceSentence, currentTime, cfg__nConceptBeliefs, &mut rng);
             }
         }));
     }
@@ -459,13 +440,92 @@ pub fn narInit() -> ProcNar {
             i+=1;
         }
     }
+
+    {
+        let mut goalSystem = nar.goalSystem.write();
+        goalSystem.cfg__enGoalSatisfaction = cfg.goalEnSatisfaction;
+        goalSystem.cfg__subworkingCycle_rounds = cfg.goalSubworkingCycleRounds;
+    }
     
     nar
 }
 
+/// applies the values of the configuration which can get changed while the reasoner is running
+pub fn applyConfig(nar:&mut ProcNar, cfg:&NarConfig) {
+    nar.cfgPerceptWindow = cfg.procPerceptWindow;
+    nar.cfgDescnThreshold = cfg.procDescnThreshold;
+    nar.cfgNMaxEvidence = cfg.procNMaxEvidence;
+    nar.cfg__perceptionSamplesPerStep = cfg.procPerceptionSamplesPerStep;
+    nar.cfgEnBabbling = cfg.procEnBabbling;
+    nar.cfg__nOpsMax = cfg.procNOpsMax;
+    nar.cfg__multiOpProbability = cfg.procMultiOpProbability;
+    nar.cfg__enAnticipation = cfg.procEnAnticipation;
+    nar.cfg__nConcepts = cfg.procNConcepts;
+    nar.cfg__resourceExpectedTime = cfg.procResourceExpectedTime;
+    nar.cfg__resourceGoalDeriverPrio = cfg.procResourceGoalDeriverPrio;
+    nar.cfg__syncGoalDerivRounds = cfg.procSyncGoalDerivRounds;
+    nar.cfgVerbosity = cfg.procVerbosity;
+
+    let mut goalSystem = nar.goalSystem.write();
+    goalSystem.nMaxEntries = cfg.goalNMaxEntries;
+    goalSystem.cfg__enGoalSatisfaction = cfg.goalEnSatisfaction;
+    goalSystem.cfg__subworkingCycle_rounds = cfg.goalSubworkingCycleRounds;
+}
+
+/// stores evidence in memory, revises it with existing evidence if possible
+///
+/// is called by the storage worker or directly if storage is synchronous
+pub fn storeEvidence(evidenceMem:&Arc<RwLock<NarMem::Mem>>, evidenceSentence:&Sentence, currentTime:i64, cfg__nConceptBeliefs:usize, rng:&mut rand::rngs::StdRng) {
+    /////////
+    // STORE
+    /////////
+    let addEvidenceFlag: Arc<AtomicBool> = Arc::new(AtomicBool::new(true)); // do we need to add new evidence?
+    
+    { // scope for guard
+        let evidenceMemGuard = evidenceMem.read();
+        for iEEArc in &NarMem::ret_beliefs_by_terms_nonunique(&evidenceMemGuard, &[retSeqCond(&evidenceSentence.term).clone(), retPred(&evidenceSentence.term).clone()]) { // iterate over evidence where seqCond and/or pred appear
+            let mut iEE = iEEArc.write();
+            
+            if !checkOverlap(&iEE.stamp, &evidenceSentence.stamp) { // evidence must no overlap!
+                if
+                    iEE.expDt.unwrap() >= evidenceSentence.expDt.unwrap() && // check for greater because we want to count evidence for longer intervals too, because longer ones are "included"
+                    
+                    // does impl seq match?
+                    alphaEq(&iEE.term, &evidenceSentence.term)
+                {
+                    iEE.stamp = merge(&iEE.stamp, &evidenceSentence.stamp);
+
+                    let (otherCntPos, otherCnt) = match evidenceSentence.evi.as_ref().unwrap() {
+                        Evidence::CNT{pos,cnt} => {
+                            (pos, cnt)
+                        },
+                        _ => {panic!("expected CNT!");}
+                    };
+
+                    match iEE.evi.as_ref().unwrap() {
+                        Evidence::CNT{pos,cnt} => {
+                            iEE.evi = Some(Evidence::CNT{pos:pos+otherCntPos,cnt:cnt+otherCnt}); // bump positive counter
+                        },
+                        _ => {panic!("expected CNT!");}
+                    }
+                    
+                    if false {println!("dbg - REV")};
+                    
+                    addEvidenceFlag.store(false, Ordering::Relaxed); // because we revised
+                }                                
+            }
+        }    
+    }
+    
+    if addEvidenceFlag.load(Ordering::Relaxed) {
+        // add evidence
+        mem_add_evidence(Arc::clone(&evidenceMem), &evidenceSentence, cfg__nConceptBeliefs, currentTime, rng);
+    }
+}
+
 // INGATE< gate >
 /// add procedural evidence to memory
-pub fn mem_add_evidence(evidenceMem: Arc<RwLock<NarMem::Mem>>, evidenceSentence: &Sentence, nBeliefs:usize, currentTime: i64,  rng: &mut rand::rngs::ThreadRng) {
+pub fn mem_add_evidence(evidenceMem: Arc<RwLock<NarMem::Mem>>, evidenceSentence: &Sentence, nBeliefs:usize, currentTime: i64,  rng: &mut rand::rngs::StdRng) {
     mem_add_evidence_internal(Arc::clone(&evidenceMem), evidenceSentence, nBeliefs, currentTime);
 
     // TODO< how to manage evidence of vars? and revise? >
@@ -550,6 +610,9 @@ pub fn narStep0(nar:&mut ProcNar) {
                     if !checkEqTerm( &retPred(& evi.term), &curEvent) { // is predicted event not current event?
                         newanticipatedEvents.push(iDeadline.clone());
                     }
+                    else {
+                        notify(&nar.observers, |o| o.anticipationConfirmed(&evi));
+                    }
                 }
                 nar.anticipatedEvents = newanticipatedEvents;
             }
@@ -578,6 +641,8 @@ pub fn narStep0(nar:&mut ProcNar) {
                     _ => {panic!("expected CNT!");}
                 }
 
+                notify(&nar.observers, |o| o.anticipationFailed(&mutEviGuard));
+
                 if false {match mutEviGuard.evi.as_ref().unwrap() {
                     Evidence::CNT{pos,cnt} => {
                         println!("TRACE anticipation: after neg conf   evidence: +/n {}/{}", pos, cnt); // evidence before neg-confirm
@@ -601,6 +666,7 @@ pub fn narStep1(nar:&mut ProcNar, declMem:&Option<Arc<RwLock<Mem2>>>) {
     let timeStart = Instant::now();
     
     let mut pickedAction:Option<Term> = None; // complete term of op
+    let mut pickedActionExp:Option<f64> = None; // expectation of the goal which lead to the picked op, None for babbling
     {
         struct BestEntry {
             unifiedSeq: Term, // unified sequence used for decision making
@@ -625,71 +691,48 @@ pub fn narStep1(nar:&mut ProcNar, declMem:&Option<Arc<RwLock<Mem2>>>) {
                 if trace_view.len() > perceptIdx {
     
                     let checkedState:Term = trace_view[trace_view.len()-1-perceptIdx].name.clone();
+
+                    // event and the generalizations of the event with the declarative knowledge, with the expectation of the generalization
+                    let mut checkedStates:Vec<(Term, f64)> = vec![(checkedState.clone(), 1.0)];
+                    if let Some(declMem) = declMem {
+                        checkedStates.extend(NarGoalSystem::retGeneralizedEvents(&checkedState, &declMem.read().shared.read().mem.read()));
+                    }
     
-                    // check if current state "leads" to action
-                    // tuple is (exp, entity)
-                    let thisEntry: (f64, Option<(Arc<RwLock<NarGoalSystem::Entry>>, Term)>) = NarGoalSystem::selHighestExpGoalByState(&nar.goalSystem.read(), &checkedState);
-    
-                    match thisEntry.1 {
-                        Some(e) => { // was a candidate found?
-    
-    
-                            match bestEntry2 { // is there best entry?
-                                Some(ref bestEntry4) => {
-                                    if thisEntry.0 > bestEntry4.exp {
+                    for (iCheckedState, iStateExp) in &checkedStates {
+                        // check if current state "leads" to action
+                        // tuple is (exp, entity)
+                        let thisEntry: (f64, Option<(Arc<RwLock<NarGoalSystem::Entry>>, Term)>) = NarGoalSystem::selHighestExpGoalByState(&nar.goalSystem.read(), &iCheckedState);
+                        let thisEntry = (thisEntry.0 * iStateExp, thisEntry.1);
+        
+                        match thisEntry.1 {
+                            Some(e) => { // was a candidate found?
+        
+        
+                                match bestEntry2 { // is there best entry?
+                                    Some(ref bestEntry4) => {
+                                        if thisEntry.0 > bestEntry4.exp {
+                                            bestEntry2 = Some(BestEntry{
+                                                unifiedSeq: e.1.clone(), // pull out unified term
+                                                exp:thisEntry.0,
+                                                evidence:cloneEvidence(&e.0.read().evidence)});
+                                        }
+                                    },
+                                    None => {
                                         bestEntry2 = Some(BestEntry{
                                             unifiedSeq: e.1.clone(), // pull out unified term
                                             exp:thisEntry.0,
                                             evidence:cloneEvidence(&e.0.read().evidence)});
                                     }
-                                },
-                                None => {
-                                    bestEntry2 = Some(BestEntry{
-                                        unifiedSeq: e.1.clone(), // pull out unified term
-                                        exp:thisEntry.0,
-                                        evidence:cloneEvidence(&e.0.read().evidence)});
                                 }
-                            }
-                        },
-                        None => {}
+                            },
+                            None => {}
+                        }
                     }
                 }
             }
         }
 
 
-        { // procedural / Q&A bridge
-            // see if the queued answers are good enough to cause an reaction/execution.
-            // we pretend that the answer did occur as an event
-
-            let mut writeGuard = nar.goalSystem.write();
-            for iQueuedAnswer in &writeGuard.queuedProcQaBridgeAnswers {
-
-                let exp:f64 = Tv::calcExp(&retTv(&iQueuedAnswer.answer).unwrap()); // TODO< calc exp() correctly! >
-
-                let nonunifiedSeq: Term = (*iQueuedAnswer.entry.read().sentence.term).clone();
-                let unifiedSeq: Term = nonunifiedSeq.clone(); // HACK< we just take the nonunified term > TODO< unify! >
-
-                match bestEntry2 { // is there best entry?
-                    Some(ref bestEntry4) => {
-                        if exp > bestEntry4.exp {
-                            bestEntry2 = Some(BestEntry{
-                                unifiedSeq: unifiedSeq.clone(), // pull out unified term
-                                exp:exp,
-                                evidence:cloneEvidence(&iQueuedAnswer.entry.read().evidence)});
-                        }
-                    },
-                    None => {
-                        bestEntry2 = Some(BestEntry{
-                            unifiedSeq: unifiedSeq.clone(), // pull out unified term
-                            exp:exp,
-                            evidence:cloneEvidence(&iQueuedAnswer.entry.read().evidence)});
-                    }
-                }
-            } // loop
-            writeGuard.queuedProcQaBridgeAnswers.clear();
-        }
-
         // MECHANISM< forward depth first prediction
         // tries to plan K steps ahead until it hopefully "hits" a goal
         //
@@ -910,6 +953,7 @@ pub fn narStep1(nar:&mut ProcNar, declMem:&Option<Arc<RwLock<Mem2>>>) {
                                     //let decodedOpArgsAndName:(Vec<Term>,String) = decodedOpOpt.unwrap();
                     
                                     pickedAction = Some(opTerm.clone());
+                                    pickedActionExp = Some(exp);
                                     
                                     bestEntry5.desirability = 0.0; // we executed action which reduces desirability of goal!
                                     nar.goalSystem.write().activeSet.set.remove(bestEntryIdx.unwrap()); // remove immediatly, else pong3 score suffers!
@@ -976,6 +1020,7 @@ pub fn narStep1(nar:&mut ProcNar, declMem:&Option<Arc<RwLock<Mem2>>>) {
                 opOpt.unwrap().call(nar, declMem, &opArgs); // call op
             
                 println!("{}!", &convTermToStr(&term)); // print execution
+                notify(&nar.observers, |o| o.opExecuted(term, pickedActionExp));
     
                 nar.trace.event_happened( Rc::new(SimpleSentence {name:term.clone(),evi:nar.t,occT:nar.t}) );
             }
@@ -991,15 +1036,25 @@ pub fn narStep1(nar:&mut ProcNar, declMem:&Option<Arc<RwLock<Mem2>>>) {
 
     // limit evidence (AIKR)
     if nar.t % 101 == 1 {
-        NarMem::limitMemory(&mut nar.evidenceMem.write(), nar.cfg__nConcepts as usize);
+        let forgotten:Vec<Term> = NarMem::limitMemory(&mut nar.evidenceMem.write(), nar.cfg__nConcepts as usize);
+        for iName in &forgotten {
+            notify(&nar.observers, |o| o.conceptForgotten(iName));
+        }
     }
 
+    NarGoalSystem::reactivateStandingGoals(&nar.goalSystem, &declMem.as_ref().unwrap().read(), nar.t); // eternal goals have to stay active
+
     if nar.t % 13 == 1 {
         NarGoalSystem::limitMemory(&nar.goalSystem, &declMem.as_ref().unwrap().read(), nar.t);
     }
 
     // give goal system resources
-    {
+    if nar.cfg__syncDerivation { // fixed number of derivations because wall clock time isn't reproducible
+        for _iRound in 0..nar.cfg__syncGoalDerivRounds {
+            NarGoalSystem::sampleAndInference(&mut nar.goalSystem, &declMem.as_ref().unwrap().read(), nar.t, &nar.evidenceMem.read(), &mut nar.rng);
+        }
+    }
+    else {
 
         loop {
             let dt:f32 = (timeStart.elapsed().as_micros() as f32)/1000000.0;
@@ -0,0 +1,383 @@
+//! table of inference rules of the declarative reasoner
+//!
+//! rules can get added, enabled and disabled at runtime, declarative rules can get loaded from a rule file
+
+use std::fmt;
+use std::fs;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::Term::*;
+use crate::Tv::*;
+use crate::NarSentence::EnumPunctation;
+use crate::NarseseParser::{parseNarsese2, looksLikeCopula};
+use crate::NarUnify::{unify, unifySubst};
+
+/// premise which is given to a rule
+pub struct Premise<'a> {
+    pub term:&'a Term,
+    pub punct:EnumPunctation,
+    pub tv:&'a Option<Tv>,
+}
+
+/// context of the derivation which is given to a rule
+pub struct RuleCtx {
+    /// maximal complexity of conclusions which introduce new structure
+    pub maxComplexity:i64,
+}
+
+/// inference rule
+///
+/// binary rules get called with both orders of the premises
+// PUBLICAPI
+pub trait Rule: Sync + Send {
+    /// unique name of the rule, is used to enable or disable it
+    fn name(&self) -> String;
+
+    /// number of premises, 1 or 2
+    fn nPremises(&self) -> usize;
+
+    /// tries to apply the rule to the premises
+    /// returns the conclusions with the "attention bias" factor
+    fn apply(&self, premises:&[Premise], ctx:&RuleCtx) -> Vec<(Term,Tv,EnumPunctation,f64)>;
+}
+
+/// rule which is implemented by a function, used for the built in rules
+pub struct FnRule {
+    pub name:String,
+    pub nPremises:usize,
+    pub f:Box<dyn Fn(&[Premise], &RuleCtx) -> Vec<(Term,Tv,EnumPunctation,f64)> + Sync + Send>,
+}
+
+impl Rule for FnRule {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn nPremises(&self) -> usize {
+        self.nPremises
+    }
+
+    fn apply(&self, premises:&[Premise], ctx:&RuleCtx) -> Vec<(Term,Tv,EnumPunctation,f64)> {
+        (self.f)(premises, ctx)
+    }
+}
+
+/// truth function of a declarative rule
+#[derive(Clone, Copy)]
+pub enum EnumRuleTvFn {
+    UNARY(fn(&Tv) -> Tv),
+    BINARY(fn(&Tv, &Tv) -> Tv),
+}
+
+/// declarative rule, the premises are matched by unification with the patterns
+///
+/// query variables in the patterns are the variables of the rule, premises which contain variables don't match
+// PUBLICAPI
+pub struct PatternRule {
+    pub name:String,
+    /// patterns of the premises with the punctuation the premises must have
+    pub premises:Vec<(Term, EnumPunctation)>,
+    /// pattern of the conclusion, variables get substituted with the matched terms
+    pub concl:Term,
+    pub conclPunct:EnumPunctation,
+    /// truth function, is called with the TV's of the premises in the order of the patterns
+    pub tvFn:EnumRuleTvFn,
+}
+
+impl Rule for PatternRule {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn nPremises(&self) -> usize {
+        self.premises.len()
+    }
+
+    fn apply(&self, premises:&[Premise], _ctx:&RuleCtx) -> Vec<(Term,Tv,EnumPunctation,f64)> {
+        if premises.len() != self.premises.len() || premises.iter().zip(self.premises.iter()).any(|(iPremise, iPattern)| iPremise.punct != iPattern.1) {
+            return vec![];
+        }
+        if premises.iter().any(|iPremise| iPremise.tv.is_none()) {
+            return vec![]; // truth function needs TV's
+        }
+        if premises.iter().any(|iPremise| checkHasVar(iPremise.term)) {
+            return vec![]; // variables of the premises would get confused with the variables of the rule
+        }
+
+        // match all patterns at once so that the variables are shared between the premises
+        let pattern = Term::Prod(self.premises.iter().map(|iv| Box::new(iv.0.clone())).collect());
+        let matched = Term::Prod(premises.iter().map(|iv| Box::new(iv.term.clone())).collect());
+        let asgnments = match unify(&pattern, &matched) {
+            Some(asgnments) => asgnments,
+            None => {return vec![];}
+        };
+
+        let conclTerm:Term = unifySubst(&self.concl, &asgnments);
+        if retSubterms(&conclTerm).iter().any(|iv| match iv {Term::QVar(_) => true, _ => false}) {
+            return vec![]; // variable of the rule wasn't matched
+        }
+        match &conclTerm {
+            Term::Stmt(_, subj, pred) if checkEqTerm(subj, pred) => {return vec![];}, // conclusion is a tautology
+            _ => {}
+        }
+
+        let conclTv:Tv = match self.tvFn {
+            EnumRuleTvFn::UNARY(f) => f(premises[0].tv.as_ref().unwrap()),
+            EnumRuleTvFn::BINARY(f) => f(premises[0].tv.as_ref().unwrap(), premises[1].tv.as_ref().unwrap()),
+        };
+        vec![(conclTerm, conclTv, self.conclPunct, 1.0)]
+    }
+}
+
+/// statistics of a rule
+// PUBLICAPI
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuleStats {
+    pub name:String,
+    pub enabled:bool,
+    /// how often the rule was tried
+    pub nTried:u64,
+    /// how often the rule derived at least one conclusion
+    pub nFired:u64,
+    /// number of derived conclusions
+    pub nConclusions:u64,
+}
+
+/// rule in the table with the counters for the statistics
+pub struct RuleEntry {
+    pub rule:Arc<dyn Rule>,
+    pub enabled:bool,
+    // atomic because the rules are applied concurrently by the workers
+    nTried:AtomicU64,
+    nFired:AtomicU64,
+    nConclusions:AtomicU64,
+}
+
+/// table of the rules which are used by the deriver
+// PUBLICAPI
+pub struct RuleTable {
+    pub entries:Vec<RuleEntry>,
+    /// are the statistics updated when rules are applied?
+    /// is false for tables which are shared between NARs
+    pub enStats:bool,
+}
+
+/// creates a empty rule table
+pub fn createRuleTable() -> RuleTable {
+    RuleTable{entries:vec![], enStats:true}
+}
+
+/// adds a enabled rule to the table, a rule with the same name is replaced
+// PUBLICAPI
+pub fn ruleTableAdd(table:&mut RuleTable, rule:Arc<dyn Rule>) {
+    let entry = RuleEntry{rule:rule, enabled:true, nTried:AtomicU64::new(0), nFired:AtomicU64::new(0), nConclusions:AtomicU64::new(0)};
+    let name:String = entry.rule.name();
+    match table.entries.iter().position(|iv| iv.rule.name() == name) {
+        Some(idx) => {table.entries[idx] = entry;},
+        None => {table.entries.push(entry);}
+    }
+}
+
+/// enables or disables the rule with the name
+///
+/// returns false if the rule isn't known
+// PUBLICAPI
+pub fn ruleTableSetEnabled(table:&mut RuleTable, name:&str, enabled:bool) -> bool {
+    match table.entries.iter_mut().find(|iv| iv.rule.name() == name) {
+        Some(entry) => {
+            entry.enabled = enabled;
+            true
+        },
+        None => false
+    }
+}
+
+/// returns the statistics of all rules in the order of the table
+// PUBLICAPI
+pub fn ruleTableRetStats(table:&RuleTable) -> Vec<RuleStats> {
+    table.entries.iter().map(|iv| RuleStats{
+        name:iv.rule.name(),
+        enabled:iv.enabled,
+        nTried:iv.nTried.load(Ordering::Relaxed),
+        nFired:iv.nFired.load(Ordering::Relaxed),
+        nConclusions:iv.nConclusions.load(Ordering::Relaxed),
+    }).collect()
+}
+
+/// sets the statistics of all rules back to zero
+// PUBLICAPI
+pub fn ruleTableResetStats(table:&RuleTable) {
+    for iEntry in &table.entries {
+        iEntry.nTried.store(0, Ordering::Relaxed);
+        iEntry.nFired.store(0, Ordering::Relaxed);
+        iEntry.nConclusions.store(0, Ordering::Relaxed);
+    }
+}
+
+/// applies all enabled rules which take as many premises as given and updates the statistics if they are enabled
+pub fn ruleTableApply(table:&RuleTable, premises:&[Premise], ctx:&RuleCtx) -> Vec<(Term,Tv,EnumPunctation,f64)> {
+    let mut res = vec![];
+    for iEntry in table.entries.iter().filter(|iv| iv.enabled && iv.rule.nPremises() == premises.len()) {
+        let concls = iEntry.rule.apply(premises, ctx);
+        if !table.enStats {
+            res.extend(concls);
+            continue;
+        }
+        iEntry.nTried.fetch_add(1, Ordering::Relaxed);
+        if concls.len() > 0 {
+            iEntry.nFired.fetch_add(1, Ordering::Relaxed);
+            iEntry.nConclusions.fetch_add(concls.len() as u64, Ordering::Relaxed);
+        }
+        res.extend(concls);
+    }
+    res
+}
+
+
+/// kind of the error which happened while parsing rules
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum EnumRuleParseErrorKind {
+    IO, // file couldn't get read
+    SYNTAX, // line is not of the form name: premises |- conclusion tvFn
+    BAD_NARSESE, // premise or conclusion couldn't get parsed
+    UNKNOWN_TV_FN, // truth function isn't known or doesn't fit the number of premises
+}
+
+/// error which describes why a rule couldn't get parsed
+// PUBLICAPI
+#[derive(Clone, Debug, PartialEq)]
+pub struct RuleParseError {
+    pub kind: EnumRuleParseErrorKind,
+    /// human readable description
+    pub msg: String,
+    /// line in the rule file, starts at 1
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for RuleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        write!(f, "{}", self.msg)
+    }
+}
+
+impl std::error::Error for RuleParseError {}
+
+fn mkErr(kind:EnumRuleParseErrorKind, msg:String) -> RuleParseError {
+    RuleParseError{kind:kind, msg:msg, line:None}
+}
+
+/// returns the truth function with the name
+pub fn retTvFnByName(name:&str) -> Option<EnumRuleTvFn> {
+    match name {
+        "neg" => Some(EnumRuleTvFn::UNARY(neg)),
+        "contraposition" => Some(EnumRuleTvFn::UNARY(contraposition)),
+        "ded" => Some(EnumRuleTvFn::BINARY(ded)),
+        "dedGoal" => Some(EnumRuleTvFn::BINARY(dedGoal)),
+        "abd" => Some(EnumRuleTvFn::BINARY(abd)),
+        "ind" => Some(EnumRuleTvFn::BINARY(ind)),
+        "exe" => Some(EnumRuleTvFn::BINARY(exe)),
+        "ana" => Some(EnumRuleTvFn::BINARY(ana)),
+        "resem" => Some(EnumRuleTvFn::BINARY(resem)),
+        "comp" => Some(EnumRuleTvFn::BINARY(comp)),
+        "int" => Some(EnumRuleTvFn::BINARY(int)),
+        "uni" => Some(EnumRuleTvFn::BINARY(uni)),
+        "dif" => Some(EnumRuleTvFn::BINARY(dif)),
+        "decomposePNN" => Some(EnumRuleTvFn::BINARY(decomposePNN)),
+        "decomposeNPP" => Some(EnumRuleTvFn::BINARY(decomposeNPP)),
+        "decomposePNP" => Some(EnumRuleTvFn::BINARY(decomposePNP)),
+        "decomposeNNN" => Some(EnumRuleTvFn::BINARY(decomposeNNN)),
+        _ => None
+    }
+}
+
+/// parses a rule of the form
+/// name: <?S --> ?M>. ; <?M --> ?P>. |- <?S --> ?P>. ded
+///
+/// premises are separated by ; outside of brackets, ; inside of brackets is the parallel conjunction
+pub fn parseRule(line:&str) -> Result<PatternRule, RuleParseError> {
+    let syntaxErr = || mkErr(EnumRuleParseErrorKind::SYNTAX, format!("expected name: premises |- conclusion tvFn, found `{}`", line));
+
+    let colonIdx:usize = line.find(':').ok_or_else(syntaxErr)?;
+    let name:&str = line[..colonIdx].trim();
+    let rest:&str = &line[colonIdx+1..];
+    let arrowIdx:usize = rest.find("|-").ok_or_else(syntaxErr)?;
+    let (premisesStr, conclAndTvFnStr) = (&rest[..arrowIdx], rest[arrowIdx+2..].trim());
+    let tvFnIdx:usize = conclAndTvFnStr.rfind(' ').ok_or_else(syntaxErr)?;
+    let (conclStr, tvFnName) = (&conclAndTvFnStr[..tvFnIdx], conclAndTvFnStr[tvFnIdx+1..].trim());
+    if name.len() == 0 || name.contains(char::is_whitespace) {
+        return Err(syntaxErr());
+    }
+
+    let parse = |narsese:&str| -> Result<(Term, EnumPunctation), RuleParseError> {
+        parseNarsese2(&narsese.trim().to_string())
+            .map(|iv| (iv.term, iv.punct))
+            .map_err(|err| mkErr(EnumRuleParseErrorKind::BAD_NARSESE, format!("{}: {}", narsese.trim(), err)))
+    };
+    let premises:Vec<(Term, EnumPunctation)> = splitPremises(premisesStr).iter().map(|iv| parse(iv)).collect::<Result<_, _>>()?;
+    let (concl, conclPunct) = parse(conclStr)?;
+
+    let tvFn:EnumRuleTvFn = match (retTvFnByName(tvFnName), premises.len()) {
+        (Some(EnumRuleTvFn::UNARY(f)), 1) => EnumRuleTvFn::UNARY(f),
+        (Some(EnumRuleTvFn::BINARY(f)), 2) => EnumRuleTvFn::BINARY(f),
+        _ => {return Err(mkErr(EnumRuleParseErrorKind::UNKNOWN_TV_FN, format!("no truth function `{}` for {} premises", tvFnName, premises.len())));}
+    };
+
+    Ok(PatternRule{name:name.to_string(), premises:premises, concl:concl, conclPunct:conclPunct, tvFn:tvFn})
+}
+
+/// splits the premises at the ; which are not inside of brackets
+///
+/// copulas are skipped because < > and the NAL-2 copulas {-- --] {-] contain brackets
+fn splitPremises(premisesStr:&str) -> Vec<&str> {
+    let mut res = vec![];
+    let mut depth:i64 = 0;
+    let mut startIdx:usize = 0;
+    let mut skipUntilIdx:usize = 0; // end of the last copula token
+    for (idx, c) in premisesStr.char_indices() {
+        if idx < skipUntilIdx {
+            continue;
+        }
+        if premisesStr[..idx].ends_with(' ') && looksLikeCopula(&premisesStr[idx..]) {
+            skipUntilIdx = idx + premisesStr[idx..].find(' ').unwrap_or(premisesStr.len()-idx);
+            continue;
+        }
+        match c {
+            '(' | '[' | '{' => {depth+=1;},
+            ')' | ']' | '}' => {depth-=1;},
+            ';' if depth == 0 => {
+                res.push(&premisesStr[startIdx..idx]);
+                startIdx = idx+1;
+            },
+            _ => {}
+        }
+    }
+    res.push(&premisesStr[startIdx..]);
+    res
+}
+
+/// parses rules, one rule per line
+///
+/// empty lines and lines starting with # or // are ignored
+// PUBLICAPI
+pub fn parseRules(content:&str) -> Result<Vec<PatternRule>, RuleParseError> {
+    let mut res = vec![];
+    for (lineIdx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.len() == 0 || line.starts_with("#") || line.starts_with("//") {
+            continue; // ignore empty lines and comments
+        }
+        res.push(parseRule(line).map_err(|mut err| {err.line = Some(lineIdx+1); err})?);
+    }
+    Ok(res)
+}
+
+/// loads rules from a rule file
+// PUBLICAPI
+pub fn loadRuleFile(path:&String) -> Result<Vec<PatternRule>, RuleParseError> {
+    let content:String = fs::read_to_string(path).map_err(|e| mkErr(EnumRuleParseErrorKind::IO, format!("couldn't read {}: {}", path, e)))?;
+    parseRules(&content)
+}
@@ -12,6 +12,42 @@ pub enum EnumPunctation {
     JUGEMENT, // .
     QUESTION, // ?
     GOAL, // !
+    QUEST, // @
+}
+
+/// tense of a sentence
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum EnumTense {
+    ETERNAL, // no tense
+    PRESENT, // :|:
+    FUTURE, // :/:
+    PAST, // :\:
+    ABSOLUTE(i64), // :!t: explicit occurrence time
+}
+
+/// converts tense to narsese, returns empty string for eternal
+pub fn convTenseToStr(tense:EnumTense) -> String {
+    match tense {
+        EnumTense::ETERNAL => "".to_string(),
+        EnumTense::PRESENT => ":|:".to_string(),
+        EnumTense::FUTURE => ":/:".to_string(),
+        EnumTense::PAST => ":\\:".to_string(),
+        EnumTense::ABSOLUTE(t) => format!(":!{}:", t),
+    }
+}
+
+/// budget of a task, controls how much attention the task gets
+/// default is 1.0 for priority and durability and 0.0 for quality
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Budget {
+    pub p:f64, // priority
+    pub d:f64, // durability
+    pub q:f64, // quality
+}
+
+/// converts budget to narsese
+pub fn convBudgetToStr(budget:&Budget) -> String {
+    format!("${};{};{}$", budget.p, budget.d, budget.q)
 }
 
 // abstraction for evidence
@@ -82,6 +118,11 @@ pub fn calcUsageUsefulness(usage: &Usage, currentTime: i64) -> f64 {
     result
 }
 
+/// returns if sentences with the punctuation have a TV, questions and quests don't have a TV
+pub fn checkHasTv(punct:EnumPunctation) -> bool {
+    punct != EnumPunctation::QUESTION && punct != EnumPunctation::QUEST
+}
+
 // create new eternal sentence
 pub fn newEternalSentenceByTv(term:&Term,punct:EnumPunctation,tv:&Tv::Tv,stamp:Stamp)->Sentence {
     Sentence {
@@ -89,7 +130,7 @@ pub fn newEternalSentenceByTv(term:&Term,punct:EnumPunctation,tv:&Tv::Tv,stamp:S
         t:None, // time of occurence 
         punct:punct,
         stamp:stamp,
-        evi:if punct != EnumPunctation::QUESTION {Some(Evidence::TV(tv.clone()))} else {None},
+        evi:if checkHasTv(punct) {Some(Evidence::TV(tv.clone()))} else {None},
         expDt:None, // not used
         usage:Arc::new(RwLock::new(Usage{lastUsed:0, useCount:0})),
     }
@@ -121,15 +162,38 @@ pub fn retConf(evidence:&Evidence)->f64 {
     }
 }
 
+/// transcribes a TV to count based evidence, the amount of evidence is computed with the evidential horizon
+///
+/// is the inverse of retFreq() and retConf() up to the rounding to whole counts
+/// /param tv confidence must be smaller than 1.0
+pub fn convTvToCnt(tv:&Tv::Tv) -> Evidence {
+    let cnt:i64 = (Tv::c2w(tv.c).round() as i64).max(1); // at least one observation
+    Evidence::CNT{pos:(tv.f*cnt as f64).round() as i64, cnt:cnt}
+}
+
 // convert only term and punctation to string
 pub fn convSentenceTermPunctToStr(s:&Sentence, enTv:bool) -> String {
+    convSentenceTermPunctToStr2(s, enTv, false)
+}
+
+/// converts the sentence to a string
+///
+/// `enParseable` prints the occurrence time of events as absolute tense, so the sentence can be parsed back, ex: `a. :!5:`
+// PUBLICAPI
+pub fn convSentenceTermPunctToStr2(s:&Sentence, enTv:bool, enParseable:bool) -> String {
     let punct = match s.punct{
         EnumPunctation::QUESTION=>"?",
         EnumPunctation::JUGEMENT=>".",
         EnumPunctation::GOAL=>"!",
+        EnumPunctation::QUEST=>"@",
     };
     let mut res = convTermToStr(&s.term) + punct;
-    if enTv && s.punct != EnumPunctation::QUESTION {
+    if enParseable {
+        if let Some(t) = s.t {
+            res = res + " " + &convTenseToStr(EnumTense::ABSOLUTE(t));
+        }
+    }
+    if enTv && checkHasTv(s.punct) {
         res = res + " " + &Tv::convToStr(&retTv(&s).unwrap());
     }
     res
@@ -17,7 +17,7 @@ use std::thread;
 use crate::Term::{Term, convTermToStr};
 use crate::Nar::*;
 use crate::NarInputFacade;
-use crate::NarWorkingCycle::QHandler;
+use crate::NarObserver::NarObserver;
 use crate::NarSentence::Sentence;
 use crate::NarSentence::convSentenceTermPunctToStr;
 
@@ -107,12 +107,15 @@ pub fn run() {
     // worker thread which runs NAR
     thread::spawn(move || {
         let mut nar = createNar();
-        nar.mem.read().globalQaHandlers.write().push(Arc::new(RwLock::new(QHandlerImpl{global:Arc::clone(&global)}))); // register Q&A handler to send answers to all clients
+        addObserver(&nar, Arc::new(RwLock::new(ObserverImpl{global:Arc::clone(&global)}))); // register observer to send answers and executions to all clients
 
         loop {
             let received:String = rx.recv().unwrap();
             let mut quit = false;
-            let resLines: Vec<String> = NarInputFacade::input(&mut nar, &received, &mut quit);
+            let resLines: Vec<String> = match NarInputFacade::input(&mut nar, &received, &mut quit) {
+                Ok(resLines) => resLines,
+                Err(err) => vec![format!("ERR - {}", err)], // send error to clients
+            };
             for iLine in &resLines {
                 let mut data = global.lock().unwrap(); // unwrap to panic when it can't unlock
                 let idCounter = data.idCounter;
@@ -140,20 +143,29 @@ pub fn run() {
     }
 }
 
-/// handler to send answer to clients
-pub struct QHandlerImpl {
+/// observer to send answers and executions to clients
+pub struct ObserverImpl {
     pub global:Arc<Mutex<Global>>,
 }
 
-impl QHandler for QHandlerImpl {
-    fn answer(&mut self, question:&Term, answer:&Sentence) {
-        // print question and send answer
-        let msg = "TRACE answer: ".to_owned() + &convTermToStr(&question) + "? " + &convSentenceTermPunctToStr(&answer, true);
-
+impl ObserverImpl {
+    fn send(&mut self, msg:String) {
         let mut data = self.global.lock().unwrap(); // unwrap to panic when it can't unlock
         let idCounter = data.idCounter;
-        data.arr.push((idCounter, msg.clone()));
+        data.arr.push((idCounter, msg));
         data.idCounter+=1;
     }
 }
 
+impl NarObserver for ObserverImpl {
+    fn answered(&mut self, question:&Sentence, answer:&Sentence) {
+        // send question and answer
+        let msg = "TRACE answer: ".to_owned() + &convSentenceTermPunctToStr(&question, true) + " " + &convSentenceTermPunctToStr(&answer, true);
+        self.send(msg);
+    }
+
+    fn opExecuted(&mut self, op:&Term, _exp:Option<f64>) {
+        self.send(format!("{}!", convTermToStr(&op)));
+    }
+}
+
@@ -2,9 +2,11 @@
 
 use crate::Term::Term;
 use crate::Term::checkEqTerm;
+use crate::Term::{checkEqNode, isCommutative};
 
 /// structure to store assignment of var
 // PUBLICAPI
+#[derive(Clone)]
 pub struct Asgnment {
     pub var:Term,
     pub val:Term,
@@ -13,14 +15,12 @@ pub struct Asgnment {
 
 fn unify2(a2:&Term,b2:&Term,assignments:&mut Vec<Asgnment>) -> bool {
     match a2 {
-        Term::QVar(_namea) => {
+        Term::QVar(_) | Term::DepVar(_) | Term::IndepVar(_) => {
             match b2 {
-                Term::QVar(_nameb) => false, // can't unify var with var
-                Term::DepVar(_nameb) => false, // can't unify var with var
-                Term::IndepVar(_nameb) => false, // can't unify var with var
+                Term::QVar(_) | Term::DepVar(_) | Term::IndepVar(_) => false, // can't unify var with var
                 _ => {
                     if checkAssigned(&a2, &assignments) {
-                        return checkSameVal(&a2, &b2, &assignments);
+                        checkSameVal(&a2, &b2, &assignments)
                     }
                     else {
                         assignments.push(Asgnment{var:a2.clone(),val:b2.clone(),}); // add assignment
@@ -29,192 +29,41 @@ fn unify2(a2:&Term,b2:&Term,assignments:&mut Vec<Asgnment>) -> bool {
                 }
             }
         },
-        Term::DepVar(_namea) => {
-            match b2 {
-                Term::QVar(_nameb) => false, // can't unify var with var
-                Term::DepVar(_nameb) => false, // can't unify var with var
-                Term::IndepVar(_nameb) => false, // can't unify var with var
-                _ => {
-                    if checkAssigned(&a2, &assignments) {
-                        return checkSameVal(&a2, &b2, &assignments);
-                    }
-                    else {
-                        assignments.push(Asgnment{var:a2.clone(),val:b2.clone(),}); // add assignment
-                        true
-                    }
-                }
-            }
-        },
-        Term::IndepVar(_namea) => {
-            match b2 {
-                Term::QVar(_nameb) => false, // can't unify var with var
-                Term::DepVar(_nameb) => false, // can't unify var with var
-                Term::IndepVar(_nameb) => false, // can't unify var with var
-                _ => {
-                    if checkAssigned(&a2, &assignments) {
-                        return checkSameVal(&a2, &b2, &assignments);
-                    }
-                    else {
-                        assignments.push(Asgnment{var:a2.clone(),val:b2.clone(),}); // add assignment
-                        true
-                    }
-                }
-            }
-        },
-        
-        
-        Term::Stmt(copulaa, subja, preda) => {
-            match b2 {
-                Term::Stmt(copulab, subjb, predb) if copulaa == copulab => {
-                    unify2(&subja, &subjb, assignments) && unify2(&preda, &predb, assignments)
-                },
-                _ => false
-            }
-        },
-        Term::Name(namea) => {
-            match b2 {
-                Term::Name(nameb) => namea == nameb,
-                _ => false
-            }
-        },
-        Term::Seq(seqa) => {
-            match b2 {
-                Term::Seq(seqb) => {
-                    if seqa.len() == seqb.len() {
-                        for idx in 0..seqa.len() {
-                            if !unify2(&seqa[idx], &seqb[idx], assignments) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
-            }
-        },
-        Term::SetInt(seta) => {
-            match b2 {
-                Term::SetInt(setb) => {
-                    if seta.len() == setb.len() {
-                        for idx in 0..seta.len() {
-                            if !unify2(&seta[idx], &setb[idx], assignments) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
-            }
-        },
-        Term::SetExt(seta) => {
-            match b2 {
-                Term::SetExt(setb) => {
-                    if seta.len() == setb.len() {
-                        for idx in 0..seta.len() {
-                            if !unify2(&seta[idx], &setb[idx], assignments) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
-            }
-        },
-        Term::Conj(elementsa) => {
-            match b2 {
-                Term::Conj(elementsb) => {
-                    if elementsa.len() == elementsb.len() {
-                        for idx in 0..elementsa.len() {
-                            if !unify2(&elementsa[idx], &elementsb[idx], assignments) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
-            }
-        },
-        Term::Prod(elementsa) => {
-            match b2 {
-                Term::Prod(elementsb) => {
-                    if elementsa.len() == elementsb.len() {
-                        for idx in 0..elementsa.len() {
-                            if !unify2(&elementsa[idx], &elementsb[idx], assignments) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
-            }
-        },
-        Term::Img(rela,idxa,elementsa) => {
-            match b2 {
-                Term::Img(relb,idxb,elementsb) if idxa==idxb => {
-                    if !unify2(&rela, &relb, assignments) {return false};
-                    
-                    if elementsa.len() == elementsb.len() {
-                        for idx in 0..elementsa.len() {
-                            if !unify2(&elementsa[idx], &elementsb[idx], assignments) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
-            }
-        },
-        Term::IntInt(seta) => {
-            match b2 {
-                Term::IntInt(setb) => {
-                    if seta.len() == setb.len() {
-                        for idx in 0..seta.len() {
-                            if !unify2(&seta[idx], &setb[idx], assignments) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
+        _ => {
+            if !checkEqNode(a2, b2) || a2.children().len() != b2.children().len() {
+                return false;
             }
-        },
-        Term::ExtInt(seta) => {
-            match b2 {
-                Term::ExtInt(setb) => {
-                    if seta.len() == setb.len() {
-                        for idx in 0..seta.len() {
-                            if !unify2(&seta[idx], &setb[idx], assignments) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
-            }
-        },
-        Term::Par(elementsa) => {
-            match b2 {
-                Term::Par(elementsb) => {
-                    if elementsa.len() == elementsb.len() {
-                        for idx in 0..elementsa.len() {
-                            if !unify2(&elementsa[idx], &elementsb[idx], assignments) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
+            if isCommutative(a2) {
+                let mut used:Vec<bool> = vec![false; b2.children().len()];
+                return unifyCommutative(&a2.children().collect::<Vec<&Term>>(), &b2.children().collect::<Vec<&Term>>(), &mut used, assignments);
             }
+            a2.children().zip(b2.children()).all(|(ia, ib)| unify2(ia, ib, assignments))
         },
+    }
+}
 
-        Term::Neg(terma) => {
-            match b2 {
-                Term::Neg(termb) => {
-                    unify2(&terma, &termb, assignments)
-                },
-                _ => false
+/// unifies the components of commutative compounds in any order
+///
+/// tries to match the first component of a with every unused component of b, backtracks if the rest doesn't unify
+fn unifyCommutative(a:&[&Term], b:&[&Term], used:&mut Vec<bool>, assignments:&mut Vec<Asgnment>) -> bool {
+    if a.len() == 0 {
+        return true; // all components were matched
+    }
+    for idx in 0..b.len() {
+        if used[idx] {
+            continue;
+        }
+        let nAssignments = assignments.len(); // remember to undo the assignments when backtracking
+        if unify2(a[0], b[idx], assignments) {
+            used[idx] = true;
+            if unifyCommutative(&a[1..], b, used, assignments) {
+                return true;
             }
-        },
+            used[idx] = false;
+        }
+        assignments.truncate(nAssignments);
     }
+    false
 }
 
 /// check if the variable is already assigned
@@ -245,7 +94,7 @@ pub fn unify(a: &Term, b: &Term) -> Option<Vec<Asgnment>> {
 // PUBLICAPI
 pub fn unifySubst(t: &Term, subst: &Vec<Asgnment>) -> Term {
     match t {
-        Term::QVar(_name) => {
+        Term::QVar(_) | Term::DepVar(_) | Term::IndepVar(_) => {
             // search for variable
             for iasgn in subst {
                 if checkEqTerm(&t, &iasgn.var) {
@@ -254,94 +103,6 @@ pub fn unifySubst(t: &Term, subst: &Vec<Asgnment>) -> Term {
             }
             (*t).clone()
         },
-
-        Term::DepVar(_name) => {
-            // search for variable
-            for iasgn in subst {
-                if checkEqTerm(&t, &iasgn.var) {
-                    return iasgn.val.clone();
-                }
-            }
-            (*t).clone()
-        },
-        Term::IndepVar(_name) => {
-            // search for variable
-            for iasgn in subst {
-                if checkEqTerm(&t, &iasgn.var) {
-                    return iasgn.val.clone();
-                }
-            }
-            (*t).clone()
-        },
-        
-        Term::Stmt(copula, subj, pred) => {Term::Stmt(*copula, Box::new(unifySubst(subj, subst)), Box::new(unifySubst(pred, subst)))},
-        Term::Name(_) => (*t).clone(),
-        
-        Term::Seq(subterms) => {
-            let mut arr = vec![];
-            for i in subterms {
-                arr.push(Box::new(unifySubst(i, subst)));
-            }
-            Term::Seq(arr)
-        },
-        Term::SetInt(subterms) => {
-            let mut arr = vec![];
-            for i in subterms {
-                arr.push(Box::new(unifySubst(i, subst)));
-            }
-            Term::SetInt(arr)
-        },
-        Term::SetExt(subterms) => {
-            let mut arr = vec![];
-            for i in subterms {
-                arr.push(Box::new(unifySubst(i, subst)));
-            }
-            Term::SetExt(arr)
-        },
-        Term::Conj(subterms) => {
-            let mut arr = vec![];
-            for i in subterms {
-                arr.push(Box::new(unifySubst(i, subst)));
-            }
-            Term::Conj(arr)
-        },
-        Term::Prod(subterms) => {
-            let mut arr = vec![];
-            for i in subterms {
-                arr.push(Box::new(unifySubst(i, subst)));
-            }
-            Term::Prod(arr)
-        },
-        Term::Img(rel,idx,subterms) => {
-            let rel2 = unifySubst(rel, subst);
-
-            let mut arr = vec![];
-            for i in subterms {
-                arr.push(Box::new(unifySubst(i, subst)));
-            }
-            Term::Img(Box::new(rel2),*idx,arr)
-        },
-        Term::IntInt(subterms) => {
-            let mut arr = vec![];
-            for i in subterms {
-                arr.push(Box::new(unifySubst(i, subst)));
-            }
-            Term::IntInt(arr)
-        },
-        Term::ExtInt(subterms) => {
-            let mut arr = vec![];
-            for i in subterms {
-                arr.push(Box::new(unifySubst(i, subst)));
-            }
-            Term::ExtInt(arr)
-        },
-        Term::Par(subterms) => {
-            let mut arr = vec![];
-            for i in subterms {
-                arr.push(Box::new(unifySubst(i, subst)));
-            }
-            Term::Par(arr)
-        },
-        Term::Neg(term2) => {Term::Neg(Box::new(unifySubst(term2, subst)))},
+        _ => t.map_children(|iChild| unifySubst(iChild, subst)),
     }
 }
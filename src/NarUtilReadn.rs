@@ -1,10 +1,11 @@
 // util to read narsese file
 use std::fs::File;
-use std::io::{self, BufRead};
+use std::io::{self, BufRead, Write};
 use std::path::Path;
 
 use crate::Nar::*;
 use crate::NarInputFacade;
+use crate::NarMem;
 
 // see/from https://doc.rust-lang.org/stable/rust-by-example/std_misc/file/read_lines.html
 
@@ -12,9 +13,11 @@ pub fn readNarseseFile(nar: &mut Nar, path:&String, quit:&mut bool) {
     // File hosts must exist in current path before this produces output
     if let Ok(lines) = read_lines(path) {
         // Consumes the iterator, returns an (Optional) String
-        for line in lines {
+        for (lineIdx, line) in lines.enumerate() {
             if let Ok(line2) = line {
-                NarInputFacade::input(nar, &line2, quit); // pass line into facade
+                if let Err(err) = NarInputFacade::input(nar, &line2, quit) { // pass line into facade
+                    println!("ERR - {}:{}: {}", path, lineIdx+1, err);
+                }
                 if *quit {
                     break; // break reading of file because we are exiting anyways
                 }
@@ -23,6 +26,18 @@ pub fn readNarseseFile(nar: &mut Nar, path:&String, quit:&mut bool) {
     }
 }
 
+/// writes the declarative and procedural beliefs of the NAR as narsese file which can be read with readNarseseFile()
+pub fn writeNarseseFile(nar: &Nar, path:&String) -> io::Result<()> {
+    let mut lines:Vec<String> = NarMem::exportNarsese(&nar.mem.read().shared.read().mem.read());
+    lines.extend(NarMem::exportNarsese2(&nar.procNar.evidenceMem.read(), &nar.procNar.expIntervalsTable));
+
+    let mut file = File::create(path)?;
+    for iLine in &lines {
+        writeln!(file, "{}", iLine)?;
+    }
+    Ok(())
+}
+
 // The output is wrapped in a Result to allow matching on errors
 // Returns an Iterator to the Reader of the lines of the file.
 fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
@@ -2,10 +2,10 @@
 
 use std::thread;
 use rand::Rng;
-use rand::rngs::ThreadRng;
+use rand::rngs::StdRng;
 
 use std::rc::Rc;
-use std::sync::{Arc};
+use std::sync::{Arc, OnceLock};
 use std::time::{Instant};
 
 use crate::Term::Term;
@@ -15,6 +15,11 @@ use crate::Term::retUniqueSubterms;
 use crate::Term::calcComplexity;
 use crate::Term::convTermToStr;
 use crate::Term::checkEqTerm;
+use crate::Term::cmpTerm;
+use crate::Term::alphaEq;
+use crate::Term::normalize;
+use crate::Term::checkHasVar;
+use crate::TermIntern::{TermId, retByTerm, retByTermMut, internIn};
 
 use crate::NarSentence::EnumPunctation;
 use crate::NarSentence::Sentence;
@@ -27,11 +32,15 @@ use crate::NarSentence::shallowCopySentence;
 
 use crate::NarMem::ret_beliefs_of_concept;
 use crate::NarMem;
+use crate::NarInfProcedural;
 
 use crate::Tv::*;
 use crate::NarStamp::*;
 use crate::NarStamp;
 use crate::NarUnify::*;
+use crate::NarRule::*;
+use crate::NarObserver::{NarObserver, Observers, makeObservers, notify};
+use crate::NarConfig::{NarConfig, createRng, RNG_STREAM_DECL, RNG_STREAM_DECL_WORKER};
 
 /* commented because not needed
 /// a --> b |- b --> a
@@ -67,6 +76,25 @@ pub fn infNeg(a: &Term, punct:EnumPunctation, aTv: &Option<Tv>) -> Option<(Term,
 }
 
 
+/// contraposition
+/// a ==> b.
+/// |-
+/// (--b) ==> (--a).
+pub fn infContraposition(a: &Term, punct:EnumPunctation, aTv: &Option<Tv>) -> Option<(Term, Tv, EnumPunctation, f64)> {
+    if punct != EnumPunctation::JUGEMENT {
+        return None;
+    }
+
+    match a {
+        Term::Stmt(Copula::IMPL, subj, pred) => {
+            let concl = Term::Stmt(Copula::IMPL, Box::new(Term::Neg(pred.clone())), Box::new(Term::Neg(subj.clone()))); // double negation is removed by normalize()
+            Some((concl, contraposition(&aTv.as_ref().unwrap()), EnumPunctation::JUGEMENT, 0.5))
+        },
+        _ => None,
+    }
+}
+
+
 /// structural
 /// a --> (X | Y).
 /// |-
@@ -343,6 +371,24 @@ pub fn infGeneralizedJudgJudg(
     aSide:i32,
     bSide:i32,
     tvFn: fn(&Tv, &Tv) -> Tv // function for conclusion TV computation
+) -> Option<(Term,Tv,EnumPunctation)> {
+    infGeneralizedJudgJudg2(a, punctA, aTv, b, punctB, bTv, aCopula, bCopula, conclCopula, aSide, bSide, tvFn, false)
+}
+
+/// generalized rule with two judgement premises
+/// /param swapConcl the conclusion is (side of b) --> (side of a) instead of (side of a) --> (side of b)
+pub fn infGeneralizedJudgJudg2(
+    a: &Term, punctA:EnumPunctation, aTv:&Option<Tv>, 
+    b: &Term, punctB:EnumPunctation, bTv:&Option<Tv>,
+
+    aCopula: Copula,
+    bCopula: Copula,
+    conclCopula: Copula,
+
+    aSide:i32,
+    bSide:i32,
+    tvFn: fn(&Tv, &Tv) -> Tv, // function for conclusion TV computation
+    swapConcl: bool
 ) -> Option<(Term,Tv,EnumPunctation)> {
     // helper to select subj or pred based on number (side)
     fn sel<'a>(subj:&'a Box<Term>,pred:&'a Box<Term>,side:i32)->&'a Box<Term> {
@@ -362,7 +408,9 @@ pub fn infGeneralizedJudgJudg(
                         checkEqTerm(sel(&asubj,&apred,aSide), sel(&bsubj,&bpred,bSide)) && // sides must be the same term
                         !checkEqTerm(sel(&asubj,&apred,-aSide), sel(&bsubj,&bpred,-bSide)) // other sides must not be equal!
                     {
-                        return Some(( Term::Stmt(conclCopula, Box::clone(sel(&asubj,&apred,-aSide)), Box::clone(sel(&bsubj,&bpred,-bSide))), tvFn(&aTv.as_ref().unwrap(),&bTv.as_ref().unwrap()), EnumPunctation::JUGEMENT));
+                        let (conclSubj, conclPred) = (sel(&asubj,&apred,-aSide), sel(&bsubj,&bpred,-bSide));
+                        let (conclSubj, conclPred) = if swapConcl {(conclPred, conclSubj)} else {(conclSubj, conclPred)};
+                        return Some(( Term::Stmt(conclCopula, Box::clone(conclSubj), Box::clone(conclPred)), tvFn(&aTv.as_ref().unwrap(),&bTv.as_ref().unwrap()), EnumPunctation::JUGEMENT));
                     }
                 },
                 _ => {},
@@ -376,6 +424,128 @@ pub fn infGeneralizedJudgJudg(
 
 
 
+/// generalized NAL-3 composition rule with two judgement premises
+/// the shared term is on the side, the conclusion is about the compound of the other sides
+///
+/// ex: M --> T1.  M --> T2.  |- M --> (T1 - T2).  with side=1
+/// ex: T1 --> M.  T2 --> M.  |- (T1 ~ T2) --> M.  with side=-1
+pub fn infGeneralizedComp(
+    a: &Term, punctA:EnumPunctation, aTv:&Option<Tv>,
+    b: &Term, punctB:EnumPunctation, bTv:&Option<Tv>,
+
+    side:i32,
+    compFn: fn(Term, Term) -> Term, // function to build the compound
+    tvFn: fn(&Tv, &Tv) -> Tv // function for conclusion TV computation
+) -> Option<(Term,Tv,EnumPunctation)> {
+    if punctA != EnumPunctation::JUGEMENT || punctB != EnumPunctation::JUGEMENT {
+        return None;
+    }
+
+    match (a, b) {
+        (Term::Stmt(Copula::INH, asubj, apred), Term::Stmt(Copula::INH, bsubj, bpred)) => {
+            let (aShared, aOther, bShared, bOther) = if side == 1 {(asubj, apred, bsubj, bpred)} else {(apred, asubj, bpred, bsubj)};
+            if !checkEqTerm(aShared, bShared) || checkEqTerm(aOther, bOther) {
+                return None; // shared side must be the same term and other sides must not be equal!
+            }
+
+            let compound:Term = compFn((**aOther).clone(), (**bOther).clone());
+            let conclTerm = if side == 1 {Term::Stmt(Copula::INH, aShared.clone(), Box::new(compound))} else {Term::Stmt(Copula::INH, Box::new(compound), aShared.clone())};
+            Some((conclTerm, tvFn(aTv.as_ref().unwrap(), bTv.as_ref().unwrap()), EnumPunctation::JUGEMENT))
+        },
+        _ => None,
+    }
+}
+
+/// generalized NAL-3 decomposition rule with two judgement premises
+/// a is about a component of the compound b is about, the conclusion is about the rest of the compound
+///
+/// ex: M --> S.  M --> (S - P).  |- M --> P.  with side=1
+/// ex: S --> M.  (S ~ P) --> M.  |- P --> M.  with side=-1
+pub fn infGeneralizedDecomp(
+    a: &Term, punctA:EnumPunctation, aTv:&Option<Tv>,
+    b: &Term, punctB:EnumPunctation, bTv:&Option<Tv>,
+
+    side:i32,
+    decompFn: fn(&Term, &Term) -> Option<Term>, // returns the rest of the compound without the component
+    tvFn: fn(&Tv, &Tv) -> Tv // function for conclusion TV computation
+) -> Option<(Term,Tv,EnumPunctation)> {
+    if punctA != EnumPunctation::JUGEMENT || punctB != EnumPunctation::JUGEMENT {
+        return None;
+    }
+
+    match (a, b) {
+        (Term::Stmt(Copula::INH, asubj, apred), Term::Stmt(Copula::INH, bsubj, bpred)) => {
+            let (aShared, aComponent, bShared, bCompound) = if side == 1 {(asubj, apred, bsubj, bpred)} else {(apred, asubj, bpred, bsubj)};
+            if !checkEqTerm(aShared, bShared) {
+                return None;
+            }
+
+            let rest:Term = decompFn(bCompound, aComponent)?;
+            let conclTerm = if side == 1 {Term::Stmt(Copula::INH, aShared.clone(), Box::new(rest))} else {Term::Stmt(Copula::INH, Box::new(rest), aShared.clone())};
+            Some((conclTerm, tvFn(aTv.as_ref().unwrap(), bTv.as_ref().unwrap()), EnumPunctation::JUGEMENT))
+        },
+        _ => None,
+    }
+}
+
+// helpers for decomposition, return the rest of the compound without the component
+
+fn decompExtInt(compound:&Term, component:&Term) -> Option<Term> {
+    match compound {
+        Term::ExtInt(elements) => retIntersectionRest(elements, component, Term::ExtInt),
+        _ => None,
+    }
+}
+
+fn decompIntInt(compound:&Term, component:&Term) -> Option<Term> {
+    match compound {
+        Term::IntInt(elements) => retIntersectionRest(elements, component, Term::IntInt),
+        _ => None,
+    }
+}
+
+fn retIntersectionRest(elements:&[Box<Term>], component:&Term, build:fn(Vec<Box<Term>>) -> Term) -> Option<Term> {
+    let rest:Vec<Box<Term>> = elements.iter().filter(|iv| !checkEqTerm(iv, component)).cloned().collect();
+    if rest.len() == elements.len() || rest.is_empty() {
+        return None; // component must be in the compound and must not be the only component
+    }
+    Some(if rest.len() == 1 {*rest[0].clone()} else {build(rest)})
+}
+
+// (S - P) without S
+fn decompDiffExtFirst(compound:&Term, component:&Term) -> Option<Term> {
+    match compound {
+        Term::DiffExt(a, b) if checkEqTerm(a, component) => Some((**b).clone()),
+        _ => None,
+    }
+}
+
+// (P - S) without S
+fn decompDiffExtSecond(compound:&Term, component:&Term) -> Option<Term> {
+    match compound {
+        Term::DiffExt(a, b) if checkEqTerm(b, component) => Some((**a).clone()),
+        _ => None,
+    }
+}
+
+// (S ~ P) without S
+fn decompDiffIntFirst(compound:&Term, component:&Term) -> Option<Term> {
+    match compound {
+        Term::DiffInt(a, b) if checkEqTerm(a, component) => Some((**b).clone()),
+        _ => None,
+    }
+}
+
+// (P ~ S) without S
+fn decompDiffIntSecond(compound:&Term, component:&Term) -> Option<Term> {
+    match compound {
+        Term::DiffInt(a, b) if checkEqTerm(b, component) => Some((**a).clone()),
+        _ => None,
+    }
+}
+
+
+
 /// a --> x.  a --> y.  |- x <-> y.
 /// a --> x.  a <-> y.  |- x <-> y.
 /// a <-> x.  a <-> y.  |- x <-> y.
@@ -624,6 +794,102 @@ pub fn infImplDed(a: &Term, punctA:EnumPunctation, aTv:&Option<Tv>, b: &Term, pu
     }
 }
 
+/// deduction where the negation is on one side only
+/// (--a) ==> x.
+/// unify a.
+/// |- ded with negated TV of a
+/// x.
+///
+/// a ==> x.
+/// unify (--a).
+/// |- ded with negated TV of (--a)
+/// x.
+pub fn infImplDedNeg(a: &Term, punctA:EnumPunctation, aTv:&Option<Tv>, b: &Term, punctB:EnumPunctation, bTv:&Option<Tv>) -> Option<(Term,Tv,EnumPunctation)> {
+    if punctA != EnumPunctation::JUGEMENT || punctB != EnumPunctation::JUGEMENT {
+        return None;
+    }
+
+    match a {
+        Term::Stmt(Copula::IMPL, asubj, apred) => {
+            let unifyRes = match (&**asubj, b) {
+                (Term::Neg(_), Term::Neg(_)) => None, // is handled by infImplDed
+                (Term::Neg(asubj2), _) => unify(asubj2, &b),
+                (_, Term::Neg(b2)) => unify(asubj, &b2),
+                _ => None,
+            };
+            let subst = unifySubst(&apred, &unifyRes?); // substitute vars
+            Some((subst,ded(&aTv.as_ref().unwrap(), &neg(&bTv.as_ref().unwrap())), EnumPunctation::JUGEMENT))
+        },
+        _ => None,
+    }
+}
+
+/// conditional deduction where a condition is replaced
+/// (a && m) ==> x.
+/// s ==> m.
+/// |- ded
+/// (a && s) ==> x.
+pub fn infCondDedSubst(a: &Term, punctA:EnumPunctation, aTv:&Option<Tv>, b: &Term, punctB:EnumPunctation, bTv:&Option<Tv>) -> Vec<(Term,Tv,EnumPunctation)> {
+    if punctA != EnumPunctation::JUGEMENT || punctB != EnumPunctation::JUGEMENT {
+        return vec![];
+    }
+
+    let mut res = vec![];
+    match (a, b) {
+        (Term::Stmt(Copula::IMPL, aconj, apred), Term::Stmt(Copula::IMPL, bsubj, bpred)) => {
+            if let Term::Conj(arr) = &**aconj {
+                for conjIdx in 0..arr.len() {
+                    if let Some(unifyVal) = unify(&arr[conjIdx], &bpred) { // vars must unify
+                        let conclConj:Vec<Box<Term>> = arr.iter().enumerate()
+                            .map(|(idx2, iv)| Box::new(if idx2 == conjIdx {unifySubst(&bsubj, &unifyVal)} else {unifySubst(iv, &unifyVal)}))
+                            .collect();
+                        let conclTerm = Term::Stmt(Copula::IMPL, Box::new(Term::Conj(conclConj)), Box::new(unifySubst(&apred, &unifyVal)));
+                        res.push((conclTerm, ded(&aTv.as_ref().unwrap(),&bTv.as_ref().unwrap()), EnumPunctation::JUGEMENT));
+                    }
+                }
+            }
+        },
+        _ => {},
+    }
+    res
+}
+
+/// conditional abduction
+/// (a && m) ==> x.
+/// a ==> x.
+/// |- abd
+/// m.
+pub fn infCondAbd(a: &Term, punctA:EnumPunctation, aTv:&Option<Tv>, b: &Term, punctB:EnumPunctation, bTv:&Option<Tv>) -> Option<(Term,Tv,EnumPunctation)> {
+    if punctA != EnumPunctation::JUGEMENT || punctB != EnumPunctation::JUGEMENT {
+        return None;
+    }
+
+    match (a, b) {
+        (Term::Stmt(Copula::IMPL, aconj, apred), Term::Stmt(Copula::IMPL, bsubj, bpred)) if checkEqTerm(&apred, &bpred) => {
+            let arr = match &**aconj {
+                Term::Conj(arr) => arr,
+                _ => {return None;}
+            };
+            let bConditions:Vec<&Term> = match &**bsubj {
+                Term::Conj(arr2) => arr2.iter().map(|iv| &**iv).collect(),
+                _ => vec![&**bsubj],
+            };
+            if !bConditions.iter().all(|iCond| arr.iter().any(|iv| checkEqTerm(iv, iCond))) {
+                return None; // all conditions of b must be conditions of a
+            }
+
+            let rest:Vec<Box<Term>> = arr.iter().filter(|iv| !bConditions.iter().any(|iCond| checkEqTerm(iv, iCond))).cloned().collect();
+            let conclTerm = match rest.len() {
+                0 => {return None;},
+                1 => (*rest[0]).clone(),
+                _ => Term::Conj(rest),
+            };
+            Some((conclTerm, abd(&aTv.as_ref().unwrap(),&bTv.as_ref().unwrap()), EnumPunctation::JUGEMENT))
+        },
+        _ => None,
+    }
+}
+
 /// necessary for symbolic manipulation for example in https://github.com/orgs/NARS-team/teams/all/discussions/71
 /// a ==> x?
 /// unify x.
@@ -650,159 +916,305 @@ pub fn inf7(a: &Term, punctA:EnumPunctation, _aTv:&Option<Tv>, b: &Term, punctB:
 
 
 
-/// do binary inference
-pub fn infBinaryInner(a: &Term, aPunct:EnumPunctation, aTv:&Option<Tv>, b: &Term, bPunct:EnumPunctation, bTv:&Option<Tv>, wereRulesApplied:&mut bool) -> Vec<(Term,Tv,EnumPunctation)> {
-    let mut res = vec![];
-    
-    match inf3(&a, aPunct, &aTv, &b, bPunct, &bTv) {
-        Some(x) => { res.push(x); *wereRulesApplied=true; } _ => {}
-    }
-    match inf4(&a, aPunct, &aTv, &b, bPunct, &bTv) {
-        Some(x) => { res.push(x); *wereRulesApplied=true; } _ => {}
-    }
-    match inf5(&a, aPunct, &aTv, &b, bPunct, &bTv, 0) {
-        Some(x) => { res.push(x); *wereRulesApplied=true; } _ => {}
-    }
-    match inf5(&a, aPunct, &aTv, &b, bPunct, &bTv, 1) {
-        Some(x) => { res.push(x); *wereRulesApplied=true; } _ => {}
-    }
-    match inf5(&a, aPunct, &aTv, &b, bPunct, &bTv, 2) {
-        Some(x) => { res.push(x); *wereRulesApplied=true; } _ => {}
-    }
-    match inf5(&a, aPunct, &aTv, &b, bPunct, &bTv, 3) {
-        Some(x) => { res.push(x); *wereRulesApplied=true; } _ => {}
-    }
-    match infImplDed(&a, aPunct, &aTv, &b, bPunct, &bTv) {
-        Some(x) => { res.push(x); *wereRulesApplied=true; } _ => {}
-    }
-    match inf7(&a, aPunct, &aTv, &b, bPunct, &bTv) {
-        Some(x) => { res.push(x); *wereRulesApplied=true; } _ => {}
-    }
-    match inf10(&a, aPunct, &aTv, &b, bPunct, &bTv) {
-        Some(x) => { res.push(x); *wereRulesApplied=true; } _ => {}
-    }
-    match infCompPred(&a, aPunct, &aTv, &b, bPunct, &bTv) {
-        Some(x) => { res.push(x); *wereRulesApplied=true; } _ => {}
-    }
-    match infCompSubj(&a, aPunct, &aTv, &b, bPunct, &bTv) {
-        Some(x) => { res.push(x); *wereRulesApplied=true; } _ => {}
-    }
-    match infGeneralizedJudgJudg( // S =/> M, M =/> P |-ded S =/> P
-        &a, aPunct, &aTv, &b, bPunct, &bTv,
+/// syllogistic rules, are the same for inheritance and similarity (NAL-1 and NAL-2) and for implication and equivalence (NAL-5)
+/// /param inhCopula copula of inheritance or implication
+/// /param simCopula copula of similarity or equivalence
+/// returns name, copulas of premises and conclusion, sides of the shared term, TV function and if the conclusion is swapped
+fn retSyllogismRules(inhCopula:Copula, simCopula:Copula) -> [(&'static str, Copula, Copula, Copula, i32, i32, fn(&Tv, &Tv) -> Tv, bool); 12] {
+    [
+        ("ded", inhCopula, inhCopula, inhCopula, -1, 1, ded, false), // S --> M, M --> P |-ded S --> P
+        ("abd", inhCopula, inhCopula, inhCopula, -1, -1, abd, false), // S --> M, P --> M |-abd S --> P
+        ("ind", inhCopula, inhCopula, inhCopula, 1, 1, ind, false), // M --> S, M --> P |-ind S --> P
+        ("exe", inhCopula, inhCopula, inhCopula, 1, -1, exe, false), // M --> P, S --> M |-exe P --> S
+        ("ana0", inhCopula, simCopula, inhCopula, -1, 1, ana, false), // S --> M, M <-> P |-ana S --> P
+        ("ana1", inhCopula, simCopula, inhCopula, -1, -1, ana, false), // S --> M, P <-> M |-ana S --> P
+        ("ana2", inhCopula, simCopula, inhCopula, 1, 1, ana, true), // M --> P, M <-> S |-ana S --> P
+        ("ana3", inhCopula, simCopula, inhCopula, 1, -1, ana, true), // M --> P, S <-> M |-ana S --> P
+        ("resem0", simCopula, simCopula, simCopula, 1, -1, resem, false), // M <-> P, S <-> M |-resem S <-> P
+        ("resem1", simCopula, simCopula, simCopula, 1, 1, resem, false), // M <-> P, M <-> S |-resem S <-> P
+        ("resem2", simCopula, simCopula, simCopula, -1, -1, resem, false), // P <-> M, S <-> M |-resem S <-> P
+        ("resem3", simCopula, simCopula, simCopula, -1, 1, resem, false), // P <-> M, M <-> S |-resem P <-> S
+    ]
+}
+
+/// wraps a inference function of two premises as a rule
+fn mkRule2<F>(name:&str, f:F) -> Arc<dyn Rule> where F: Fn(&Term, EnumPunctation, &Option<Tv>, &Term, EnumPunctation, &Option<Tv>) -> Vec<(Term,Tv,EnumPunctation)> + Sync + Send + 'static {
+    Arc::new(FnRule{name:name.to_string(), nPremises:2, f:Box::new(move |premises:&[Premise], _ctx:&RuleCtx| {
+        f(premises[0].term, premises[0].punct, premises[0].tv, premises[1].term, premises[1].punct, premises[1].tv).into_iter().map(|(term, tv, punct)| (term, tv, punct, 1.0)).collect()
+    })})
+}
 
+/// wraps a inference function of one premise as a rule
+fn mkRule1<F>(name:&str, f:F) -> Arc<dyn Rule> where F: Fn(&Term, EnumPunctation, &Option<Tv>) -> Vec<(Term,Tv,EnumPunctation,f64)> + Sync + Send + 'static {
+    Arc::new(FnRule{name:name.to_string(), nPremises:1, f:Box::new(move |premises:&[Premise], _ctx:&RuleCtx| {
+        f(premises[0].term, premises[0].punct, premises[0].tv)
+    })})
+}
+
+/// creates the table with the built in rules of the declarative reasoner
+// PUBLICAPI
+pub fn createBuiltinRuleTable() -> RuleTable {
+    let mut table = createRuleTable();
+
+    // rules with two premises
+    for (name, infFn) in &[
+        ("setIntComp", inf3 as fn(&Term, EnumPunctation, &Option<Tv>, &Term, EnumPunctation, &Option<Tv>) -> Option<(Term,Tv,EnumPunctation)>), // x --> [a].  x --> [b].  |- x --> [a b].
+        ("setExtComp", inf4), // {a} --> x.  {b} --> x.  |- {a b} --> x.
+        ("implDed", infImplDed), // a ==> x.  a.  |- x.
+        ("implDedNeg", infImplDedNeg), // (! a) ==> x.  (! a).  |- x.
+        ("condAbd", infCondAbd), // (a && m) ==> x.  a ==> x.  |- m.
+        ("implQuestion", inf7), // a ==> x?  x.  |- a ==> x.
+        ("extIntComp", inf10), // x --> a.  x --> b.  |- x --> (a&b).
+        ("compPred", infCompPred), // a --> x.  a --> y.  |- x <-> y.
+        ("compSubj", infCompSubj), // x --> a.  y --> a.  |- x <-> y.
+        ("implAbd", infImplAbd), // X ==> P.  P.  |- X.  eliminates independent variables
+    ] {
+        let infFn = *infFn;
+        ruleTableAdd(&mut table, mkRule2(name, move |a, aPunct, aTv, b, bPunct, bTv| infFn(a, aPunct, aTv, b, bPunct, bTv).into_iter().collect()));
+    }
+    ruleTableAdd(&mut table, mkRule2("condDed", |a, aPunct, aTv, b, bPunct, bTv| (0..4).filter_map(|conjIdx| inf5(a, aPunct, aTv, b, bPunct, bTv, conjIdx)).collect())); // (a && b) ==> x.  a.  |- b ==> x.
+    ruleTableAdd(&mut table, mkRule2("condDedSubst", infCondDedSubst)); // (a && m) ==> x.  s ==> m.  |- (a && s) ==> x.
+    ruleTableAdd(&mut table, mkRule2("depVarElim", infDepVarElim)); // (<#1 --> a> && <#1 --> b>).  <x --> a>.  |- <x --> b>.
+    ruleTableAdd(&mut table, mkRule2("goalDed", infGoalDed)); // x!  a ==> x.  |- a!
+
+    for (name, side, compFn, tvFn) in &[
+        ("compIntIntPred", 1, (|x, y| Term::IntInt(vec![Box::new(x), Box::new(y)])) as fn(Term, Term) -> Term, uni as fn(&Tv, &Tv) -> Tv), // M --> T1, M --> T2 |-uni M --> (T1 | T2)
+        ("compDiffExtPred", 1, |x, y| Term::DiffExt(Box::new(x), Box::new(y)), dif), // M --> T1, M --> T2 |-dif M --> (T1 - T2)
+        ("compIntIntSubj", -1, |x, y| Term::IntInt(vec![Box::new(x), Box::new(y)]), int), // T1 --> M, T2 --> M |-int (T1 | T2) --> M
+        ("compExtIntSubj", -1, |x, y| Term::ExtInt(vec![Box::new(x), Box::new(y)]), uni), // T1 --> M, T2 --> M |-uni (T1 & T2) --> M
+        ("compDiffIntSubj", -1, |x, y| Term::DiffInt(Box::new(x), Box::new(y)), dif), // T1 --> M, T2 --> M |-dif (T1 ~ T2) --> M
+    ] {
+        let (side, compFn, tvFn) = (*side, *compFn, *tvFn);
+        ruleTableAdd(&mut table, mkRule2(name, move |a, aPunct, aTv, b, bPunct, bTv| infGeneralizedComp(a, aPunct, aTv, b, bPunct, bTv, side, compFn, tvFn).into_iter().collect()));
+    }
+    for (name, side, decompFn, tvFn) in &[
+        ("decompExtIntPred", 1, decompExtInt as fn(&Term, &Term) -> Option<Term>, decomposePNN as fn(&Tv, &Tv) -> Tv), // M --> S, M --> (S & P) |- M --> P
+        ("decompIntIntPred", 1, decompIntInt, decomposeNPP), // M --> S, M --> (S | P) |- M --> P
+        ("decompDiffExtFirstPred", 1, decompDiffExtFirst, decomposePNP), // M --> S, M --> (S - P) |- M --> P
+        ("decompDiffExtSecondPred", 1, decompDiffExtSecond, decomposeNNN), // M --> S, M --> (P - S) |- M --> P
+        ("decompIntIntSubj", -1, decompIntInt, decomposePNN), // S --> M, (S | P) --> M |- P --> M
+        ("decompExtIntSubj", -1, decompExtInt, decomposeNPP), // S --> M, (S & P) --> M |- P --> M
+        ("decompDiffIntFirstSubj", -1, decompDiffIntFirst, decomposePNP), // S --> M, (S ~ P) --> M |- P --> M
+        ("decompDiffIntSecondSubj", -1, decompDiffIntSecond, decomposeNNN), // S --> M, (P ~ S) --> M |- P --> M
+    ] {
+        let (side, decompFn, tvFn) = (*side, *decompFn, *tvFn);
+        ruleTableAdd(&mut table, mkRule2(name, move |a, aPunct, aTv, b, bPunct, bTv| infGeneralizedDecomp(a, aPunct, aTv, b, bPunct, bTv, side, decompFn, tvFn).into_iter().collect()));
+    }
+
+    ruleTableAdd(&mut table, mkRule2("predImplDed", |a, aPunct, aTv, b, bPunct, bTv| infGeneralizedJudgJudg( // S =/> M, M =/> P |-ded S =/> P
+        a, aPunct, aTv, b, bPunct, bTv,
         Copula::PREDIMPL,
         Copula::PREDIMPL,
         Copula::PREDIMPL,
-        -1,1,ded) {
-        Some(x) => { res.push(x); *wereRulesApplied=true; } _ => {}
+        -1,1,ded).into_iter().collect()));
+    for (prefix, inhCopula, simCopula) in &[("nal1", Copula::INH, Copula::SIM), ("nal5", Copula::IMPL, Copula::EQUIV)] { // NAL-1 and NAL-2, NAL-5
+        for (name, aCopula, bCopula, conclCopula, aSide, bSide, tvFn, swapConcl) in retSyllogismRules(*inhCopula, *simCopula) {
+            ruleTableAdd(&mut table, mkRule2(&format!("{}.{}", prefix, name), move |a, aPunct, aTv, b, bPunct, bTv| {
+                infGeneralizedJudgJudg2(a, aPunct, aTv, b, bPunct, bTv, aCopula, bCopula, conclCopula, aSide, bSide, tvFn, swapConcl).into_iter().collect()
+            }));
+        }
     }
-    match infGeneralizedJudgJudg( // S ==> M, M ==> P |-ded S ==> P
-        &a, aPunct, &aTv, &b, bPunct, &bTv,
 
-        Copula::IMPL,
-        Copula::IMPL,
-        Copula::IMPL,
-        -1,1,ded) {
-        Some(x) => { res.push(x); *wereRulesApplied=true; } _ => {}
-    }
-    match infGeneralizedJudgJudg( // S --> M, M --> P |-ded S --> P
-        &a, aPunct, &aTv, &b, bPunct, &bTv,
+    // variable introduction is limited by the complexity because it builds bigger terms
+    ruleTableAdd(&mut table, Arc::new(FnRule{name:"varIntro".to_string(), nPremises:2, f:Box::new(|premises:&[Premise], ctx:&RuleCtx| {
+        infVarIntro(premises[0].term, premises[0].punct, premises[0].tv, premises[1].term, premises[1].punct, premises[1].tv).into_iter()
+            .filter(|iv| calcComplexity(&iv.0) as i64 <= ctx.maxComplexity)
+            .map(|(term, tv, punct)| (term, tv, punct, 1.0))
+            .collect()
+    })}));
+
+    // rules with one premise
+    for (name, infFn) in &[
+        ("neg", infNeg as fn(&Term, EnumPunctation, &Option<Tv>) -> Option<(Term,Tv,EnumPunctation,f64)>),
+        ("contraposition", infContraposition),
+        ("structProd0", infStructProd0),
+        ("structProd1", infStructProd1),
+        ("structImg0", infStructImg0),
+        ("structImg1", infStructImg1),
+        ("structSetInt", infStructSetInt),
+        ("structSetExt", infStructSetExt),
+    ] {
+        let infFn = *infFn;
+        ruleTableAdd(&mut table, mkRule1(name, move |a, punct, aTv| infFn(a, punct, aTv).into_iter().collect()));
+    }
+    ruleTableAdd(&mut table, mkRule1("structSubj", |a, punct, aTv| (0..3).filter_map(|idx| infStructSubj1(a, punct, aTv, idx)).collect()));
+    ruleTableAdd(&mut table, mkRule1("structPred", |a, punct, aTv| (0..3).filter_map(|idx| infStructPred1(a, punct, aTv, idx)).collect()));
+    ruleTableAdd(&mut table, mkRule1("structDecomposePar", |a, punct, aTv| infStructDecomposePar(a, punct, aTv).unwrap_or(vec![])));
+
+    table
+}
 
-        Copula::INH,
-        Copula::INH,
-        Copula::INH,
-        -1,1,ded) {
-        Some(x) => { res.push(x); *wereRulesApplied=true; } _ => {}
-    }
-    match infGeneralizedJudgJudg( // S --> M, P --> M |-abd S --> P
-        &a, aPunct, &aTv, &b, bPunct, &bTv,
+/// returns the table with the built in rules, is used when no table of a NAR is given
+///
+/// the table is shared by all callers, so it doesn't keep statistics, they are kept by the table of each NAR
+pub fn retBuiltinRuleTable() -> &'static RuleTable {
+    static BUILTIN_RULES: OnceLock<RuleTable> = OnceLock::new();
+    BUILTIN_RULES.get_or_init(|| {
+        let mut table = createBuiltinRuleTable();
+        table.enStats = false;
+        table
+    })
+}
 
-        Copula::INH,
-        Copula::INH,
-        Copula::INH,
-        -1,-1,abd) {
-        Some(x) => { res.push(x); *wereRulesApplied=true; } _ => {}
+/// NAL-6 variable introduction, the shared term of the premises is replaced by a variable
+/// {tim} --> animal.  {tim} --> [fat].  |-
+/// <$1 --> animal> ==> <$1 --> [fat]>.  ind
+/// <$1 --> animal> <=> <$1 --> [fat]>.  comp
+/// (<#1 --> animal> && <#1 --> [fat]>).  int
+///
+/// works the same for a shared predicate, ex: cat --> animal.  dog --> animal.
+///
+/// the rule is called with both orders of the premises, the symmetric <=> and && are only derived for one order so they are not derived twice
+pub fn infVarIntro(a: &Term, punctA:EnumPunctation, aTv:&Option<Tv>, b: &Term, punctB:EnumPunctation, bTv:&Option<Tv>) -> Vec<(Term,Tv,EnumPunctation)> {
+    if punctA != EnumPunctation::JUGEMENT || punctB != EnumPunctation::JUGEMENT {
+        return vec![];
     }
-    match infGeneralizedJudgJudg( // M --> S, M --> P |-ind S --> P
-        &a, aPunct, &aTv, &b, bPunct, &bTv,
 
-        Copula::INH,
-        Copula::INH,
-        Copula::INH,
-        1,1,ind) {
-        Some(x) => { res.push(x); *wereRulesApplied=true; } _ => {}
+    let enSymmetric:bool = cmpTerm(a, b) == std::cmp::Ordering::Less; // derive symmetric conclusions only for one order of the premises
+    let mut res = vec![];
+    match (a, b) {
+        (Term::Stmt(Copula::INH, asubj, apred), Term::Stmt(Copula::INH, bsubj, bpred)) => {
+            for side in &[1, -1] {
+                let (aShared, aOther, bShared, bOther) = if *side == 1 {(asubj, apred, bsubj, bpred)} else {(apred, asubj, bpred, bsubj)};
+                if !checkEqTerm(aShared, bShared) || checkEqTerm(aOther, bOther) || checkHasVar(aShared) {
+                    continue; // shared side must be the same term without variables and other sides must not be equal!
+                }
+
+                let varName:String = retFreshVarName(&[a, b]);
+                // helper to build the statement with the variable
+                let mkState = |var:&Term, other:&Box<Term>| -> Box<Term> {
+                    Box::new(if *side == 1 {Term::Stmt(Copula::INH, Box::new(var.clone()), other.clone())} else {Term::Stmt(Copula::INH, other.clone(), Box::new(var.clone()))})
+                };
+
+                let indepVar = Term::IndepVar(varName.clone());
+                let depVar = Term::DepVar(varName);
+                let aTv2:&Tv = aTv.as_ref().unwrap();
+                let bTv2:&Tv = bTv.as_ref().unwrap();
+                res.push((Term::Stmt(Copula::IMPL, mkState(&indepVar, aOther), mkState(&indepVar, bOther)), ind(aTv2, bTv2), EnumPunctation::JUGEMENT));
+                if enSymmetric {
+                    res.push((Term::Stmt(Copula::EQUIV, mkState(&indepVar, aOther), mkState(&indepVar, bOther)), comp(aTv2, bTv2), EnumPunctation::JUGEMENT));
+                    res.push((Term::Conj(vec![mkState(&depVar, aOther), mkState(&depVar, bOther)]), int(aTv2, bTv2), EnumPunctation::JUGEMENT));
+                }
+            }
+        },
+        _ => {},
     }
-    
     res
 }
 
-/// do binary inference
-pub fn infBinary(a: &Term, aPunct:EnumPunctation, aTv:&Option<Tv>, b: &Term, bPunct:EnumPunctation, bTv:&Option<Tv>, wereRulesApplied:&mut bool) -> Vec<(Term,Tv,EnumPunctation)> {
-    let mut res = vec![];
-    *wereRulesApplied = false; // because no rules were applied yet
-    res.extend(infBinaryInner(&a, aPunct, &aTv, &b, bPunct, &bTv, wereRulesApplied).iter().cloned());
-    res.extend(infBinaryInner(&b, bPunct, &aTv, &a, aPunct, &bTv, wereRulesApplied).iter().cloned());
-    res
+/// returns name of a variable which doesn't occur in the terms
+fn retFreshVarName(terms:&[&Term]) -> String {
+    let names:Vec<String> = terms.iter().flat_map(|iTerm| retSubterms(iTerm)).filter_map(|iv| match iv {
+        Term::QVar(name) | Term::DepVar(name) | Term::IndepVar(name) => Some(name),
+        _ => None,
+    }).collect();
+    (1..).map(|iv:usize| iv.to_string()).find(|iv| !names.contains(iv)).unwrap()
 }
 
-pub fn infSinglePremise(a: &Term, punct:EnumPunctation, aTv:&Option<Tv>) -> Vec<(Term,Tv,EnumPunctation,f64)> {
-    let mut res = vec![];
-
-    match infNeg(&a, punct, &aTv) {
-        Some(x) => { res.push(x); } _ => {}
-    }
-    match infStructSubj1(&a, punct, &aTv, 0) {
-        Some(x) => { res.push(x); } _ => {}
-    }
-    match infStructSubj1(&a, punct, &aTv, 1) {
-        Some(x) => { res.push(x); } _ => {}
-    }
-    match infStructSubj1(&a, punct, &aTv, 2) {
-        Some(x) => { res.push(x); } _ => {}
+/// NAL-6 elimination of the dependent variable
+/// (<#1 --> animal> && <#1 --> [fat]>).
+/// unify <{tim} --> animal>.
+/// |- ded
+/// <{tim} --> [fat]>.
+pub fn infDepVarElim(a: &Term, punctA:EnumPunctation, aTv:&Option<Tv>, b: &Term, punctB:EnumPunctation, bTv:&Option<Tv>) -> Vec<(Term,Tv,EnumPunctation)> {
+    if punctA != EnumPunctation::JUGEMENT || punctB != EnumPunctation::JUGEMENT {
+        return vec![];
     }
 
-    match infStructPred1(&a, punct, &aTv, 0) {
-        Some(x) => { res.push(x); } _ => {}
-    }
-    match infStructPred1(&a, punct, &aTv, 1) {
-        Some(x) => { res.push(x); } _ => {}
-    }
-    match infStructPred1(&a, punct, &aTv, 2) {
-        Some(x) => { res.push(x); } _ => {}
+    let mut res = vec![];
+    if let Term::Conj(arr) = a {
+        for conjIdx in 0..arr.len() {
+            if !checkHasVar(&arr[conjIdx]) {
+                continue; // is only elimination if the component has a variable
+            }
+            if let Some(unifyVal) = unify(&arr[conjIdx], &b) { // vars must unify
+                let rest:Vec<Box<Term>> = arr.iter().enumerate().filter(|(idx2, _)| *idx2 != conjIdx).map(|(_, iv)| Box::new(unifySubst(iv, &unifyVal))).collect();
+                let conclTerm = if rest.len() == 1 {(*rest[0]).clone()} else {Term::Conj(rest)};
+                res.push((conclTerm, ded(&aTv.as_ref().unwrap(),&bTv.as_ref().unwrap()), EnumPunctation::JUGEMENT));
+            }
+        }
     }
+    res
+}
 
-    match infStructProd0(&a, punct, &aTv) {
-        Some(x) => { res.push(x); } _ => {}
-    }
-    match infStructProd1(&a, punct, &aTv) {
-        Some(x) => { res.push(x); } _ => {}
-    }
-    match infStructImg0(&a, punct, &aTv) {
-        Some(x) => { res.push(x); } _ => {}
-    }
-    match infStructImg1(&a, punct, &aTv) {
-        Some(x) => { res.push(x); } _ => {}
+/// NAL-6 abductive elimination of the independent variable
+/// <$1 --> animal> ==> <$1 --> [fat]>.
+/// unify <{tim} --> [fat]>.
+/// |- abd
+/// <{tim} --> animal>.
+pub fn infImplAbd(a: &Term, punctA:EnumPunctation, aTv:&Option<Tv>, b: &Term, punctB:EnumPunctation, bTv:&Option<Tv>) -> Option<(Term,Tv,EnumPunctation)> {
+    if punctA != EnumPunctation::JUGEMENT || punctB != EnumPunctation::JUGEMENT {
+        return None;
     }
 
-    match infStructSetInt(&a, punct, &aTv) {
-        Some(x) => { res.push(x); } _ => {}
+    match a {
+        Term::Stmt(Copula::IMPL, asubj, apred) if checkHasVar(apred) => {
+            let unifyRes = unify(apred, &b)?; // vars must unify
+            let subst = unifySubst(&asubj, &unifyRes); // substitute vars
+            Some((subst,abd(&aTv.as_ref().unwrap(), &bTv.as_ref().unwrap()), EnumPunctation::JUGEMENT))
+        },
+        _ => None,
     }
-    match infStructSetExt(&a, punct, &aTv) {
-        Some(x) => { res.push(x); } _ => {}
+}
+
+/// goal deduction over implication, equivalence and similarity
+/// x!
+/// a ==> x.  or  a <=> x.  or  x <=> a.  or  a <-> x.  or  x <-> a.
+/// |- dedGoal
+/// a!
+pub fn infGoalDed(a: &Term, punctA:EnumPunctation, aTv:&Option<Tv>, b: &Term, punctB:EnumPunctation, bTv:&Option<Tv>) -> Vec<(Term,Tv,EnumPunctation)> {
+    if punctA != EnumPunctation::GOAL || punctB != EnumPunctation::JUGEMENT {
+        return vec![];
     }
 
-    match infStructDecomposePar(&a, punct, &aTv) {
-        Some(arr) => {
-            for iRes in arr {
-                res.push(iRes);
-            }
+    let sides:Vec<(&Term, &Term)> = match b { // (side which has to match with the goal, side which is the conclusion)
+        Term::Stmt(Copula::IMPL, bsubj, bpred) => vec![(bpred, bsubj)],
+        Term::Stmt(Copula::EQUIV, bsubj, bpred) | Term::Stmt(Copula::SIM, bsubj, bpred) => vec![(bpred, bsubj), (bsubj, bpred)],
+        _ => vec![],
+    };
+
+    let mut res = vec![];
+    for (matched, concl) in sides {
+        if let Some(unifyRes) = unify(matched, a) { // vars must unify
+            let subst = unifySubst(concl, &unifyRes); // substitute vars
+            res.push((subst, dedGoal(&aTv.as_ref().unwrap(), &bTv.as_ref().unwrap()), EnumPunctation::GOAL));
         }
-        _ => {}
     }
-
     res
 }
 
+/// do binary inference with the rules of the table, the rules are applied to both orders of the premises
+/// /param maxComplexity conclusions of variable introduction which are more complex are ignored
+pub fn infBinaryByRules(rules:&RuleTable, a: &Term, aPunct:EnumPunctation, aTv:&Option<Tv>, b: &Term, bPunct:EnumPunctation, bTv:&Option<Tv>, maxComplexity:i64, wereRulesApplied:&mut bool) -> Vec<(Term,Tv,EnumPunctation)> {
+    let ctx = RuleCtx{maxComplexity:maxComplexity};
+    let mut res = vec![];
+    res.extend(ruleTableApply(rules, &[Premise{term:a, punct:aPunct, tv:aTv}, Premise{term:b, punct:bPunct, tv:bTv}], &ctx));
+    res.extend(ruleTableApply(rules, &[Premise{term:b, punct:bPunct, tv:bTv}, Premise{term:a, punct:aPunct, tv:aTv}], &ctx));
+    *wereRulesApplied = res.len() > 0;
+    res.into_iter().map(|(term, tv, punct, _attBias)| (term, tv, punct)).collect()
+}
+
+/// do binary inference with the built in rules, includes the rules which introduce and eliminate variables (NAL-6)
+/// /param maxComplexity conclusions of variable introduction which are more complex are ignored
+pub fn infBinary2(a: &Term, aPunct:EnumPunctation, aTv:&Option<Tv>, b: &Term, bPunct:EnumPunctation, bTv:&Option<Tv>, maxComplexity:i64, wereRulesApplied:&mut bool) -> Vec<(Term,Tv,EnumPunctation)> {
+    infBinaryByRules(retBuiltinRuleTable(), a, aPunct, aTv, b, bPunct, bTv, maxComplexity, wereRulesApplied)
+}
+
+/// do binary inference with the built in rules, variable introduction is limited by the complexity of the default configuration
+pub fn infBinary(a: &Term, aPunct:EnumPunctation, aTv:&Option<Tv>, b: &Term, bPunct:EnumPunctation, bTv:&Option<Tv>, wereRulesApplied:&mut bool) -> Vec<(Term,Tv,EnumPunctation)> {
+    infBinary2(a, aPunct, aTv, b, bPunct, bTv, NarConfig::default().maxComplexity, wereRulesApplied)
+}
+
+/// do single premise inference with the rules of the table
+pub fn infSinglePremiseByRules(rules:&RuleTable, a: &Term, punct:EnumPunctation, aTv:&Option<Tv>) -> Vec<(Term,Tv,EnumPunctation,f64)> {
+    ruleTableApply(rules, &[Premise{term:a, punct:punct, tv:aTv}], &RuleCtx{maxComplexity:i64::MAX})
+}
+
+/// do single premise inference with the built in rules
+pub fn infSinglePremise(a: &Term, punct:EnumPunctation, aTv:&Option<Tv>) -> Vec<(Term,Tv,EnumPunctation,f64)> {
+    infSinglePremiseByRules(retBuiltinRuleTable(), a, punct, aTv)
+}
+
 
 
 
@@ -820,6 +1232,35 @@ mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
 
+    // NAL-3 composition and decomposition of differences
+    #[test]
+    pub fn nal3Difference() {
+        let parseTerm = |narsese:&str| -> Term {crate::NarseseParser::parseNarsese(&narsese.to_string()).ok().unwrap().0};
+        let retConcls = |a:&str, aTv:Tv, b:&str, bTv:Tv| -> Vec<(String, Tv)> {
+            let mut wereRulesApplied = false;
+            infBinary(&parseTerm(a), EnumPunctation::JUGEMENT, &Some(aTv), &parseTerm(b), EnumPunctation::JUGEMENT, &Some(bTv), &mut wereRulesApplied)
+                .iter().map(|(term, tv, _)| (convTermToStr(term), *tv)).collect()
+        };
+
+        let concls = retConcls("<m --> a>.", Tv{f:1.0,c:0.9}, "<m --> b>.", Tv{f:0.0,c:0.9});
+        let (_, tv) = concls.iter().find(|(term, _)| term == "<m --> ( a - b )>").unwrap();
+        assert!((tv.f - 1.0).abs() < 1.0e-6 && (tv.c - 0.81).abs() < 1.0e-6);
+
+        let concls = retConcls("<m --> (a - b)>.", Tv{f:0.0,c:0.9}, "<m --> a>.", Tv{f:1.0,c:0.9});
+        let (_, tv) = concls.iter().find(|(term, _)| term == "<m --> b>").unwrap();
+        assert!((tv.f - 1.0).abs() < 1.0e-6 && (tv.c - 0.81).abs() < 1.0e-6); // m isn't a but not b, so it is b
+    }
+
+    // regression: the premises are also tried in reverse order, the truth values have to be swapped with them
+    #[test]
+    pub fn reverseOrderTv() {
+        let parseTerm = |narsese:&str| -> Term {crate::NarseseParser::parseNarsese(&narsese.to_string()).ok().unwrap().0};
+        let mut wereRulesApplied = false;
+        let concls = infBinary(&parseTerm("<m --> b>."), EnumPunctation::JUGEMENT, &Some(Tv{f:0.0,c:0.9}), &parseTerm("<m --> a>."), EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &mut wereRulesApplied);
+        let (_, tv, _) = concls.iter().find(|(term, _, _)| convTermToStr(term) == "<m --> ( a - b )>").unwrap(); // is only derived in reverse order
+        assert!((tv.f - 1.0).abs() < 1.0e-6 && (tv.c - 0.81).abs() < 1.0e-6);
+    }
+
     #[test]
     //    <<a --> b> ==> <c --> d>>
     //    <a --> b>
@@ -893,37 +1334,626 @@ mod tests {
         let mut success = false;
         
         let mut wereRulesApplied = false;
-        let infConcl = infBinary(&impl0, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &inh1, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &mut wereRulesApplied);
+        let infConcl = infBinary(&impl0, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &inh1, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &mut wereRulesApplied);
+        for iInfConcl in infConcl {
+            let (conclTerm, _conclTv, _punct) = iInfConcl;
+            let conclTermStr = convTermToStr(&conclTerm);
+            println!("{}", &conclTermStr);
+            if conclTermStr == "<<c --> d> ==> x>" {
+                success=true;
+            }
+        }
+
+        assert_eq!(success, true);
+    }
+
+    #[test]
+    // test
+    //    <a --> b>
+    //    <b <-> c>
+    //    concl:
+    //    <a --> c> with analogy
+    pub fn analogy_a() {
+        let a0 = Term::Name("a".to_string());
+        let b0 = Term::Name("b".to_string());
+        let stmt0 = Term::Stmt(Copula::INH, Box::new(a0), Box::new(b0));
+        
+        let b1 = Term::Name("b".to_string());
+        let c1 = Term::Name("c".to_string());
+        let stmt1 = Term::Stmt(Copula::SIM, Box::new(b1), Box::new(c1));
+        
+        println!("{}", &convTermToStr(&stmt0));
+        println!("{}", &convTermToStr(&stmt1));
+        println!("concl:");
+
+        let mut success = false;
+        
+        let mut wereRulesApplied = false;
+        let infConcl = infBinary(&stmt0, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &stmt1, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &mut wereRulesApplied);
+        for iInfConcl in infConcl {
+            let (conclTerm, conclTv, _punct) = iInfConcl;
+            let conclTermStr = convTermToStr(&conclTerm);
+            println!("{} {}", &conclTermStr, crate::Tv::convToStr(&conclTv));
+            if conclTermStr == "<a --> c>" && (conclTv.f - 1.0).abs() < 0.001 && (conclTv.c - 0.81).abs() < 0.001 {
+                success=true;
+            }
+        }
+
+        assert_eq!(success, true);
+    }
+
+    #[test]
+    // test
+    //    <a <-> b>
+    //    <b <-> c>
+    //    concl:
+    //    <c <-> a> with resemblance
+    pub fn resemblance_a() {
+        let a0 = Term::Name("a".to_string());
+        let b0 = Term::Name("b".to_string());
+        let stmt0 = Term::Stmt(Copula::SIM, Box::new(a0), Box::new(b0));
+        
+        let b1 = Term::Name("b".to_string());
+        let c1 = Term::Name("c".to_string());
+        let stmt1 = Term::Stmt(Copula::SIM, Box::new(b1), Box::new(c1));
+        
+        println!("{}", &convTermToStr(&stmt0));
+        println!("{}", &convTermToStr(&stmt1));
+        println!("concl:");
+
+        let mut success = false;
+        
+        let mut wereRulesApplied = false;
+        let infConcl = infBinary(&stmt0, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &stmt1, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &mut wereRulesApplied);
+        for iInfConcl in infConcl {
+            let (conclTerm, conclTv, _punct) = iInfConcl;
+            let conclTermStr = convTermToStr(&conclTerm);
+            println!("{} {}", &conclTermStr, crate::Tv::convToStr(&conclTv));
+            if conclTermStr == "<c <-> a>" && (conclTv.f - 1.0).abs() < 0.001 && (conclTv.c - 0.81).abs() < 0.001 {
+                success=true;
+            }
+        }
+
+        assert_eq!(success, true);
+    }
+
+    #[test]
+    // test
+    //    <a --> b>
+    //    <b --> c>
+    //    concl:
+    //    <c --> a> with exemplification
+    pub fn exemplification_a() {
+        let a0 = Term::Name("a".to_string());
+        let b0 = Term::Name("b".to_string());
+        let stmt0 = Term::Stmt(Copula::INH, Box::new(a0), Box::new(b0));
+        
+        let b1 = Term::Name("b".to_string());
+        let c1 = Term::Name("c".to_string());
+        let stmt1 = Term::Stmt(Copula::INH, Box::new(b1), Box::new(c1));
+        
+        println!("{}", &convTermToStr(&stmt0));
+        println!("{}", &convTermToStr(&stmt1));
+        println!("concl:");
+
+        let mut success = false;
+        
+        let mut wereRulesApplied = false;
+        let infConcl = infBinary(&stmt0, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &stmt1, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &mut wereRulesApplied);
+        for iInfConcl in infConcl {
+            let (conclTerm, conclTv, _punct) = iInfConcl;
+            let conclTermStr = convTermToStr(&conclTerm);
+            println!("{} {}", &conclTermStr, crate::Tv::convToStr(&conclTv));
+            if conclTermStr == "<c --> a>" && (conclTv.f - 1.0).abs() < 0.001 && (conclTv.c - 0.4475).abs() < 0.001 {
+                success=true;
+            }
+        }
+
+        assert_eq!(success, true);
+    }
+
+    #[test]
+    // test
+    //    <( <a --> b> && <c --> d> ) ==> x>
+    //    <<a --> b> ==> x>
+    //    concl:
+    //    <c --> d>
+    pub fn condAbd_a() {
+        let a0 = Term::Name("a".to_string());
+        let b0 = Term::Name("b".to_string());
+        let inh0 = Term::Stmt(Copula::INH, Box::new(a0), Box::new(b0));
+        
+        let c0 = Term::Name("c".to_string());
+        let d0 = Term::Name("d".to_string());
+        let inh1 = Term::Stmt(Copula::INH, Box::new(c0), Box::new(d0));
+        
+        let conj0 = Term::Conj(vec![Box::new(inh0.clone()), Box::new(inh1)]);
+        
+        let x0 = Term::Name("x".to_string());
+        let impl0 = Term::Stmt(Copula::IMPL, Box::new(conj0), Box::new(x0.clone()));
+        let impl1 = Term::Stmt(Copula::IMPL, Box::new(inh0), Box::new(x0));
+        
+        println!("{}", &convTermToStr(&impl0));
+        println!("{}", &convTermToStr(&impl1));
+        println!("concl:");
+
+        let mut success = false;
+        
+        let mut wereRulesApplied = false;
+        let infConcl = infBinary(&impl0, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &impl1, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &mut wereRulesApplied);
+        for iInfConcl in infConcl {
+            let (conclTerm, _conclTv, _punct) = iInfConcl;
+            let conclTermStr = convTermToStr(&conclTerm);
+            println!("{}", &conclTermStr);
+            if conclTermStr == "<c --> d>" {
+                success=true;
+            }
+        }
+
+        assert_eq!(success, true);
+    }
+
+    #[test]
+    // test
+    //    <a ==> b>
+    //    concl:
+    //    <(! b ) ==> (! a )>
+    pub fn contraposition_a() {
+        let a0 = Term::Name("a".to_string());
+        let b0 = Term::Name("b".to_string());
+        let impl0 = Term::Stmt(Copula::IMPL, Box::new(a0), Box::new(b0));
+
+        let mut success = false;
+
+        let infConcl = infSinglePremise(&impl0, EnumPunctation::JUGEMENT, &Some(Tv{f:0.0,c:0.9}));
+        for iInfConcl in infConcl {
+            let (conclTerm, conclTv, _punct, _attBias) = iInfConcl;
+            let conclTermStr = convTermToStr(&conclTerm);
+            println!("{}", &conclTermStr);
+            if conclTermStr == "<(! b ) ==> (! a )>" && (conclTv.c - 0.4737).abs() < 0.001 {
+                success=true;
+            }
+        }
+
+        assert_eq!(success, true);
+    }
+
+    #[test]
+    // test
+    //    <a ==> m> {0.8 0.9}
+    //    <c ==> m> {1.0 0.9}
+    //    concl:
+    //    <a ==> c> with abduction
+    pub fn implAbd_a() {
+        let m0 = Term::Name("m".to_string());
+        let impl0 = Term::Stmt(Copula::IMPL, Box::new(Term::Name("a".to_string())), Box::new(m0.clone()));
+        let impl1 = Term::Stmt(Copula::IMPL, Box::new(Term::Name("c".to_string())), Box::new(m0));
+
+        let mut success = false;
+
+        let mut wereRulesApplied = false;
+        let infConcl = infBinary(&impl0, EnumPunctation::JUGEMENT, &Some(Tv{f:0.8,c:0.9}), &impl1, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &mut wereRulesApplied);
+        for iInfConcl in infConcl {
+            let (conclTerm, conclTv, _punct) = iInfConcl;
+            let conclTermStr = convTermToStr(&conclTerm);
+            println!("{} {}", &conclTermStr, crate::Tv::convToStr(&conclTv));
+            if conclTermStr == "<a ==> c>" && (conclTv.f - 0.8).abs() < 0.001 && (conclTv.c - 0.4475).abs() < 0.001 {
+                success=true;
+            }
+        }
+
+        assert_eq!(success, true);
+    }
+
+    #[test]
+    // test
+    //    <a ==> b> {1.0 0.9}
+    //    <b <=> c> {0.8 0.9}
+    //    concl:
+    //    <a ==> c> with analogy
+    pub fn equivAna_a() {
+        let b0 = Term::Name("b".to_string());
+        let impl0 = Term::Stmt(Copula::IMPL, Box::new(Term::Name("a".to_string())), Box::new(b0.clone()));
+        let equiv0 = Term::Stmt(Copula::EQUIV, Box::new(b0), Box::new(Term::Name("c".to_string())));
+
+        let mut success = false;
+
+        let mut wereRulesApplied = false;
+        let infConcl = infBinary(&impl0, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &equiv0, EnumPunctation::JUGEMENT, &Some(Tv{f:0.8,c:0.9}), &mut wereRulesApplied);
+        for iInfConcl in infConcl {
+            let (conclTerm, conclTv, _punct) = iInfConcl;
+            let conclTermStr = convTermToStr(&conclTerm);
+            println!("{} {}", &conclTermStr, crate::Tv::convToStr(&conclTv));
+            if conclTermStr == "<a ==> c>" && (conclTv.f - 0.8).abs() < 0.001 && (conclTv.c - 0.648).abs() < 0.001 {
+                success=true;
+            }
+        }
+
+        assert_eq!(success, true);
+    }
+
+    #[test]
+    // test
+    //    <(! a ) ==> x> {1.0 0.9}
+    //    a {0.0 0.9}
+    //    concl:
+    //    x with deduction of the negated TV of a
+    pub fn implDedNeg_a() {
+        let a0 = Term::Name("a".to_string());
+        let impl0 = Term::Stmt(Copula::IMPL, Box::new(Term::Neg(Box::new(a0.clone()))), Box::new(Term::Name("x".to_string())));
+
+        let mut success = false;
+
+        let mut wereRulesApplied = false;
+        let infConcl = infBinary(&impl0, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &a0, EnumPunctation::JUGEMENT, &Some(Tv{f:0.0,c:0.9}), &mut wereRulesApplied);
+        for iInfConcl in infConcl {
+            let (conclTerm, conclTv, _punct) = iInfConcl;
+            let conclTermStr = convTermToStr(&conclTerm);
+            println!("{} {}", &conclTermStr, crate::Tv::convToStr(&conclTv));
+            if conclTermStr == "x" && (conclTv.f - 1.0).abs() < 0.001 && (conclTv.c - 0.81).abs() < 0.001 {
+                success=true;
+            }
+        }
+
+        assert_eq!(success, true);
+    }
+
+    #[test]
+    // test
+    //    <{tim} --> animal>
+    //    <{tim} --> [fat]>
+    //    concl:
+    //    <<$1 --> animal> ==> <$1 --> [fat]>>
+    //    ( <#1 --> animal> && <#1 --> [fat]> )
+    pub fn varIntro_a() {
+        let tim0 = Term::SetExt(vec![Box::new(Term::Name("tim".to_string()))]);
+        let inh0 = Term::Stmt(Copula::INH, Box::new(tim0.clone()), Box::new(Term::Name("animal".to_string())));
+        let fat0 = Term::SetInt(vec![Box::new(Term::Name("fat".to_string()))]);
+        let inh1 = Term::Stmt(Copula::INH, Box::new(tim0), Box::new(fat0));
+
+        let mut successImpl = false;
+        let mut successConj = false;
+
+        let mut wereRulesApplied = false;
+        let infConcl = infBinary2(&inh0, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &inh1, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), 100, &mut wereRulesApplied);
+        for iInfConcl in infConcl {
+            let (conclTerm, _conclTv, _punct) = iInfConcl;
+            let conclTermStr = convTermToStr(&conclTerm);
+            println!("{}", &conclTermStr);
+            successImpl |= conclTermStr == "<<$1 --> animal> ==> <$1 --> [fat]>>";
+            successConj |= conclTermStr == "( <#1 --> animal> && <#1 --> [fat]> )";
+        }
+
+        assert_eq!(successImpl, true);
+        assert_eq!(successConj, true);
+
+        // symmetric conclusions must be derived only once, ==> in both directions
+        let infConcl = infBinary2(&inh0, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &inh1, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), 100, &mut wereRulesApplied);
+        let countConcls = |pred:&dyn Fn(&Term) -> bool| infConcl.iter().filter(|iv| checkHasVar(&iv.0) && pred(&iv.0)).count();
+        assert_eq!(countConcls(&|t| matches!(t, Term::Stmt(Copula::EQUIV, _, _))), 1);
+        assert_eq!(countConcls(&|t| matches!(t, Term::Conj(_))), 1);
+        assert_eq!(countConcls(&|t| matches!(t, Term::Stmt(Copula::IMPL, _, _))), 2);
+
+        // conclusions of variable introduction are limited by the complexity
+        let infConcl = infBinary2(&inh0, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &inh1, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), 3, &mut wereRulesApplied);
+        assert_eq!(infConcl.iter().any(|iv| checkHasVar(&iv.0)), false);
+
+        // old API uses the built in rules and the complexity limit of the default configuration
+        let a = crate::NarSentence::newEternalSentenceByTv(&inh0, EnumPunctation::JUGEMENT, &Tv{f:1.0,c:0.9}, newStamp(&vec![0]));
+        let b = crate::NarSentence::newEternalSentenceByTv(&inh1, EnumPunctation::JUGEMENT, &Tv{f:1.0,c:0.9}, newStamp(&vec![1]));
+        let concls = inference(&a, &b, &mut wereRulesApplied);
+        assert_eq!(concls.iter().any(|iv| convTermToStr(&iv.0.term) == "<<$1 --> animal> ==> <$1 --> [fat]>>"), true);
+    }
+
+    #[test]
+    // test
+    //    (<#1 --> a> && <#1 --> b>) {1.0 0.9}
+    //    <x --> a> {0.8 0.9}
+    //    concl:
+    //    <x --> b> with deduction
+    pub fn depVarElim_a() {
+        let inh0 = Term::Stmt(Copula::INH, Box::new(Term::DepVar("1".to_string())), Box::new(Term::Name("a".to_string())));
+        let inh1 = Term::Stmt(Copula::INH, Box::new(Term::DepVar("1".to_string())), Box::new(Term::Name("b".to_string())));
+        let conj0 = Term::Conj(vec![Box::new(inh0), Box::new(inh1)]);
+        let inh2 = Term::Stmt(Copula::INH, Box::new(Term::Name("x".to_string())), Box::new(Term::Name("a".to_string())));
+
+        let mut success = false;
+
+        let mut wereRulesApplied = false;
+        let infConcl = infBinary(&conj0, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &inh2, EnumPunctation::JUGEMENT, &Some(Tv{f:0.8,c:0.9}), &mut wereRulesApplied);
         for iInfConcl in infConcl {
-            let (conclTerm, _conclTv, _punct) = iInfConcl;
+            let (conclTerm, conclTv, _punct) = iInfConcl;
             let conclTermStr = convTermToStr(&conclTerm);
-            println!("{}", &conclTermStr);
-            if conclTermStr == "<<c --> d> ==> x>" {
+            println!("{} {}", &conclTermStr, crate::Tv::convToStr(&conclTv));
+            if conclTermStr == "<x --> b>" && (conclTv.f - 0.8).abs() < 0.001 && (conclTv.c - 0.648).abs() < 0.001 {
                 success=true;
             }
         }
 
         assert_eq!(success, true);
     }
+
+    // goal deduction
+    // <a ==> x>.  x!  |- a!
+    #[test]
+    pub fn goalDed_a() {
+        let a = Term::Name("a".to_string());
+        let x = Term::Name("x".to_string());
+        let impl0 = Term::Stmt(Copula::IMPL, Box::new(a.clone()), Box::new(x.clone()));
+
+        let mut wereRulesApplied = false;
+        let infConcl = infBinary(&x, EnumPunctation::GOAL, &Some(Tv{f:1.0,c:0.9}), &impl0, EnumPunctation::JUGEMENT, &Some(Tv{f:1.0,c:0.9}), &mut wereRulesApplied);
+        assert_eq!(infConcl.len(), 1);
+        let (conclTerm, conclTv, punct) = &infConcl[0];
+        assert_eq!(convTermToStr(conclTerm), "a");
+        assert_eq!(*punct, EnumPunctation::GOAL);
+        assert!((conclTv.c - 0.81).abs() < 0.0001);
+    }
+
+    // goal derivation with procedural knowledge which is unified by the predicate
+    // <(a,^x({SELF},$1)) =/> <$1 --> b>>.  <c --> b>!  |- (a,^x({SELF},c))!
+    #[test]
+    pub fn predImplUnifyPred_a() {
+        let parseTerm = |narsese:&str| -> Term {crate::NarseseParser::parseNarsese(&narsese.to_string()).ok().unwrap().0};
+        let belief = crate::NarSentence::newEternalSentenceByTv(&parseTerm("<(a,^x({SELF},$1)) =/> <$1 --> b>>."), EnumPunctation::JUGEMENT, &Tv{f:1.0,c:0.9}, newStamp(&vec![0]));
+        let goal = crate::NarSentence::newEternalSentenceByTv(&parseTerm("<c --> b>!"), EnumPunctation::GOAL, &Tv{f:1.0,c:0.9}, newStamp(&vec![1]));
+
+        let concl:Sentence = NarInfProcedural::infGoalBelief(&goal, &belief).unwrap();
+        assert_eq!(convTermToStr(&concl.term), convTermToStr(&parseTerm("(a,^x({SELF},c))!")));
+
+        let otherGoal = crate::NarSentence::newEternalSentenceByTv(&parseTerm("<c --> d>!"), EnumPunctation::GOAL, &Tv{f:1.0,c:0.9}, newStamp(&vec![1]));
+        assert_eq!(NarInfProcedural::infGoalBelief(&otherGoal, &belief).is_none(), true);
+    }
+
+    // procedural / declarative bridge
+    // b!  <a ==> b>.  |- a!
+    // {tim}  <{tim} --> cat>.  |- cat
+    #[test]
+    pub fn declBridge_a() {
+        let parseTerm = |narsese:&str| -> Term {crate::NarseseParser::parseNarsese(&narsese.to_string()).ok().unwrap().0};
+        let mut declMem = NarMem::make();
+        for (idx, iNarsese) in ["<a ==> b>.", "<{tim} --> cat>."].iter().enumerate() {
+            let belief = crate::NarSentence::newEternalSentenceByTv(&parseTerm(iNarsese), EnumPunctation::JUGEMENT, &Tv{f:1.0,c:0.9}, newStamp(&vec![idx as i64]));
+            NarMem::storeInConcepts(&mut declMem, &belief, 10, 0);
+        }
+
+        let goal = crate::NarSentence::newEternalSentenceByTv(&parseTerm("b!"), EnumPunctation::GOAL, &Tv{f:1.0,c:0.9}, newStamp(&vec![5]));
+        let concls:Vec<Sentence> = crate::NarGoalSystem::deriveGoalsByDecl(&goal, &declMem);
+        assert_eq!(concls.len(), 1);
+        assert_eq!(convTermToStr(&concls[0].term), "a");
+
+        let generalized:Vec<(Term, f64)> = crate::NarGoalSystem::retGeneralizedEvents(&parseTerm("{tim}."), &declMem);
+        assert_eq!(generalized.len(), 1);
+        assert_eq!(convTermToStr(&generalized[0].0), "cat");
+    }
+
+    // rules loaded from the rule DSL, enabling and disabling of rules and statistics
+    #[test]
+    pub fn ruleTable_a() {
+        let parseTerm = |narsese:&str| -> Term {crate::NarseseParser::parseNarsese(&narsese.to_string()).ok().unwrap().0};
+        let a = parseTerm("<a --> b>.");
+        let b = parseTerm("<b --> c>.");
+        let tv = Some(Tv{f:1.0,c:0.9});
+
+        let mut table = createRuleTable();
+        for iRule in parseRules("// deduction\nded2: <?S --> ?M>. ; <?M --> ?P>. |- <?S --> ?P>. ded\n").unwrap() {
+            ruleTableAdd(&mut table, Arc::new(iRule));
+        }
+
+        let mut wereRulesApplied = false;
+        let concls = infBinaryByRules(&table, &a, EnumPunctation::JUGEMENT, &tv, &b, EnumPunctation::JUGEMENT, &tv, 100, &mut wereRulesApplied);
+        assert_eq!(concls.iter().map(|iv| convTermToStr(&iv.0)).collect::<Vec<String>>(), vec!["<a --> c>".to_string()]);
+        assert_eq!(wereRulesApplied, true);
+        assert_eq!(ruleTableRetStats(&table), vec![RuleStats{name:"ded2".to_string(), enabled:true, nTried:2, nFired:1, nConclusions:1}]);
+
+        assert_eq!(ruleTableSetEnabled(&mut table, "ded2", false), true);
+        assert_eq!(infBinaryByRules(&table, &a, EnumPunctation::JUGEMENT, &tv, &b, EnumPunctation::JUGEMENT, &tv, 100, &mut wereRulesApplied).len(), 0);
+        assert_eq!(ruleTableSetEnabled(&mut table, "unknown", false), false);
+
+        assert_eq!(parseRules("ded3: <?S --> ?M>. |- <?S --> ?M>. ded").err().map(|iv| (iv.kind, iv.line)), Some((crate::NarRule::EnumRuleParseErrorKind::UNKNOWN_TV_FN, Some(1))));
+    }
+
+    // ; inside of a premise is the parallel conjunction, premises with variables don't match, the shared table doesn't count
+    #[test]
+    pub fn ruleTable_b() {
+        let parseTerm = |narsese:&str| -> Term {crate::NarseseParser::parseNarsese(&narsese.to_string()).ok().unwrap().0};
+        let tv = Some(Tv{f:1.0,c:0.9});
+
+        let mut table = createRuleTable();
+        for iRule in parseRules("parDed: <(?A;?B) =/> ?C>. ; <?C --> ?D>. |- <(?A;?B) =/> <?C --> ?D>>. ded\n").unwrap() {
+            assert_eq!(iRule.premises.len(), 2);
+            ruleTableAdd(&mut table, Arc::new(iRule));
+        }
+
+        let mut wereRulesApplied = false;
+        let a = parseTerm("<(x;y) =/> z>.");
+        let b = parseTerm("<z --> w>.");
+        let concls = infBinaryByRules(&table, &a, EnumPunctation::JUGEMENT, &tv, &b, EnumPunctation::JUGEMENT, &tv, 100, &mut wereRulesApplied);
+        assert_eq!(concls.iter().map(|iv| convTermToStr(&iv.0)).collect::<Vec<String>>(), vec!["<( x ; y ) =/> <z --> w>>".to_string()]);
+
+        let aWithVar = parseTerm("<(x;$1) =/> z>.");
+        assert_eq!(infBinaryByRules(&table, &aWithVar, EnumPunctation::JUGEMENT, &tv, &b, EnumPunctation::JUGEMENT, &tv, 100, &mut wereRulesApplied).len(), 0);
+
+        // brackets of NAL-2 copulas must not be counted when the premises are split
+        for iRule in parseRules("instDed: <?A {-- ?B>. ; <?B --> ?C>. |- <?A {-- ?C>. ded\n").unwrap() {
+            assert_eq!(iRule.premises.len(), 2);
+            ruleTableAdd(&mut table, Arc::new(iRule));
+        }
+        let concls = infBinaryByRules(&table, &parseTerm("<tom {-- cat>."), EnumPunctation::JUGEMENT, &tv, &parseTerm("<cat --> animal>."), EnumPunctation::JUGEMENT, &tv, 100, &mut wereRulesApplied);
+        assert_eq!(concls.iter().map(|iv| (convTermToStr(&iv.0), iv.1.f, (iv.1.c*100.0).round())).collect::<Vec<_>>(), vec![("<{tom} --> animal>".to_string(), 1.0, 81.0)]);
+
+        infBinary2(&parseTerm("<a --> b>."), EnumPunctation::JUGEMENT, &tv, &parseTerm("<b --> c>."), EnumPunctation::JUGEMENT, &tv, 100, &mut wereRulesApplied);
+        assert_eq!(ruleTableRetStats(retBuiltinRuleTable()).iter().all(|iv| iv.nTried == 0), true);
+    }
+
+    #[test]
+    // question <?x --> animal>? must remember the best answer for every binding of ?x
+    pub fn qaBindings() {
+        let animal = Term::Name("animal".to_string());
+        let question = Term::Stmt(Copula::INH, Box::new(Term::QVar("x".to_string())), Box::new(animal.clone()));
+        let mut qTask = Task2 {
+            sentence:crate::NarSentence::newEternalSentenceByTv(&question, EnumPunctation::QUESTION, &Tv{f:1.0,c:0.0}, newStamp(&vec![0])),
+            handler:None,
+            bestAnswerExp:0.0,
+            bindings:vec![],
+            prio:1.0,
+        };
+
+        for (name, f, c) in &[("dog", 0.8, 0.9), ("cat", 1.0, 0.9), ("dog", 1.0, 0.5), ("dog", 1.0, 0.8)] {
+            let term = Term::Stmt(Copula::INH, Box::new(Term::Name(name.to_string())), Box::new(animal.clone()));
+            let answer = crate::NarSentence::newEternalSentenceByTv(&term, EnumPunctation::JUGEMENT, &Tv{f:*f,c:*c}, newStamp(&vec![1]));
+            qaTryAnswer(&mut qTask, &answer, 100, &mut vec![]);
+        }
+
+        let boundNames:Vec<String> = qTask.bindings.iter().map(|iv| convTermToStr(&iv.binding[0].val)).collect();
+        assert_eq!(boundNames, vec!["cat", "dog"]);
+        assert_eq!((qTask.bindings[1].exp - 0.9).abs() < 0.001, true); // best answer for dog has f=1.0 c=0.8
+    }
+
+    #[test]
+    // the quality of the budget is the fraction of the credit which doesn't decay
+    pub fn taskQuality() {
+        let term = Term::Name("a".to_string());
+        let mut task = Task {
+            sentence:crate::NarSentence::newEternalSentenceByTv(&term, EnumPunctation::JUGEMENT, &Tv{f:1.0,c:0.9}, newStamp(&vec![0])),
+            credit:1.0,
+            qaCredit:0.0,
+            mulCredit:1.0,
+            quality:0.0,
+            id:0,
+            derivTime:0,
+        };
+        assert_eq!(taskCalcCredit(&task, 10000) < 0.01, true);
+        task.quality = 0.3;
+        assert_eq!((taskCalcCredit(&task, 10000) - 0.3).abs() < 0.0001, true);
+        assert_eq!((taskCalcCredit(&task, 0) - 1.0).abs() < 0.0001, true);
+    }
+
+    // observer which records events and calls back into the NAR when it gets a answer
+    struct TestObserver {
+        shared: Arc<RwLock<DeclarativeShared>>,
+        derivations: Vec<String>,
+        answers: Vec<String>,
+    }
+
+    impl NarObserver for TestObserver {
+        fn derived(&mut self, conclusion:&Sentence, premises:&[Sentence]) {
+            self.derivations.push(format!("{} {}", convTermToStr(&conclusion.term), premises.len()));
+        }
+
+        fn answered(&mut self, _question:&Sentence, answer:&Sentence) {
+            self.answers.push(convTermToStr(&answer.term));
+            let question = crate::NarSentence::newEternalSentenceByTv(&answer.term, EnumPunctation::QUESTION, &Tv{f:1.0,c:0.0}, newStamp(&vec![100]));
+            memAddQuestionTask(&self.shared, &question, 1.0, None); // must not deadlock
+        }
+    }
+
+    #[test]
+    // observers get notified about derivations and answers and can call back into the NAR
+    pub fn observer_a() {
+        let mut nar = crate::Nar::createNarWithConfig(crate::NarConfig::NarConfigBuilder::new().seed(42).syncDerivation(true).build().unwrap());
+        let observer = Arc::new(RwLock::new(TestObserver{shared:Arc::clone(&nar.mem.read().shared), derivations:vec![], answers:vec![]}));
+        crate::Nar::addObserver(&nar, Arc::clone(&observer) as Arc<RwLock<dyn NarObserver>>);
+
+        for iNarsese in &["<a --> b>.", "<b --> c>."] {
+            crate::Nar::inputN(&mut nar, &iNarsese.to_string()).unwrap();
+        }
+        let question = crate::NarSentence::newEternalSentenceByTv(&crate::NarseseParser::parseNarsese(&"<a --> c>?".to_string()).ok().unwrap().0, EnumPunctation::QUESTION, &Tv{f:1.0,c:0.0}, newStamp(&vec![99]));
+        memAddQuestionTask(&nar.mem.read().shared, &question, 1.0, None);
+        for _i in 0..50 {
+            crate::Nar::cycle(&mut nar);
+        }
+
+        let observerGuard = observer.read();
+        assert_eq!(observerGuard.derivations.iter().any(|iv| iv == "<a --> c> 2"), true);
+        assert_eq!(observerGuard.answers.first(), Some(&"<a --> c>".to_string()));
+    }
+
+    // observer which logs all events as text
+    struct LogObserver {
+        log: Vec<String>,
+    }
+
+    impl NarObserver for LogObserver {
+        fn derived(&mut self, conclusion:&Sentence, _premises:&[Sentence]) {
+            self.log.push(format!("derived {}", convSentenceTermPunctToStr(conclusion, true)));
+        }
+        fn opExecuted(&mut self, op:&Term, exp:Option<f64>) {
+            self.log.push(format!("exec {} {:?}", convTermToStr(op), exp));
+        }
+        fn anticipationConfirmed(&mut self, evidence:&Sentence) {
+            self.log.push(format!("confirmed {}", convSentenceTermPunctToStr(evidence, true)));
+        }
+        fn anticipationFailed(&mut self, evidence:&Sentence) {
+            self.log.push(format!("failed {}", convSentenceTermPunctToStr(evidence, true)));
+        }
+    }
+
+    // runs a seeded NAR with declarative and procedural input and returns the log of events
+    fn runSeeded(seed:u64) -> Vec<String> {
+        let mut nar = crate::Nar::createNarWithConfig(crate::NarConfig::NarConfigBuilder::new().seed(seed).syncDerivation(true).build().unwrap());
+        let observer = Arc::new(RwLock::new(LogObserver{log:vec![]}));
+        crate::Nar::addObserver(&nar, Arc::clone(&observer) as Arc<RwLock<dyn NarObserver>>);
+
+        let mut quit = false;
+        for iLine in &["!por NOP ^left", "!por NOP ^right", "<a --> b>.", "<b --> c>.", "<c --> d>.", "<d --> e>."] {
+            crate::NarInputFacade::input(&mut nar, &iLine.to_string(), &mut quit).unwrap();
+        }
+        for iStep in 0..40 {
+            let line = if iStep % 3 == 0 {"x. :|:"} else {"y! :|:"};
+            crate::NarInputFacade::input(&mut nar, &line.to_string(), &mut quit).unwrap();
+            crate::NarInputFacade::input(&mut nar, &"!s5".to_string(), &mut quit).unwrap();
+            crate::NarInputFacade::input(&mut nar, &"!sp".to_string(), &mut quit).unwrap();
+        }
+
+        let log = observer.read().log.clone();
+        log
+    }
+
+    #[test]
+    // two runs with the same seed must do exactly the same
+    pub fn deterministic_a() {
+        let log0 = runSeeded(23);
+        let log1 = runSeeded(23);
+        assert_eq!(log0.iter().any(|iv| iv.starts_with("derived")), true);
+        assert_eq!(log0.iter().any(|iv| iv.starts_with("exec")), true);
+        assert_eq!(log0, log1);
+    }
 }
 
 
-/// do inference of two sentences
+/// do inference of two sentences with the built in rules
+///
+/// variable introduction is limited by the complexity of the default configuration
 /// /param wereRulesApplied is true if any rules were applied
 pub fn inference2(
     paTerm:&Term, paPunct:EnumPunctation, paStamp:&Stamp, paTv:&Option<Tv>,  
     pbTerm:&Term, pbPunct:EnumPunctation, pbStamp:&Stamp, pbTv:&Option<Tv>, 
     wereRulesApplied:&mut bool
+)->Vec<(Sentence,f64)> {
+    inference2ByRules(paTerm, paPunct, paStamp, paTv, pbTerm, pbPunct, pbStamp, pbTv, retBuiltinRuleTable(), NarConfig::default().maxComplexity, wereRulesApplied)
+}
+
+/// do inference of two sentences
+/// /param rules table of the rules which are applied
+/// /param maxComplexity maximal complexity of conclusions of variable introduction
+/// /param wereRulesApplied is true if any rules were applied
+pub fn inference2ByRules(
+    paTerm:&Term, paPunct:EnumPunctation, paStamp:&Stamp, paTv:&Option<Tv>,  
+    pbTerm:&Term, pbPunct:EnumPunctation, pbStamp:&Stamp, pbTv:&Option<Tv>, 
+    rules:&RuleTable,
+    maxComplexity:i64,
+    wereRulesApplied:&mut bool
 )->Vec<(Sentence,f64)> {
     *wereRulesApplied = false;
 
     let mut concl = vec![];
 
-    let infConcl = infBinary(&paTerm, paPunct, paTv, &pbTerm, pbPunct, pbTv, wereRulesApplied);
+    let infConcl = infBinaryByRules(rules, &paTerm, paPunct, paTv, &pbTerm, pbPunct, pbTv, maxComplexity, wereRulesApplied);
     for iInfConcl in infConcl {
         let (term, tv, punct) = iInfConcl;
         concl.push((Sentence{
-            term:Arc::new(term.clone()),
+            term:Arc::new(normalize(&term)),
             evi:if true {Some(Evidence::TV(tv.clone()))} else {None},
             stamp:merge(&paStamp, &pbStamp),
             t:None, // time of occurence 
@@ -941,25 +1971,37 @@ pub fn inference2(
 }
 
 
-/// do inference of two sentences
+/// do inference of two sentences with the built in rules
+///
+/// variable introduction is limited by the complexity of the default configuration
 /// /param wereRulesApplied is true if any rules were applied
 pub fn inference(pa:&Sentence, pb:&Sentence, wereRulesApplied:&mut bool)->Vec<(Sentence,f64)> {
-    inference2(
+    inferenceByRules(pa, pb, retBuiltinRuleTable(), NarConfig::default().maxComplexity, wereRulesApplied)
+}
+
+/// do inference of two sentences
+/// /param rules table of the rules which are applied
+/// /param maxComplexity maximal complexity of conclusions of variable introduction
+/// /param wereRulesApplied is true if any rules were applied
+pub fn inferenceByRules(pa:&Sentence, pb:&Sentence, rules:&RuleTable, maxComplexity:i64, wereRulesApplied:&mut bool)->Vec<(Sentence,f64)> {
+    inference2ByRules(
         &pa.term, pa.punct, &pa.stamp, &retTv(&pa),  
         &pb.term, pb.punct, &pb.stamp, &retTv(&pb), 
+        rules,
+        maxComplexity,
         wereRulesApplied
     )
 }
 
-pub fn infSinglePremise2(pa:&Sentence) -> Vec<(Sentence,f64)> {
+pub fn infSinglePremise2(pa:&Sentence, rules:&RuleTable) -> Vec<(Sentence,f64)> {
     let mut concl = vec![];
 
-    let infConcl = infSinglePremise(&pa.term, pa.punct, &retTv(pa));
+    let infConcl = infSinglePremiseByRules(rules, &pa.term, pa.punct, &retTv(pa));
     for iInfConcl in infConcl {
         let (term, tv, punct, attBias) = iInfConcl;
         
         concl.push((Sentence{
-            term:Arc::new(term.clone()),
+            term:Arc::new(normalize(&term)),
             evi:if true {Some(Evidence::TV(tv.clone()))} else {None},
             stamp:pa.stamp.clone(),
             t:None, // time of occurence 
@@ -982,6 +2024,8 @@ pub struct Task {
     pub qaCredit:f64,
     /// mul for credit
     pub mulCredit:f64,
+    /// quality, fraction of the credit which doesn't decay
+    pub quality:f64,
 
     /// unique id to quickly find unique tasks
     pub id:i64,
@@ -997,7 +2041,8 @@ pub fn taskCalcCredit(task:&Task, cycleCounter:i64) -> f64 {
     let decayFactor:f64 = (-decayFactor * (dt as f64)).exp();
 
     let qaCredit:f64 = task.qaCredit*0.2; // limit Q&A credit to a low range, to give other tasks a higher chance
-    (qaCredit + task.credit)*decayFactor*task.mulCredit // multiply because we want to decay the actual "base credit"
+    let decayedCredit:f64 = (qaCredit + task.credit)*decayFactor;
+    decayedCredit.max(task.credit*task.quality)*task.mulCredit // multiply because we want to decay the actual "base credit"
 }
 
 /// task for a question
@@ -1007,11 +2052,26 @@ pub struct Task2 {
     pub handler:Option< Arc<RwLock< dyn QHandler>> >,
     /// expectation of best answer
     pub bestAnswerExp:f64,
+    /// best answer for each distinct binding of the query variables, ordered by expectation
+    pub bindings:Vec<QaBinding>,
     /// priority
     pub prio:f64,
 }
 
 
+/// answer for one binding of the query variables of a question
+// PUBLICAPI
+#[derive(Clone)]
+pub struct QaBinding {
+    /// values of the query variables, ordered by the name of the variable
+    pub binding:Vec<Asgnment>,
+    /// best answer with this binding
+    pub answer:Sentence,
+    /// expectation of the answer
+    pub exp:f64,
+}
+
+
 /// stores the message for the actual work
 pub struct DeriverWorkMessage {
     pub primary: Arc<RwLock<Task>>,
@@ -1030,7 +2090,7 @@ use std::sync::atomic::{AtomicI64, Ordering};
 /// all other memory is in nonshared portion!
 pub struct DeclarativeShared {
     pub judgementTasks:Vec<Arc<RwLock<Task>>>,
-    pub judgementTasksByTerm:Arc<RwLock< HashMap<Term, Vec<Arc<RwLock<Task>>>> >>, // for fast lookup
+    pub judgementTasksByTerm:Arc<RwLock< HashMap<TermId, Vec<Arc<RwLock<Task>>>> >>, // for fast lookup by interned term
 
     pub questionTasks:Arc<RwLock< Vec<Box<Task2>> >>,
 
@@ -1045,15 +2105,25 @@ pub struct DeclarativeShared {
 
     /// procedural memory for temporal Q&A
     pub procMem: Arc<RwLock<NarMem::Mem>>,
+
+    /// rules which are used for derivation
+    pub rules: Arc<RwLock<RuleTable>>,
 }
 
 /// memory of NAR for eternal beliefs
 pub struct Mem2 {
     pub shared:Arc<RwLock<DeclarativeShared>>,
 
-    /// global handlers for Q&A
+    /// observers which get notified about events of the reasoner
+    pub observers: Observers,
+
+    /// handlers which are called for every answer
+    #[deprecated(note = "register a NarObserver with Nar::addObserver() and implement answered() instead")]
     pub globalQaHandlers: Arc<RwLock<  Vec<Arc<RwLock< dyn QHandler>>>  >>,
-    pub rng: RwLock<ThreadRng>,
+
+    /// configuration of the declarative reasoner
+    pub cfg: NarConfig,
+    pub rng: RwLock<StdRng>,
     
     /// array of workers
     pub deriverWorkers: Vec<JoinHandle<()>>,
@@ -1061,14 +2131,14 @@ pub struct Mem2 {
     pub deriverWorkersTx: Vec<SyncSender<DeriverWorkMessage>>,
 }
 
-pub fn createMem2(cfg__maxComplexity: i64, cfg__nConceptBeliefs:usize)->Arc<RwLock<Mem2>> {
-    let mem0:NarMem::Mem = NarMem::Mem{
-        concepts:HashMap::new(),
-    };
+pub fn createMem2(cfg:&NarConfig)->Arc<RwLock<Mem2>> {
+    let cfg__maxComplexity: i64 = cfg.maxComplexity;
+    let cfg__nConceptBeliefs: usize = cfg.nConceptBeliefs;
+    let cfg__qaNMaxBindings: usize = cfg.qaNMaxBindings;
+
+    let mem0:NarMem::Mem = NarMem::make();
     let memArc:Arc<RwLock<NarMem::Mem>> = Arc::new(RwLock::new(mem0));
-    let mem1:NarMem::Mem = NarMem::Mem{
-        concepts:HashMap::new(),
-    };
+    let mem1:NarMem::Mem = NarMem::make();
     let procMemArc:Arc<RwLock<NarMem::Mem>> = Arc::new(RwLock::new(mem1)); // create dummy procedural memory by default, we need to link it up externally
 
     let shared = DeclarativeShared {
@@ -1081,32 +2151,39 @@ pub fn createMem2(cfg__maxComplexity: i64, cfg__nConceptBeliefs:usize)->Arc<RwLo
         taskIdCounter:Arc::new(AtomicI64::new(1000)), // high number to easy debugging to prevent confusion
         cycleCounter:AtomicI64::new(0),
         procMem:Arc::clone(&procMemArc),
+        rules:Arc::new(RwLock::new(createBuiltinRuleTable())),
     };
 
 
+    // handlers in globalQaHandlers are called by a observer to keep the old API working
+    let globalQaHandlers:Arc<RwLock<Vec<Arc<RwLock<dyn QHandler>>>>> = Arc::new(RwLock::new(vec![]));
+    let observers:Observers = makeObservers();
+    observers.write().push(Arc::new(RwLock::new(QHandlersObserver{handlers:Arc::clone(&globalQaHandlers)})));
+
+    #[allow(deprecated)]
     let res:Mem2 = Mem2{
         shared:Arc::new(RwLock::new(shared)),
 
-        globalQaHandlers:Arc::new(RwLock::new(vec![])), 
-        rng:RwLock::new(rand::thread_rng()),
+        observers:observers,
+        globalQaHandlers:globalQaHandlers,
+        cfg:cfg.clone(),
+        rng:RwLock::new(createRng(cfg, RNG_STREAM_DECL)),
 
         deriverWorkers:vec![],
         deriverWorkersTx:vec![],
     };
     let resArc:Arc<RwLock<Mem2>> = Arc::new(RwLock::new(res));
 
-    { // create workers for derivation
+    if !cfg.syncDerivation { // create workers for derivation
         let (tx, rx) = sync_channel(4); // create channel with fixed size, reason is that we want to limit backlog!
         resArc.write().deriverWorkersTx.push(tx);
 
         let sharedArc:Arc<RwLock<DeclarativeShared>> = Arc::clone(&resArc.read().shared);
-        let globalQaHandlers = Arc::clone(&resArc.read().globalQaHandlers);
+        let observers = Arc::clone(&resArc.read().observers);
+        let mut rng = createRng(cfg, RNG_STREAM_DECL_WORKER);
         // let cfg__nConceptBeliefs = cfg__nConceptBeliefs;
 
         resArc.write().deriverWorkers.push(thread::spawn(move|| {
-            let cfgEnInstrumentation = false;
-            let mut rng = rand::thread_rng();
-
             loop {
                 let msgRes = rx.recv();
                 if !msgRes.is_ok() {
@@ -1115,195 +2192,242 @@ pub fn createMem2(cfg__maxComplexity: i64, cfg__nConceptBeliefs:usize)->Arc<RwLo
                 let msg:DeriverWorkMessage = msgRes.unwrap(); // receive message
                 //println!("[WORKER] received MSG!");//DBG
 
-                let currentTime: i64 = msg.cycleCounter; // HACK< we source the time here from the cycleCounter of the declarative reasoner >    TODO< maintain only one real time! >
+                deriveAndWriteBack(&sharedArc, &observers, msg, &mut rng, cfg__maxComplexity, cfg__nConceptBeliefs, cfg__qaNMaxBindings, true);
+            }
+        }));
+    }
+    
 
-                /////////
-                // DERIVE
-                /////////
-                let mut concl:Vec<(Sentence,f64)> = vec![]; // conclusions, which are the sentences with "attention bias" factor
+    resArc
+}
 
-                { // single premise derivation
-                    let mut concl2: Vec<(Sentence,f64)> = infSinglePremise2(&msg.primary.read().sentence);
-                    concl.append(&mut concl2);
-                }
+/// does the derivation of the work message and puts the conclusions back into memory
+///
+/// is called by the worker thread or directly by the reasoning cycle if derivation is synchronous
+/// /param enThreads does it use a additional thread for derivation? must be false for reproducible derivation
+pub fn deriveAndWriteBack(sharedArc:&Arc<RwLock<DeclarativeShared>>, observers:&Observers, msg:DeriverWorkMessage, rng:&mut StdRng, cfg__maxComplexity:i64, cfg__nConceptBeliefs:usize, cfg__qaNMaxBindings:usize, enThreads:bool) {
+    let cfgEnInstrumentation = false;
 
-                let enInferenceSampleSecondaryByCredit = false; // do we sample secondary premise randomly by credit?
-                let enInferenceSecondaryAll = true; // do we select and process all secondary premises (like in ALANN)
+    let currentTime: i64 = msg.cycleCounter; // HACK< we source the time here from the cycleCounter of the declarative reasoner >    TODO< maintain only one real time! >
 
-                if enInferenceSampleSecondaryByCredit { // sample secondary premise randomly by credit?
-                    // sample from secondaryElligable by priority
-                    let selVal:f64 = rng.gen_range(0.0..1.0);
-                    let secondarySelTaskIdx = taskSelByCreditRandom(selVal, &msg.secondary, msg.cycleCounter);
-                    let secondarySelTask: &Arc<RwLock<Task>> = &msg.secondary[secondarySelTaskIdx];
+    /////////
+    // DERIVE
+    /////////
+    let mut concl:Vec<(Sentence,f64)> = vec![]; // conclusions, which are the sentences with "attention bias" factor
+    let mut conclSecondary:Vec<Option<Sentence>> = vec![]; // secondary premise of each conclusion, None for single premise derivations
 
-                    // debug premises
-                    {
-                        println!("TRACE do inference...");
+    let rules:Arc<RwLock<RuleTable>> = Arc::clone(&sharedArc.read().rules);
+    // NOTE< the rules are only locked for one inference, because the table may get changed concurrently >
 
-                        {
-                            let taskSentenceAsStr = convSentenceTermPunctToStr(&msg.primary.read().sentence, false);
-                            //println!("TRACE   primary   task  {}  credit={}", taskSentenceAsStr, taskCalcCredit(&selPrimaryTask.lock().unwrap(), mem.cycleCounter));    
-                        }
-                        {
-                            let taskSentenceAsStr = convSentenceTermPunctToStr(&secondarySelTask.read().sentence, false);
-                            //println!("TRACE   secondary task  {}  credit={}", taskSentenceAsStr, taskCalcCredit(&secondarySelTask.lock().unwrap(), mem.cycleCounter));
-                        }
-                    }
+    { // single premise derivation
+        let mut concl2: Vec<(Sentence,f64)> = infSinglePremise2(&msg.primary.read().sentence, &rules.read());
+        conclSecondary.extend(concl2.iter().map(|_| None));
+        concl.append(&mut concl2);
+    }
 
-                    // do inference with premises
-                    let mut wereRulesApplied = false;
-                    let mut concl2: Vec<(Sentence,f64)> = inference(&msg.primary.read().sentence, &secondarySelTask.read().sentence, &mut wereRulesApplied);
-                    concl.append(&mut concl2);
-                }
+    let enInferenceSampleSecondaryByCredit = false; // do we sample secondary premise randomly by credit?
+    let enInferenceSecondaryAll = true; // do we select and process all secondary premises (like in ALANN)
 
+    if enInferenceSampleSecondaryByCredit { // sample secondary premise randomly by credit?
+        // sample from secondaryElligable by priority
+        let selVal:f64 = rng.gen_range(0.0..1.0);
+        let secondarySelTaskIdx = taskSelByCreditRandom(selVal, &msg.secondary, msg.cycleCounter);
+        let secondarySelTask: &Arc<RwLock<Task>> = &msg.secondary[secondarySelTaskIdx];
 
-                if enInferenceSecondaryAll {
-                    let timeStart = Instant::now();
+        // debug premises
+        {
+            println!("TRACE do inference...");
 
-                    let secondaryElligablePartA = &msg.secondary[..msg.secondary.len()/2];
-                    let secondaryElligablePartB2 = msg.secondary[msg.secondary.len()/2..].to_vec();
-                    let secondaryElligablePartB:Vec<(Term,EnumPunctation,Stamp,Option<Tv>)> = msg.secondary.iter().map(|s| {
-                        let s2:&Sentence = &s.read().sentence;
-                        ((*s2.term).clone(), s2.punct, s2.stamp.clone(), retTv(&s2))
-                    }).collect();
+            {
+                let taskSentenceAsStr = convSentenceTermPunctToStr(&msg.primary.read().sentence, false);
+                //println!("TRACE   primary   task  {}  credit={}", taskSentenceAsStr, taskCalcCredit(&selPrimaryTask.lock().unwrap(), mem.cycleCounter));    
+            }
+            {
+                let taskSentenceAsStr = convSentenceTermPunctToStr(&secondarySelTask.read().sentence, false);
+                //println!("TRACE   secondary task  {}  credit={}", taskSentenceAsStr, taskCalcCredit(&secondarySelTask.lock().unwrap(), mem.cycleCounter));
+            }
+        }
 
-                    let selPrimarySentenceTuple;
-                    {
-                        let s2:&Sentence = &msg.primary.read().sentence;
-                        selPrimarySentenceTuple = ((*s2.term).clone(), s2.punct, s2.stamp.clone(), retTv(&s2))
-                    }
+        // do inference with premises
+        let mut wereRulesApplied = false;
+        let mut concl2: Vec<(Sentence,f64)> = inferenceByRules(&msg.primary.read().sentence, &secondarySelTask.read().sentence, &rules.read(), cfg__maxComplexity, &mut wereRulesApplied);
+        conclSecondary.extend(concl2.iter().map(|_| Some(secondarySelTask.read().sentence.clone())));
+        concl.append(&mut concl2);
+    }
 
-                    let handleB = thread::spawn(move|| {
-                        let mut res = vec![];
-                        for iSecondarySentence in &secondaryElligablePartB {
-                            let mut wereRulesApplied = false;
-                            let mut concl2: Vec<(Sentence,f64)> = inference2(
-                                &selPrimarySentenceTuple.0, selPrimarySentenceTuple.1, &selPrimarySentenceTuple.2, &selPrimarySentenceTuple.3,
-                                &iSecondarySentence.0, iSecondarySentence.1, &iSecondarySentence.2, &iSecondarySentence.3, 
-                                &mut wereRulesApplied
-                            );
-                            res.append(&mut concl2);
-                        }
-                        res
-                    });
 
-                    let selPrimaryTaskSentence:&Sentence = &msg.primary.read().sentence;
-                    for iSecondaryTask in secondaryElligablePartA {
-                        // do inference and add conclusions to array
-                        if !Arc::ptr_eq(&msg.primary, &iSecondaryTask) { // arcs must not point to same task!
-                            let mut wereRulesApplied = false;
-                            let mut concl2: Vec<(Sentence,f64)> = inference(selPrimaryTaskSentence, &iSecondaryTask.read().sentence, &mut wereRulesApplied);
-                            concl.append(&mut concl2);
-                        }
-                    }
-                    
-                    let mut conclPartB = handleB.join().unwrap();
-                    concl.append(&mut conclPartB);
+    if enInferenceSecondaryAll {
+        let timeStart = Instant::now();
 
-                    if cfgEnInstrumentation {
-                        println!("[instr] secondard inf took {}us", timeStart.elapsed().as_micros());
-                    }
+        let secondaryElligablePartA = &msg.secondary[..msg.secondary.len()/2];
+        let secondaryElligablePartB2 = msg.secondary[msg.secondary.len()/2..].to_vec();
+        let secondaryElligablePartB:Vec<(Term,EnumPunctation,Stamp,Option<Tv>)> = msg.secondary.iter().map(|s| {
+            let s2:&Sentence = &s.read().sentence;
+            ((*s2.term).clone(), s2.punct, s2.stamp.clone(), retTv(&s2))
+        }).collect();
 
-                }
+        let selPrimarySentenceTuple;
+        {
+            let s2:&Sentence = &msg.primary.read().sentence;
+            selPrimarySentenceTuple = ((*s2.term).clone(), s2.punct, s2.stamp.clone(), retTv(&s2))
+        }
 
+        let rulesB:Arc<RwLock<RuleTable>> = Arc::clone(&rules);
+        let inferPartB = move|| {
+            let mut res = vec![];
+            let mut resSecondaryIdxs:Vec<usize> = vec![]; // index of the secondary premise of each conclusion
+            for (iSecondaryIdx, iSecondarySentence) in secondaryElligablePartB.iter().enumerate() {
+                let mut wereRulesApplied = false;
+                let mut concl2: Vec<(Sentence,f64)> = inference2ByRules(
+                    &selPrimarySentenceTuple.0, selPrimarySentenceTuple.1, &selPrimarySentenceTuple.2, &selPrimarySentenceTuple.3,
+                    &iSecondarySentence.0, iSecondarySentence.1, &iSecondarySentence.2, &iSecondarySentence.3, 
+                    &rulesB.read(),
+                    cfg__maxComplexity,
+                    &mut wereRulesApplied
+                );
+                resSecondaryIdxs.extend(concl2.iter().map(|_| iSecondaryIdx));
+                res.append(&mut concl2);
+            }
+            (res, resSecondaryIdxs)
+        };
+        // part B is derived on a own thread, except if derivation is synchronous to not depend on the scheduler
+        let mut resPartB:Option<(Vec<(Sentence,f64)>, Vec<usize>)> = None;
+        let handleB = if enThreads {Some(thread::spawn(inferPartB))} else {resPartB = Some(inferPartB()); None};
+
+        let selPrimaryTaskSentence:&Sentence = &msg.primary.read().sentence;
+        for iSecondaryTask in secondaryElligablePartA {
+            // do inference and add conclusions to array
+            if !Arc::ptr_eq(&msg.primary, &iSecondaryTask) { // arcs must not point to same task!
+                let mut wereRulesApplied = false;
+                let mut concl2: Vec<(Sentence,f64)> = inferenceByRules(selPrimaryTaskSentence, &iSecondaryTask.read().sentence, &rules.read(), cfg__maxComplexity, &mut wereRulesApplied);
+                conclSecondary.extend(concl2.iter().map(|_| Some(iSecondaryTask.read().sentence.clone())));
+                concl.append(&mut concl2);
+            }
+        }
+        
+        let (mut conclPartB, conclPartBSecondaryIdxs) = match handleB {
+            Some(handleB) => handleB.join().unwrap(),
+            None => resPartB.unwrap(),
+        };
+        conclSecondary.extend(conclPartBSecondaryIdxs.iter().map(|iIdx| Some(msg.secondary[*iIdx].read().sentence.clone())));
+        concl.append(&mut conclPartB);
 
+        if cfgEnInstrumentation {
+            println!("[instr] secondard inf took {}us", timeStart.elapsed().as_micros());
+        }
 
-                { // attention mechanism which selects the secondary task from concepts
-                    let keyTerm = msg.primary.read().sentence.term.clone();
-                    match sharedArc.read().mem.read().concepts.get(&keyTerm) {
-                        Some(concept) => {
-                            if false {println!("sample concept {}", convTermToStr(&concept.name))};
-        
-                            let processAllBeliefs:bool = true; // does the deriver process all beliefs?
-                            //let processSampledBelief:bool = false; // does it just sample one belief?
-        
-                            if processAllBeliefs { // code for processing all beliefs! is slower but should be more complete
-                                // MECHANISM<
-                                // process of all revelant beliefs of a concept as the first premise with a selected belief as the second premise
-                                // >
-                                // TODO< limit secondary beliefs to keep reasoning strictly under AIKR >
-                                for iBelief in &concept.payload.beliefsByExp {
-                                    let iBeliefGuard = iBelief.read();
-                                    // do inference and add conclusions to array
-                                    let mut wereRulesApplied = false;
-                                    let mut concl2: Vec<(Sentence,f64)> = inference(&msg.primary.read().sentence, &iBeliefGuard, &mut wereRulesApplied);
-                                    concl.append(&mut concl2);
-                                }
-                                for iBelief in &concept.payload.beliefsByUsage {
-                                    let iBeliefGuard = iBelief.read();
-                                    // do inference and add conclusions to array
-                                    let mut wereRulesApplied = false;
-                                    let mut concl2: Vec<(Sentence,f64)> = inference(&msg.primary.read().sentence, &iBeliefGuard, &mut wereRulesApplied);
-                                    concl.append(&mut concl2);
-                                }
-                            }
-                            /* commented because flag is false anyways
-                            if processSampledBelief { // code for sampling, is faster
-                                // MECHANISM<
-                                // sample belief from concept
-                                // This has the advantage that it's super cheap, but it can "hit" not fruitful premises
-                                // >
-                                let selVal:f64 = rng.gen_range(0.0,1.0);
-                                
-                                let allBeliefs = vec![];
-                                for iv in concept.beliefsByExp.iter() {
-                                    allBeliefs.push(iv);
-                                }
-                                for iv in concept.beliefsByUsage.iter() {
-                                    allBeliefs.push(iv);
-                                }
-                                
-                                let selBeliefIdx:usize = conceptSelByAvRandom(selVal, &allBeliefs);
-                                let selBelief:&Sentence = &allBeliefs[selBeliefIdx].read();
-                                
-                                // do inference and add conclusions to array
-                                let mut wereRulesApplied = false;
-                                let mut concl2: Vec<(Sentence,f64)> = inference(&msg.primary.read().sentence, selBelief, &mut wereRulesApplied);
-                                concl.append(&mut concl2);
-                            }
-                            */
-                        },
-                        None => {} // concept doesn't exist, ignore
+    }
+
+
+
+    { // attention mechanism which selects the secondary task from concepts
+        let primaryTerm:Arc<Term> = Arc::clone(&msg.primary.read().sentence.term);
+        let sharedGuard = sharedArc.read();
+        let memGuard = sharedGuard.mem.read(); // hold the guard instead of cloning the concept, a additional reference to the concept would prevent the write back with Arc::get_mut()
+        match NarMem::retConcept(&memGuard, &primaryTerm) {
+            Some(concept) => {
+                if false {println!("sample concept {}", convTermToStr(&concept.name))};
+
+                let processAllBeliefs:bool = true; // does the deriver process all beliefs?
+                //let processSampledBelief:bool = false; // does it just sample one belief?
+
+                if processAllBeliefs { // code for processing all beliefs! is slower but should be more complete
+                    // MECHANISM<
+                    // process of all revelant beliefs of a concept as the first premise with a selected belief as the second premise
+                    // >
+                    // TODO< limit secondary beliefs to keep reasoning strictly under AIKR >
+                    for iBelief in &concept.payload.beliefsByExp {
+                        let iBeliefGuard = iBelief.read();
+                        // do inference and add conclusions to array
+                        let mut wereRulesApplied = false;
+                        let mut concl2: Vec<(Sentence,f64)> = inferenceByRules(&msg.primary.read().sentence, &iBeliefGuard, &rules.read(), cfg__maxComplexity, &mut wereRulesApplied);
+                        conclSecondary.extend(concl2.iter().map(|_| Some((*iBeliefGuard).clone())));
+                        concl.append(&mut concl2);
+                    }
+                    for iBelief in &concept.payload.beliefsByUsage {
+                        let iBeliefGuard = iBelief.read();
+                        // do inference and add conclusions to array
+                        let mut wereRulesApplied = false;
+                        let mut concl2: Vec<(Sentence,f64)> = inferenceByRules(&msg.primary.read().sentence, &iBeliefGuard, &rules.read(), cfg__maxComplexity, &mut wereRulesApplied);
+                        conclSecondary.extend(concl2.iter().map(|_| Some((*iBeliefGuard).clone())));
+                        concl.append(&mut concl2);
                     }
                 }
+                /* commented because flag is false anyways
+                if processSampledBelief { // code for sampling, is faster
+                    // MECHANISM<
+                    // sample belief from concept
+                    // This has the advantage that it's super cheap, but it can "hit" not fruitful premises
+                    // >
+                    let selVal:f64 = rng.gen_range(0.0,1.0);
+                    
+                    let allBeliefs = vec![];
+                    for iv in concept.beliefsByExp.iter() {
+                        allBeliefs.push(iv);
+                    }
+                    for iv in concept.beliefsByUsage.iter() {
+                        allBeliefs.push(iv);
+                    }
+                    
+                    let selBeliefIdx:usize = conceptSelByAvRandom(selVal, &allBeliefs);
+                    let selBelief:&Sentence = &allBeliefs[selBeliefIdx].read();
+                    
+                    // do inference and add conclusions to array
+                    let mut wereRulesApplied = false;
+                    let mut concl2: Vec<(Sentence,f64)> = inferenceByRules(&msg.primary.read().sentence, selBelief, &rules.read(), cfg__maxComplexity, &mut wereRulesApplied);
+                    concl.append(&mut concl2);
+                }
+                */
+            },
+            None => {} // concept doesn't exist, ignore
+        }
+    }
 
 
 
 
-                ////////////
-                // write back
-                ////////////
+    ////////////
+    // write back
+    ////////////
 
 
-                // put conclusions back into memory!
-                {
-                    // MECHANISM< Q&A - answer questions >
-                    {
-                        for (iConcl, _iConclAttBias) in &concl {
-                            if iConcl.punct == EnumPunctation::JUGEMENT { // only jugements can answer questions!
-                                for mut iQTask in &mut *sharedArc.read().questionTasks.write() {
-                                    qaTryAnswer(&mut iQTask, &iConcl, &globalQaHandlers.read());
-                                }
-                            }
-                        }
-                    }
-                    
-                    for (iConcl, iConclAttBias) in &concl {
-                        // TODO< check if task exists already, don't add if it exists >
-                        let mut mulCredit:f64 = 1.0;
-                        mulCredit *= msg.primary.read().mulCredit; // inherit mul credit from parent, like in ONA
-                        //mulCredit *= 0.9; // inherit the priority from the parent, similar to ONA, leads to worse score when evaluating with Eval.sh
-
-                        mulCredit *= iConclAttBias; // multiply by "attention bias" to get a lower attention value, to avoid repeating the same derivations over and over
-                        memAddTask(Arc::clone(&sharedArc), iConcl, true, cfg__maxComplexity, cfg__nConceptBeliefs, mulCredit, currentTime);
+    // put conclusions back into memory!
+    {
+        // notify observers about derivations
+        if observers.read().len() > 0 {
+            let primarySentence:Sentence = msg.primary.read().sentence.clone();
+            for ((iConcl, _iConclAttBias), iSecondary) in concl.iter().zip(conclSecondary.iter()) {
+                let premises:Vec<Sentence> = match iSecondary {
+                    Some(secondary) => vec![primarySentence.clone(), secondary.clone()],
+                    None => vec![primarySentence.clone()]
+                };
+                notify(&observers, |o| o.derived(iConcl, &premises));
+            }
+        }
+
+        // MECHANISM< Q&A - answer questions >
+        {
+            let mut answers:Vec<QaAnswer> = vec![];
+            for (iConcl, _iConclAttBias) in &concl {
+                if iConcl.punct == EnumPunctation::JUGEMENT || iConcl.punct == EnumPunctation::GOAL { // only jugements and goals can answer questions and quests!
+                    for mut iQTask in &mut *sharedArc.read().questionTasks.write() {
+                        qaTryAnswer(&mut iQTask, &iConcl, cfg__qaNMaxBindings, &mut answers);
                     }
                 }
             }
-        }));
+            qaNotifyAnswers(answers, &observers); // lock of the question tasks must be released, because handlers and observers may call back into the NAR
+        }
+        
+        for (iConcl, iConclAttBias) in &concl {
+            // TODO< check if task exists already, don't add if it exists >
+            let mut mulCredit:f64 = 1.0;
+            mulCredit *= msg.primary.read().mulCredit; // inherit mul credit from parent, like in ONA
+            //mulCredit *= 0.9; // inherit the priority from the parent, similar to ONA, leads to worse score when evaluating with Eval.sh
+
+            mulCredit *= iConclAttBias; // multiply by "attention bias" to get a lower attention value, to avoid repeating the same derivations over and over
+            memAddTask(Arc::clone(&sharedArc), iConcl, true, cfg__maxComplexity, cfg__nConceptBeliefs, mulCredit, currentTime);
+        }
     }
-    
-
-    resArc
 }
 
 /// helper to select random task by credit
@@ -1398,19 +2522,20 @@ pub fn tasksSelHighestCreditIdx(arr: &Vec<Rc<RefCell<Task>>>, cycleCounter:i64)
 /// stores missing entries of mem.judgementTasksByTerm by subterm of term
 ///
 /// IMPL< is actually a helper function for memAddTask, still exposed as public for code reuse >
-pub fn populateTaskByTermLookup(judgementTasksByTerm:Arc<RwLock< HashMap<Term, Vec<Arc<RwLock<Task>>>> >>, term:&Term, task:&Arc<RwLock<Task>>) {
-    let mut judgementTasksByTermGuard = judgementTasksByTerm.write();
-    
-    for iSubTerm in &retSubterms(&term) {
-        if judgementTasksByTermGuard.contains_key(iSubTerm) {
-            let mut v = judgementTasksByTermGuard.get(iSubTerm).unwrap().clone();
-            v.push(Arc::clone(&task));
-            judgementTasksByTermGuard.insert(iSubTerm.clone(), v);
-        }
-        else {
-            judgementTasksByTermGuard.insert(iSubTerm.clone(), vec![Arc::clone(&task)]);
+pub fn populateTaskByTermLookup(judgementTasksByTerm:Arc<RwLock< HashMap<TermId, Vec<Arc<RwLock<Task>>>> >>, term:&Term, task:&Arc<RwLock<Task>>) {
+    populateTaskByTermLookup2(&mut judgementTasksByTerm.write(), &HashMap::new(), term, task);
+}
+
+/// stores missing entries of judgementTasksByTerm by subterm of term
+///
+/// /param oldTasksByTerm the keys of it are reused for new entries, so a rebuild of the lookup doesn't clone the terms again
+pub fn populateTaskByTermLookup2(judgementTasksByTerm:&mut HashMap<TermId, Vec<Arc<RwLock<Task>>>>, oldTasksByTerm:&HashMap<TermId, Vec<Arc<RwLock<Task>>>>, term:&Term, task:&Arc<RwLock<Task>>) {
+    term.fold((), &mut |(), iSubTerm| {
+        match retByTermMut(judgementTasksByTerm, iSubTerm) {
+            Some(tasks) => {tasks.push(Arc::clone(&task));},
+            None => {judgementTasksByTerm.insert(internIn(oldTasksByTerm, iSubTerm), vec![Arc::clone(&task)]);}
         }
-    }
+    });
 }
 
 /// tries to revise the belief if possible
@@ -1430,7 +2555,7 @@ pub fn memReviseBelief(mem:Arc<RwLock<NarMem::Mem>>, sentence:&Sentence) -> Opti
         EnumPunctation::JUGEMENT => {
             
             for iTerm in retSubterms(&*sentence.term) { // enumerate all terms, we need to do this to add the sentence to all relevant names
-                match mem.write().concepts.get_mut(&iTerm.clone()) {
+                match NarMem::retConceptMut(&mut mem.write(), &iTerm) {
                     Some(arcConcept) => {
                         match Arc::get_mut(arcConcept) {
                             Some(concept) => {
@@ -1441,7 +2566,7 @@ pub fn memReviseBelief(mem:Arc<RwLock<NarMem::Mem>>, sentence:&Sentence) -> Opti
                                     
                                     for iBeliefIdx in 0..concept.payload.beliefsByExp.len() {
                                         let iBelief = &concept.payload.beliefsByExp[iBeliefIdx].read();
-                                        if checkEqTerm(&iBelief.term, &sentence.term) && !NarStamp::checkOverlap(&iBelief.stamp, &sentence.stamp) {
+                                        if alphaEq(&iBelief.term, &sentence.term) && !NarStamp::checkOverlap(&iBelief.stamp, &sentence.stamp) {
                                             let stamp = NarStamp::merge(&iBelief.stamp, &sentence.stamp);
                                             let tvA:Tv = retTv(&iBelief).unwrap();
                                             let tvB:Tv = retTv(&sentence).unwrap();
@@ -1480,7 +2605,7 @@ pub fn memReviseBelief(mem:Arc<RwLock<NarMem::Mem>>, sentence:&Sentence) -> Opti
                                     
                                     for iBeliefIdx in 0..concept.payload.beliefsByUsage.len() {
                                         let iBelief = &concept.payload.beliefsByUsage[iBeliefIdx].read();
-                                        if checkEqTerm(&iBelief.term, &sentence.term) && !NarStamp::checkOverlap(&iBelief.stamp, &sentence.stamp) {
+                                        if alphaEq(&iBelief.term, &sentence.term) && !NarStamp::checkOverlap(&iBelief.stamp, &sentence.stamp) {
                                             let stamp = NarStamp::merge(&iBelief.stamp, &sentence.stamp);
                                             let tvA:Tv = retTv(&iBelief).unwrap();
                                             let tvB:Tv = retTv(&sentence).unwrap();
@@ -1529,6 +2654,13 @@ pub fn memReviseBelief(mem:Arc<RwLock<NarMem::Mem>>, sentence:&Sentence) -> Opti
 
 /// /param calcCredit compute the credit?
 pub fn memAddTask(shared:Arc<RwLock<DeclarativeShared>>, sentence:&Sentence, calcCredit:bool, cfg__maxComplexity: i64, cfg__nConceptBeliefs:usize, mulCredit:f64, currentTime: i64) {
+    memAddTask2(shared, sentence, calcCredit, cfg__maxComplexity, cfg__nConceptBeliefs, 1.0, mulCredit, 0.0, 1.0, currentTime);
+}
+
+/// add task with explicit attention values
+/// /param credit is the initial credit of judgement and goal tasks
+/// /param prio is the priority of question tasks
+pub fn memAddTask2(shared:Arc<RwLock<DeclarativeShared>>, sentence:&Sentence, calcCredit:bool, cfg__maxComplexity: i64, cfg__nConceptBeliefs:usize, credit:f64, mulCredit:f64, quality:f64, prio:f64, currentTime: i64) {
     if calcComplexity(&sentence.term) as i64 > cfg__maxComplexity { // don't add to complex terms because of AIKR god
         return;
     }
@@ -1551,9 +2683,16 @@ pub fn memAddTask(shared:Arc<RwLock<DeclarativeShared>>, sentence:&Sentence, cal
         NarMem::storeInConcepts(&mut shared.read().mem.write(), sentence, cfg__nConceptBeliefs, currentTime); // store sentence in memory, adressed by concepts
     }
 
+    if sentence.punct == EnumPunctation::GOAL { // eternal goals are stored as desires
+        let revisedDesire:Option<Sentence> = NarMem::storeDesire(&mut shared.read().mem.write(), sentence, cfg__nConceptBeliefs);
+        if let Some(revisedDesire) = revisedDesire {
+            toAddToTasks.push(revisedDesire);
+        }
+    }
+
     for iToAddToTasks in &toAddToTasks {
         match iToAddToTasks.punct {
-            EnumPunctation::JUGEMENT => {
+            EnumPunctation::JUGEMENT | EnumPunctation::GOAL => {
                 let task = {
                     let sharedGuard = shared.read();
                     if true { // check if we should check if it already exist in the tasks
@@ -1568,9 +2707,10 @@ pub fn memAddTask(shared:Arc<RwLock<DeclarativeShared>>, sentence:&Sentence, cal
                     let taskId:i64 = sharedGuard.taskIdCounter.fetch_add(1, Ordering::SeqCst); // TODO< is this ordering ok? >
                     let mut task = Task {
                         sentence:shallowCopySentence(&iToAddToTasks),
-                        credit:1.0,
+                        credit:credit,
                         qaCredit:0.0, // no question was posed!
                         mulCredit:mulCredit,
+                        quality:quality,
                         id:taskId,
                         derivTime:sharedGuard.cycleCounter.load(Ordering::Relaxed)
                     };
@@ -1593,65 +2733,126 @@ pub fn memAddTask(shared:Arc<RwLock<DeclarativeShared>>, sentence:&Sentence, cal
                 let sharedGuard = shared.read();
                 populateTaskByTermLookup(Arc::clone(&sharedGuard.judgementTasksByTerm), &iToAddToTasks.term, &taskArc);
             },
-            EnumPunctation::QUESTION => {
+            EnumPunctation::QUESTION | EnumPunctation::QUEST => {
                 println!("TODO - check if we should check if it already exist in the tasks");
                 
-                let sharedGuard = shared.read();
-                sharedGuard.questionTasks.write().push(Box::new(Task2 {
-                    sentence:shallowCopySentence(&iToAddToTasks.clone()),
-                    handler:None,
-                    bestAnswerExp:0.0, // because has no answer yet
-                    prio:1.0,
-                }));
-            },
-            EnumPunctation::GOAL => {
-                println!("ERROR: goal is not implemented!");
+                memAddQuestionTask(&shared, &iToAddToTasks, prio, None);
             },
         }
     }
 }
 
+/// add question task
+/// /param handler is called when a better answer to this question is found
+pub fn memAddQuestionTask(shared:&Arc<RwLock<DeclarativeShared>>, sentence:&Sentence, prio:f64, handler:Option<Arc<RwLock<dyn QHandler>>>) {
+    let sharedGuard = shared.read();
+    sharedGuard.questionTasks.write().push(Box::new(Task2 {
+        sentence:shallowCopySentence(sentence),
+        handler:handler,
+        bestAnswerExp:0.0, // because has no answer yet
+        bindings:vec![],
+        prio:prio,
+    }));
+}
+
 /// helper for attention
 pub fn divCreditByComplexity(task:&mut Task) {
     task.credit /= calcComplexity(&task.sentence.term) as f64;
     task.qaCredit /= calcComplexity(&task.sentence.term) as f64;
 }
 
+/// better answer to a question which was found, handler and observers are notified with qaNotifyAnswers()
+pub struct QaAnswer {
+    pub question:Sentence,
+    pub answer:Sentence,
+    /// handler of the question task
+    pub handler:Option<Arc<RwLock<dyn QHandler>>>,
+}
+
 /// tries to find a better answer for a question task
+///
+/// questions are answered by judgements, quests are answered by goals
+/// also remembers the best answer for each binding of the query variables
 /// # Arguments
 /// * `qTask` - the question task to find a answer to
 /// * `concl` - candidate answer to get evaluated
-/// * `globalQaHandlers` - 
-pub fn qaTryAnswer(qTask: &mut Task2, concl: &Sentence, globalQaHandlers: &Vec<Arc<RwLock< dyn QHandler>>>) {
-    if concl.punct != EnumPunctation::JUGEMENT { // only jugements can answer questions!
+/// * `cfg__nMaxBindings` - maximal number of bindings of query variables which are remembered, to keep it under AIKR
+/// * `answers` - better answer gets appended, the caller has to notify with qaNotifyAnswers() after releasing the lock of the question tasks
+pub fn qaTryAnswer(qTask: &mut Task2, concl: &Sentence, cfg__nMaxBindings:usize, answers: &mut Vec<QaAnswer>) {
+    let answerPunct = if qTask.sentence.punct == EnumPunctation::QUEST {EnumPunctation::GOAL} else {EnumPunctation::JUGEMENT};
+    if concl.punct != answerPunct { // only jugements can answer questions and only goals can answer quests!
         return;
     }
 
-    if calcExp(&retTv(concl).unwrap()) > qTask.bestAnswerExp { // is the answer potentially better?
-        let unifyRes: Option<Vec<Asgnment>> = unify(&qTask.sentence.term, &concl.term); // try unify question with answer
-        if unifyRes.is_some() { // was answer found?
-            let _unifiedRes: Term = unifySubst(&qTask.sentence.term, &unifyRes.unwrap());
-            
-            if qTask.handler.is_some() {
-                // call Q&A handler for task
-                let handler1 = qTask.handler.as_ref().unwrap();
-                let mut handlerGuard = handler1.write();
-                handlerGuard.answer(&qTask.sentence.term, &concl); // call callback because we found a answer
-            }
+    let exp:f64 = calcExp(&retTv(concl).unwrap());
+    let isBetter:bool = exp > qTask.bestAnswerExp; // is the answer potentially better?
+    let hasQVar:bool = retSubterms(&qTask.sentence.term).iter().any(|iTerm| match iTerm {Term::QVar(_) => true, _ => false});
+    if !isBetter && !hasQVar { // answer can't be better for any binding
+        return;
+    }
 
-            // call global Q&A handlers
-            for iHandler in globalQaHandlers {
-                let mut handlerGuard = iHandler.write();
-                handlerGuard.answer(&qTask.sentence.term, &concl);
-            }
+    let unifyRes: Option<Vec<Asgnment>> = unify(&qTask.sentence.term, &concl.term); // try unify question with answer
+    if unifyRes.is_some() { // was answer found?
+        let asgnments:Vec<Asgnment> = unifyRes.unwrap();
+        if hasQVar {
+            qaUpdateBindings(qTask, &asgnments, concl, exp, cfg__nMaxBindings);
+        }
+
+        if !isBetter {
+            return;
+        }
+
+        let _unifiedRes: Term = unifySubst(&qTask.sentence.term, &asgnments);
+
+        answers.push(QaAnswer{question:shallowCopySentence(&qTask.sentence), answer:shallowCopySentence(concl), handler:qTask.handler.as_ref().map(|iv| Arc::clone(iv))});
+
+        qTask.bestAnswerExp = exp; // update exp of best found answer
+    }
+}
+
+/// calls the handlers of the question tasks and notifies the observers about the answers
+///
+/// must be called without holding the lock of the question tasks
+pub fn qaNotifyAnswers(answers: Vec<QaAnswer>, observers: &Observers) {
+    for iAnswer in &answers {
+        if let Some(handler) = &iAnswer.handler {
+            // call Q&A handler for task
+            handler.write().answer(&iAnswer.question.term, &iAnswer.answer); // call callback because we found a answer
+        }
+
+        // notify observers
+        notify(observers, |o| o.answered(&iAnswer.question, &iAnswer.answer));
 
-            qTask.bestAnswerExp = calcExp(&retTv(&concl).unwrap()); // update exp of best found answer
+        // print question and answer
+        let msg = "TRACE answer: ".to_owned() + &convSentenceTermPunctToStr(&iAnswer.question, true) + " " + &convSentenceTermPunctToStr(&iAnswer.answer, true);
+        println!("{}", msg);
+    }
+}
 
-            // print question and answer
-            let msg = "TRACE answer: ".to_owned() + &convSentenceTermPunctToStr(&qTask.sentence, true) + " " + &convSentenceTermPunctToStr(&concl, true);
-            println!("{}", msg);
+/// remembers the answer if it is the best answer for the binding of the query variables
+fn qaUpdateBindings(qTask: &mut Task2, asgnments: &Vec<Asgnment>, concl: &Sentence, exp:f64, cfg__nMaxBindings:usize) {
+    // extract binding of query variables
+    let mut binding:Vec<Asgnment> = vec![];
+    for iAsgnment in asgnments {
+        let isQVar = match &iAsgnment.var {Term::QVar(_) => true, _ => false};
+        if isQVar && !binding.iter().any(|iv| checkEqTerm(&iv.var, &iAsgnment.var)) {
+            binding.push(iAsgnment.clone());
         }
     }
+    binding.sort_by(|a, b| convTermToStr(&a.var).cmp(&convTermToStr(&b.var)));
+
+    let isSameBinding = |other:&Vec<Asgnment>| -> bool {
+        other.len() == binding.len() && other.iter().zip(binding.iter()).all(|(a, b)| checkEqTerm(&a.var, &b.var) && checkEqTerm(&a.val, &b.val))
+    };
+    match qTask.bindings.iter().position(|iv| isSameBinding(&iv.binding)) {
+        Some(idx) if qTask.bindings[idx].exp >= exp => {return;}, // already has a better answer
+        Some(idx) => {qTask.bindings.remove(idx);},
+        None => {},
+    }
+
+    let idx = qTask.bindings.iter().position(|iv| iv.exp < exp).unwrap_or(qTask.bindings.len()); // keep it ordered by exp
+    qTask.bindings.insert(idx, QaBinding{binding:binding, answer:concl.clone(), exp:exp});
+    qTask.bindings.truncate(cfg__nMaxBindings);
 }
 
 /// performs one reasoning cycle
@@ -1659,6 +2860,8 @@ pub fn qaTryAnswer(qTask: &mut Task2, concl: &Sentence, globalQaHandlers: &Vec<A
 /// * `mem` - memory
 pub fn reasonCycle(mem:Arc<RwLock<Mem2>>, currentTime: &AtomicI64) {
     let cfgEnInstrumentation:bool = false; // enable instrumentation
+    let cfg:NarConfig = mem.read().cfg.clone();
+    let nMaxSecondary:usize = cfg.nMaxSecondary; // maximal number of secondary premises
 
     mem.read().shared.read().cycleCounter.fetch_add(1, Ordering::SeqCst); // TODO< is this ordering ok? >
     
@@ -1676,7 +2879,7 @@ pub fn reasonCycle(mem:Arc<RwLock<Mem2>>, currentTime: &AtomicI64) {
                 {
                     for iSubTerm in &retSubterms(&iTask.sentence.term) { // iterate over all terms
                         let optTasksBySubtermGuard = sharedGuard.judgementTasksByTerm.read();
-                        let optTasksBySubterm = optTasksBySubtermGuard.get(&iSubTerm);
+                        let optTasksBySubterm = retByTerm(&optTasksBySubtermGuard, &iSubTerm);
                         match optTasksBySubterm {
                             Some(tasksBySubterms) => {
                                 for iIdx in 0..tasksBySubterms.len() {
@@ -1708,6 +2911,7 @@ pub fn reasonCycle(mem:Arc<RwLock<Mem2>>, currentTime: &AtomicI64) {
     }
 
     // sample question to answer
+    let mut answers:Vec<QaAnswer> = vec![]; // observers are notified after the lock of the question tasks was released
     {
         let memGuard = mem.read();
         let sharedGuard = memGuard.shared.read(); // get read guard because we need only read here
@@ -1745,14 +2949,31 @@ pub fn reasonCycle(mem:Arc<RwLock<Mem2>>, currentTime: &AtomicI64) {
                 match beliefsOpt {
                     Some(beliefs) => {
                         for iBelief in beliefs {
-                            qaTryAnswer(&mut selTask, &iBelief.read(), &memGuard.globalQaHandlers.read());
+                            let belief = iBelief.read();
+                            if isTemporal && checkHasVar(&belief.term) { // =/> can answer the question after the variables of the predicate were bound
+                                if let Term::Stmt(Copula::PREDIMPL, _, qPred) = &*(*selTask).sentence.term {
+                                    if let Some(unifiedTerm) = NarInfProcedural::unifyPredImplByPred(&belief.term, qPred) {
+                                        let mut unifiedBelief:Sentence = shallowCopySentence(&belief);
+                                        unifiedBelief.term = Arc::new(unifiedTerm);
+                                        qaTryAnswer(&mut selTask, &unifiedBelief, cfg.qaNMaxBindings, &mut answers);
+                                    }
+                                }
+                            }
+                            qaTryAnswer(&mut selTask, &belief, cfg.qaNMaxBindings, &mut answers);
                         }
                     },
                     None => {}
                 }
+
+                if (*selTask).sentence.punct == EnumPunctation::QUEST { // quests are answered by the desires
+                    for iDesire in &NarMem::ret_desires_of_concept(&accessedMemGuard, &iSubTerm) {
+                        qaTryAnswer(&mut selTask, &iDesire.read(), cfg.qaNMaxBindings, &mut answers);
+                    }
+                }
             };
         }
     }
+    qaNotifyAnswers(answers, &mem.read().observers);
     
     let mut msg: Option<DeriverWorkMessage> = None; // message which we have to send to worker for derivation
     {
@@ -1800,8 +3021,8 @@ pub fn reasonCycle(mem:Arc<RwLock<Mem2>>, currentTime: &AtomicI64) {
                     if false{println!("DBG enter enum secondary elligable")};
                     if false{println!("DBG   term  {}", &convTermToStr(&selPrimaryTaskTerm))};
                     for iSubTerm in &retUniqueSubterms(&selPrimaryTaskTerm) {
-                        if sharedGuard.judgementTasksByTerm.read().contains_key(iSubTerm) {
-                            let itJudgementTasksByTerm:Vec<Arc<RwLock<Task>>> = sharedGuard.judgementTasksByTerm.read().get(iSubTerm).unwrap().to_vec();
+                        let itJudgementTasksByTermOpt:Option<Vec<Arc<RwLock<Task>>>> = retByTerm(&sharedGuard.judgementTasksByTerm.read(), iSubTerm).map(|iv| iv.to_vec());
+                        if let Some(itJudgementTasksByTerm) = itJudgementTasksByTermOpt {
                             
                             let mut icnt = 0; // counter to limit it
                             for it in &itJudgementTasksByTerm {// append to elligable, because it contains the term
@@ -1857,7 +3078,7 @@ pub fn reasonCycle(mem:Arc<RwLock<Mem2>>, currentTime: &AtomicI64) {
                         }).collect();
 
                         arr.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
-                        arr = arr[0..arr.len().min(300)].to_vec(); // keep under AIKR
+                        arr = arr[0..arr.len().min(nMaxSecondary)].to_vec(); // keep under AIKR
                         secondaryElligable = arr.iter().map(|(_,v)| Arc::clone(v)).collect();
                     }
         
@@ -1898,14 +3119,19 @@ pub fn reasonCycle(mem:Arc<RwLock<Mem2>>, currentTime: &AtomicI64) {
                 usageUpdate(&mut iBeliefTask.read().sentence.usage.write(), currentTime2);
             }
             
-            // submit message to worker
             let memGuard = mem.read();
-            memGuard.deriverWorkersTx[0].send(unwrappedMsg).unwrap();
+            if memGuard.deriverWorkersTx.len() > 0 {
+                // submit message to worker
+                memGuard.deriverWorkersTx[0].send(unwrappedMsg).unwrap();
+            }
+            else { // derivation is synchronous
+                deriveAndWriteBack(&memGuard.shared, &memGuard.observers, unwrappedMsg, &mut memGuard.rng.write(), cfg.maxComplexity, cfg.nConceptBeliefs, cfg.qaNMaxBindings, false);
+            }
         }
     }
 
-    let intervalCheckTasks = 111; // cycle counter to check for AIKR of tasks - should be prime
-    let maxJudgementTasks = 30; // maximal number of judgement tasks
+    let intervalCheckTasks = cfg.intervalCheckTasks; // cycle counter to check for AIKR of tasks - should be prime
+    let maxJudgementTasks = cfg.maxJudgementTasks; // maximal number of judgement tasks
 
     // keep working tasks of judgements under AIKR
     {
@@ -1925,7 +3151,7 @@ pub fn reasonCycle(mem:Arc<RwLock<Mem2>>, currentTime: &AtomicI64) {
             sharedGuard.judgementTasks = sharedGuard.judgementTasks[0..maxJudgementTasks.min(sharedGuard.judgementTasks.len())].to_vec(); // limit to keep under AIKR
             
             // minimal priority which a work item can have
-            let cfg__minWorkItemPriority = 0.001;
+            let cfg__minWorkItemPriority = cfg.minWorkItemPriority;
             sharedGuard.judgementTasks = sharedGuard.judgementTasks.iter().filter(|iTask| taskCalcCredit(&iTask.read(), memCycleCounter) > cfg__minWorkItemPriority).cloned().collect();
 
             if false {println!("[d] EXIT: keep working tasks under AIKR")};
@@ -1938,34 +3164,38 @@ pub fn reasonCycle(mem:Arc<RwLock<Mem2>>, currentTime: &AtomicI64) {
         if memGuard.shared.read().cycleCounter.load(Ordering::Relaxed) % intervalCheckTasks == 0 {
             let mut sharedGuard = memGuard.shared.write();
             
-            sharedGuard.judgementTasksByTerm = Arc::new(RwLock::new(HashMap::new())); // flush, because we will repopulate
+            let oldTasksByTerm = std::mem::replace(&mut sharedGuard.judgementTasksByTerm, Arc::new(RwLock::new(HashMap::new()))); // flush, because we will repopulate
 
             // repopulate judgementTasksByTerm
             // IMPL< we had to split it because mem was accessed twice! >
             let mut termAndTask = vec![];
             for iJudgementTask in &sharedGuard.judgementTasks {
-                let termRc:&Arc<Term> = &iJudgementTask.read().sentence.term;
-                let term:Term = (**termRc).clone();
+                let term:Arc<Term> = Arc::clone(&iJudgementTask.read().sentence.term); // share term instead of cloning it
 
                 termAndTask.push((term, Arc::clone(iJudgementTask)));
             }
 
+            let oldTasksByTermGuard = oldTasksByTerm.read();
+            let mut tasksByTermGuard = sharedGuard.judgementTasksByTerm.write();
             for (term, task) in &termAndTask { // iterate over prepared tuples
-                // populate hashmap lookup
-                populateTaskByTermLookup(Arc::clone(&sharedGuard.judgementTasksByTerm), &term, &task);
+                // populate hashmap lookup, the interned terms of the old lookup are reused
+                populateTaskByTermLookup2(&mut tasksByTermGuard, &oldTasksByTermGuard, &term, &task);
             }
         }
     }
 
 
-    let intervalCheckConcepts = 173;
-    let nConcepts = 3000; // number of concepts
+    let intervalCheckConcepts = cfg.intervalCheckConcepts;
+    let nConcepts = cfg.nConcepts; // number of concepts
 
     { // limit number of concepts
         let memGuard = mem.read();
         let sharedGuard = memGuard.shared.read(); // get read guard because we need only read here
         if sharedGuard.cycleCounter.load(Ordering::Relaxed) % intervalCheckConcepts == 0 {
-            NarMem::limitMemory(&mut sharedGuard.mem.write(), nConcepts);
+            let forgotten:Vec<Term> = NarMem::limitMemory(&mut sharedGuard.mem.write(), nConcepts);
+            for iName in &forgotten {
+                notify(&memGuard.observers, |o| o.conceptForgotten(iName));
+            }
         }
     }
 }
@@ -2013,3 +3243,16 @@ pub fn debugCreditsOfTasks(mem: &Mem2) -> Vec<String> {
 pub trait QHandler: Sync + Send {
     fn answer(&mut self, question:&Term, answer:&Sentence);
 }
+
+/// observer which calls the deprecated globalQaHandlers
+struct QHandlersObserver {
+    handlers: Arc<RwLock<  Vec<Arc<RwLock< dyn QHandler>>>  >>,
+}
+
+impl NarObserver for QHandlersObserver {
+    fn answered(&mut self, question:&Sentence, answer:&Sentence) {
+        for iHandler in self.handlers.read().iter() {
+            iHandler.write().answer(&question.term, answer);
+        }
+    }
+}
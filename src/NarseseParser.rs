@@ -1,7 +1,10 @@
 
+use std::fmt;
+
 use nom::{
   IResult,
   bytes::complete::{tag, take_while_m_n},
+  character::complete::{digit1, space0},
   combinator::map_res,
   //Err,
 //  sequence::tuple,
@@ -13,88 +16,394 @@ use nom::{
 //use nom::many_m_n;
 
 use crate::Term::*;
-use crate::NarSentence::EnumPunctation;
+use crate::NarSentence::{EnumPunctation, EnumTense, Budget};
 use crate::Tv::Tv;
 use crate::TermApi::*;
+use crate::TermUtils::encodeOp;
+
+/// kind of the error which happened while parsing narsese
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum EnumNarseseErrorKind {
+  EMPTY, // nothing to parse
+  BAD_TV, // malformed {f c}
+  BAD_PUNCTUATION, // missing or unknown punctuation
+  BAD_TENSE, // malformed tense
+  BAD_BUDGET, // malformed $p;d;q$
+  BAD_EXPDT, // malformed +EXPDTn
+  UNBALANCED_BRACKETS, // bracket without matching bracket
+  UNKNOWN_COPULA, // statement with a copula which isn't known
+  TRAILING_INPUT, // term was parsed but there is input left over
+  INVALID_TERM, // term couldn't get parsed for another reason
+}
+
+/// error which describes why and where narsese couldn't get parsed
+// PUBLICAPI
+#[derive(Clone, Debug, PartialEq)]
+pub struct NarseseError {
+  pub kind: EnumNarseseErrorKind,
+  /// byte offset into the parsed narsese
+  pub offset: usize,
+  /// line of the error, starts at 1
+  pub line: usize,
+  /// column of the error in characters, starts at 1
+  pub column: usize,
+  /// tokens which were expected at the position of the error
+  pub expected: Vec<String>,
+  /// text which was found at the position of the error
+  pub found: String,
+}
+
+impl fmt::Display for NarseseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let kindAsStr = match self.kind {
+      EnumNarseseErrorKind::EMPTY => "empty input",
+      EnumNarseseErrorKind::BAD_TV => "bad truth value",
+      EnumNarseseErrorKind::BAD_PUNCTUATION => "bad punctuation",
+      EnumNarseseErrorKind::BAD_TENSE => "bad tense",
+      EnumNarseseErrorKind::BAD_BUDGET => "bad budget",
+      EnumNarseseErrorKind::BAD_EXPDT => "bad exponential time delta",
+      EnumNarseseErrorKind::UNBALANCED_BRACKETS => "unbalanced brackets",
+      EnumNarseseErrorKind::UNKNOWN_COPULA => "unknown copula",
+      EnumNarseseErrorKind::TRAILING_INPUT => "trailing input",
+      EnumNarseseErrorKind::INVALID_TERM => "invalid term",
+    };
+    write!(f, "{} at line {}, column {}", kindAsStr, self.line, self.column)?;
+    if self.expected.len() > 0 {
+      write!(f, ", expected {}", self.expected.iter().map(|v| format!("`{}`", v)).collect::<Vec<String>>().join(" or "))?;
+    }
+    if self.found.len() > 0 {
+      write!(f, ", found `{}`", self.found)?;
+    }
+    Ok(())
+  }
+}
+
+impl std::error::Error for NarseseError {}
+
+/// helper to build error, computes line and column from the offset
+fn mkErr(narsese:&str, kind:EnumNarseseErrorKind, offset:usize, expected:&[&str]) -> NarseseError {
+  let offset = offset.min(narsese.len());
+  let before = &narsese[..offset];
+  let line = before.matches('\n').count() + 1;
+  let column = match before.rfind('\n') {
+    Some(idx) => before[idx+1..].chars().count() + 1,
+    None => before.chars().count() + 1,
+  };
+  // found text is the token at the offset
+  let found:String = narsese[offset..].trim_start().chars().take_while(|c| !c.is_whitespace()).take(16).collect();
+
+  NarseseError{kind:kind, offset:offset, line:line, column:column, expected:expected.iter().map(|v| v.to_string()).collect(), found:found}
+}
+
+/// all copulas
+const COPULA_TOKENS: [&str; 8] = ["-->", "<->", "==>", "=/>", "<=>", "{--", "--]", "{-]"];
+
+/// checks if brackets are balanced
+///
+/// `narsese` is the complete narsese, `termStart` and `termEnd` are the byte indices of the term
+fn checkBrackets(narsese:&str, termStart:usize, termEnd:usize) -> Result<(), NarseseError> {
+  let term = &narsese[..termEnd];
+  let mut stack:Vec<(char, usize)> = vec![]; // opening brackets with offset
+  let mut idx = termStart;
+  while idx < term.len() {
+    if idx > termStart && term[..idx].ends_with(' ') && !term[idx..].starts_with(' ') && looksLikeCopula(&term[idx..]) {
+      let copLen = term[idx..].find(' ').unwrap_or(term.len()-idx);
+      idx += copLen; // skip copula because it contains brackets
+      continue;
+    }
+
+    let c = term[idx..].chars().next().unwrap();
+    match c {
+      '(' | '[' | '{' | '<' => {
+        stack.push((c, idx));
+      },
+      ')' | ']' | '}' | '>' => {
+        match stack.pop() {
+          Some((open, _)) if retClosingBracket(open) == c => {},
+          Some((open, _)) => {
+            let expected = retClosingBracket(open).to_string();
+            return Err(mkErr(narsese, EnumNarseseErrorKind::UNBALANCED_BRACKETS, idx, &[&expected]));
+          },
+          None => {
+            return Err(mkErr(narsese, EnumNarseseErrorKind::UNBALANCED_BRACKETS, idx, &[]));
+          }
+        }
+      },
+      _ => {}
+    }
+    idx += c.len_utf8();
+  }
+
+  match stack.pop() {
+    Some((open, _)) => {
+      let expected = retClosingBracket(open).to_string();
+      Err(mkErr(narsese, EnumNarseseErrorKind::UNBALANCED_BRACKETS, termEnd, &[&expected]))
+    },
+    None => Ok(())
+  }
+}
+
+fn retClosingBracket(open:char) -> char {
+  match open {
+    '(' => ')',
+    '[' => ']',
+    '{' => '}',
+    _ => '>',
+  }
+}
 
 // finds out if narsese has tv and returns TV if TV exists
 // cuts away narsese of TV if TV is detected
-pub fn parseNarseseRetTv(narsese:&mut String, tv:&mut Tv) {
+pub fn parseNarseseRetTv(narsese:&mut String, tv:&mut Tv) -> Result<(), NarseseError> {
   *tv = Tv{f:1.0,c:0.9}; // set TV to default
-  
-  if narsese.len() > 0 && narsese.chars().nth(narsese.len()-1).unwrap() == '}' {
-    for revIdx in 1..narsese.len() { // scan for '{'
-      let idx = narsese.len() - revIdx; // compute index from back
-      
-      if narsese.chars().nth(idx).unwrap() == '{' {
-        let tvStr = &narsese[idx+1..narsese.len()-1];
-        let splitted:Vec<&str> = tvStr.split(" ").collect();
-        
-        if splitted.len() == 2 { // must have two values
-          // TODO< handle error better >
-          let f = splitted[0].parse::<f64>().unwrap();
-          let c = splitted[1].parse::<f64>().unwrap();
-          *narsese = narsese[..idx].to_string(); // cut away
-          *tv = Tv{f:f,c:c};
-          return;
-        }
-        return;
+
+  if !narsese.ends_with('}') {
+    return Ok(()); // has no TV
+  }
+
+  let idx = match narsese.rfind('{') { // scan for '{'
+    Some(idx) => idx,
+    None => {return Ok(());} // brackets are checked later
+  };
+  // it is only a TV if punctuation is in front of it, else it is a set
+  let before = narsese[..idx].trim_end();
+  let isAfterExpDt:bool = before.rsplit(' ').next().map_or(false, |v| v.starts_with("+EXPDT"));
+  if !(before.ends_with('.') || before.ends_with('!') || before.ends_with('?') || before.ends_with(':') || isAfterExpDt) { // punctuation, tense or expDt
+    return Ok(());
+  }
+
+  let tvStr = &narsese[idx+1..narsese.len()-1];
+  let splitted:Vec<&str> = tvStr.split_whitespace().collect();
+  if splitted.len() != 2 { // must have two values
+    return Err(mkErr(narsese, EnumNarseseErrorKind::BAD_TV, idx, &["{frequency confidence}"]));
+  }
+
+  // helper to parse one component of the TV
+  fn parseComponent(narsese:&str, component:&str, name:&str, maxExclusive:bool) -> Result<f64, NarseseError> {
+    let offset = component.as_ptr() as usize - narsese.as_ptr() as usize;
+    match component.parse::<f64>() {
+      Ok(v) if v >= 0.0 && (v < 1.0 || (!maxExclusive && v <= 1.0)) => Ok(v),
+      _ => Err(mkErr(narsese, EnumNarseseErrorKind::BAD_TV, offset, &[name])),
+    }
+  }
+  let f = parseComponent(narsese, splitted[0], "frequency in [0, 1]", false)?;
+  let c = parseComponent(narsese, splitted[1], "confidence in [0, 1)", true)?;
+
+  *narsese = narsese[..idx].to_string(); // cut away
+  *tv = Tv{f:f,c:c};
+  Ok(())
+}
+
+/// removes spaces after opening brackets, before closing brackets and around connectors like '*' and ','
+/// so the parser itself doesn't have to care to much about spaces in certain places,
+/// this allows to parse the output of convTermToStr(), ex: "( a , ^left() )"
+///
+/// spaces around copulas, images and between elements of sets are kept because they are significant
+///
+/// normalizes the byte range from `start` to `end`
+/// returns the normalized string and the offset into the original string for each byte of the normalized string
+fn normalizeSpaces(narsese:&str, start:usize, end:usize) -> (String, Vec<usize>) {
+  let mut res = String::new();
+  let mut offsets:Vec<usize> = vec![];
+  let bytes = &narsese.as_bytes()[..end];
+  for (idx, c) in narsese[start..end].char_indices() {
+    let idx = start+idx;
+    if c == ' ' {
+      let prevIsConnector = res.ends_with(|c| "(,*&|;!".contains(c)); // '!' is from negation "(!"
+      let next:Option<&u8> = bytes[idx..].iter().find(|b| **b != b' ');
+      let nextIsConnector = next.map_or(true, |b| b")*,&|;".contains(b));
+      if prevIsConnector || nextIsConnector || res.ends_with(' ') {
+        continue; // skip space, multiple spaces count as one
       }
     }
+    res.push(c);
+    for _i in 0..c.len_utf8() {
+      offsets.push(idx);
+    }
   }
+  offsets.push(end); // offset of the end
+  (res, offsets)
 }
 
-pub fn parseNarsese(narsese:&String) -> Option<(Term, Tv, EnumPunctation, bool)> {
-  let mut narsese2:String = narsese.clone();
+/// checks if the text looks like a copula
+pub(crate) fn looksLikeCopula(text:&str) -> bool {
+  let token:String = text.trim_start().chars().take_while(|c| !c.is_whitespace()).collect();
+  token.len() >= 2 && token.len() <= 4 && token.chars().all(|c| "-<>=/|\\~{]".contains(c))
+}
 
-  // HACK< replace spaces so the parser itself doesn't have to care to much about spaces in certain places >
-  while narsese2.contains("* ") {
-    narsese2 = narsese2.replace("* ", "*");
+/// parsed narsese sentence
+// PUBLICAPI
+pub struct NarseseSentence {
+  pub term:Term,
+  pub tv:Tv,
+  pub punct:EnumPunctation,
+  pub tense:EnumTense,
+  pub budget:Option<Budget>,
+  /// explicit exponential time delta of =/>, index into the table of the exponential intervals
+  pub expDt:Option<i64>,
+}
+
+// PUBLICAPI
+pub fn parseNarsese(narsese:&String) -> Result<(Term, Tv, EnumPunctation, bool), NarseseError> {
+  let parsed = parseNarsese2(narsese)?;
+  let isEvent = parsed.tense != EnumTense::ETERNAL;
+  Ok((parsed.term, parsed.tv, parsed.punct, isEvent))
+}
+
+/// tries to parse tense at the end of narsese and cuts it away if it was found
+fn parseNarseseRetTense(narsese:&mut String) -> Result<Option<EnumTense>, NarseseError> {
+  let trimmed = narsese.trim_end();
+  let tense = if trimmed.ends_with(":|:") {
+    Some((EnumTense::PRESENT, 3))
+  }
+  else if trimmed.ends_with(":/:") {
+    Some((EnumTense::FUTURE, 3))
+  }
+  else if trimmed.ends_with(":\\:") {
+    Some((EnumTense::PAST, 3))
   }
+  else if trimmed.ends_with(':') && trimmed.contains(":!") { // explicit occurrence time
+    let idx = trimmed.rfind(":!").unwrap();
+    match trimmed[idx+2..trimmed.len()-1].parse::<i64>() {
+      Ok(t) => Some((EnumTense::ABSOLUTE(t), trimmed.len()-idx)),
+      Err(_) => {return Err(mkErr(narsese, EnumNarseseErrorKind::BAD_TENSE, idx+2, &["occurrence time"]));}
+    }
+  }
+  else {
+    None
+  };
 
-  while narsese2.contains(" *") {
-    narsese2 = narsese2.replace(" *", "*");
+  match tense {
+    Some((tense, len)) => {
+      *narsese = trimmed[..trimmed.len()-len].trim_end().to_string(); // cut away
+      Ok(Some(tense))
+    },
+    None => Ok(None)
   }
+}
+
+/// tries to parse explicit exponential time delta at the end of narsese and cuts it away if it was found, ex: +EXPDT3
+fn parseNarseseRetExpDt(narsese:&mut String) -> Result<Option<i64>, NarseseError> {
+  let trimmed = narsese.trim_end();
+  let idx = match trimmed.rfind("+EXPDT") {
+    Some(idx) if trimmed[..idx].ends_with(' ') => idx,
+    _ => {return Ok(None);}
+  };
+  match trimmed[idx+6..].parse::<i64>() {
+    Ok(expDt) if expDt >= 0 => {
+      *narsese = trimmed[..idx].trim_end().to_string(); // cut away
+      Ok(Some(expDt))
+    },
+    _ => Err(mkErr(narsese, EnumNarseseErrorKind::BAD_EXPDT, idx+6, &["index of interval"])),
+  }
+}
+
+/// tries to parse budget at the beginning of narsese, ex: $0.8;0.5;0.9$
+/// missing durability defaults to 1.0, missing quality defaults to 0.0
+///
+/// `termStart` is set to the byte index after the budget
+fn parseNarseseRetBudget(narsese:&str, termStart:&mut usize) -> Result<Option<Budget>, NarseseError> {
+  let start = narsese.len() - narsese.trim_start().len();
+  *termStart = start;
+
+  let rest = &narsese[start..];
+  // must start with '$' and a digit, else it is a independent variable
+  if !(rest.starts_with('$') && rest[1..].starts_with(|c:char| c.is_ascii_digit())) {
+    return Ok(None);
+  }
+  let endIdx = match rest[1..].find('$') {
+    Some(idx) => start+1+idx,
+    None => {return Ok(None);} // not a budget
+  };
+
+  let mut values:Vec<f64> = vec![];
+  let mut componentStart = start+1;
+  for iComponent in narsese[start+1..endIdx].split(';') {
+    match iComponent.trim().parse::<f64>() {
+      Ok(v) if v >= 0.0 && v <= 1.0 && values.len() < 3 => {values.push(v);},
+      _ => {return Err(mkErr(narsese, EnumNarseseErrorKind::BAD_BUDGET, componentStart, &["value in [0, 1]"]));}
+    }
+    componentStart += iComponent.len()+1;
+  }
+
+  let rest2 = &narsese[endIdx+1..];
+  *termStart = endIdx+1 + (rest2.len() - rest2.trim_start().len()); // skip whitespace
 
+  Ok(Some(Budget{
+    p:values[0],
+    d:*values.get(1).unwrap_or(&1.0),
+    q:*values.get(2).unwrap_or(&0.0),
+  }))
+}
+
+/// parses narsese with all information
+///
+/// budget can be in front of the term, tense and TV can be in any order after the punctuation,
+/// explicit exponential time delta follows directly after the punctuation, ex: <(a,^x) =/> b>. +EXPDT3 {0.8 0.9}
+// PUBLICAPI
+pub fn parseNarsese2(narsese:&String) -> Result<NarseseSentence, NarseseError> {
+  let mut narsese2:String = narsese.trim_end().to_string();
 
-  let mut isEvent = false;
-  if narsese2.ends_with(" :|:") {
-    isEvent = true;
-    narsese2 = narsese2[..narsese2.len()-4].to_string();
+  if narsese2.trim_start().len() == 0 {
+    return Err(mkErr(narsese, EnumNarseseErrorKind::EMPTY, 0, &["narsese"])); // can't parse empty string!
   }
 
+  let mut termStart:usize = 0; // start of the term after the budget
+  let budget:Option<Budget> = parseNarseseRetBudget(narsese, &mut termStart)?;
+
+  let mut tense:Option<EnumTense> = parseNarseseRetTense(&mut narsese2)?;
+
   let mut tv = Tv{f:1.0,c:0.9};
-  parseNarseseRetTv(&mut narsese2, &mut tv);
-  //println!("{}", &narsese2);
+  parseNarseseRetTv(&mut narsese2, &mut tv)?;
   narsese2 = narsese2.trim_end().to_string();
-  //println!("{}", &narsese2);
 
-  if narsese2.len() == 0 {
-    return None; // can't parse empty string!
+  if tense.is_none() { // tense can be in front of TV
+    tense = parseNarseseRetTense(&mut narsese2)?;
   }
+
+  let expDt:Option<i64> = parseNarseseRetExpDt(&mut narsese2)?;
   
-  let punctationChar = narsese2.chars().nth(narsese2.len()-1).unwrap();
-  let narseseInner = narsese2[..narsese2.len()-1].to_string();
-  
+  let punctationChar = match narsese2.chars().last() {
+    Some(c) => c,
+    None => {return Err(mkErr(narsese, EnumNarseseErrorKind::EMPTY, 0, &["narsese"]));}
+  };
   let punctation = match punctationChar {
     '.' => {EnumPunctation::JUGEMENT},
     '!' => {EnumPunctation::GOAL},
     '?' => {EnumPunctation::QUESTION},
-    _ => {return None;},
+    '@' => {EnumPunctation::QUEST},
+    _ => {
+      let offset = narsese2.len()-punctationChar.len_utf8();
+      return Err(mkErr(narsese, EnumNarseseErrorKind::BAD_PUNCTUATION, offset, &[".", "!", "?", "@"]));
+    },
   };
+  let termEnd = narsese2.len()-1; // end of term before punctuation
+  if termStart > termEnd {
+    return Err(mkErr(narsese, EnumNarseseErrorKind::INVALID_TERM, termEnd, &["term"]));
+  }
 
-  //println!("{}   {}", narseseInner, punctationChar);
-  //println!("f = {}", tv.f);
-  //println!("c = {}", tv.c);
+  checkBrackets(narsese, termStart, termEnd)?;
 
+  let (narseseInner, offsets) = normalizeSpaces(&narsese2, termStart, termEnd);
   let parsed:IResult<&str, Term> = parseEntry(&narseseInner);
 
   match parsed {
-    Ok((_str2, term)) => {
-      Some((term, tv, punctation, isEvent))
+    Ok((rest, term)) => {
+      if rest.len() > 0 {
+        let offset = offsets[narseseInner.len()-rest.len()];
+        return Err(mkErr(narsese, EnumNarseseErrorKind::TRAILING_INPUT, offset, &[".", "!", "?", "@"]));
+      }
+      Ok(NarseseSentence{term:term, tv:tv, punct:punctation, tense:tense.unwrap_or(EnumTense::ETERNAL), budget:budget, expDt:expDt})
+    },
+    Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
+      let offset = offsets[narseseInner.len()-e.input.len()];
+      if offset > termStart && looksLikeCopula(e.input) {
+        return Err(mkErr(narsese, EnumNarseseErrorKind::UNKNOWN_COPULA, offset, &COPULA_TOKENS));
+      }
+      Err(mkErr(narsese, EnumNarseseErrorKind::INVALID_TERM, offset, &["term"]))
     },
-    Err(_) => {
-      None
+    Err(nom::Err::Incomplete(_)) => {
+      Err(mkErr(narsese, EnumNarseseErrorKind::INVALID_TERM, termEnd, &["term"]))
     }
   }
 }
@@ -106,13 +415,14 @@ pub fn parseNarsese(narsese:&String) -> Option<(Term, Tv, EnumPunctation, bool)>
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::Term::convTermToStr;
+  use crate::Term::{convTermToStr, convTermToStr2, checkEqTerm, normalize, standardizeVars, alphaEq};
+  use crate::NarSentence::convTenseToStr;
 
   #[test]
   pub fn inhWithTv() {
     let narsese = "<a --> b>. {0.4 0.8}".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<a --> b>");
@@ -124,8 +434,8 @@ mod tests {
   #[test]
   pub fn inhWithoutTv() {
     let narsese = "<a --> b>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<a --> b>");
@@ -137,8 +447,8 @@ mod tests {
   #[test]
   pub fn predImpl() {
     let narsese = "<a =/> b>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<a =/> b>");
@@ -150,8 +460,8 @@ mod tests {
   #[test]
   pub fn equiv() {
     let narsese = "<a <=> b>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<a <=> b>");
@@ -164,8 +474,8 @@ mod tests {
   #[test]
   pub fn numeric() {
     let narsese = "<0 --> b>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<0 --> b>");
@@ -177,8 +487,8 @@ mod tests {
   #[test]
   pub fn qVar() {
     let narsese = "<?a --> b>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<?a --> b>");
@@ -190,8 +500,8 @@ mod tests {
   #[test]
   pub fn indepVar() {
     let narsese = "<$a --> b>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<$a --> b>");
@@ -203,8 +513,8 @@ mod tests {
   #[test]
   pub fn depVar() {
     let narsese = "<#a --> b>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<#a --> b>");
@@ -217,8 +527,8 @@ mod tests {
   #[test]
   pub fn setInt() {
     let narsese = "<a --> [b]>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<a --> [b]>");
@@ -230,8 +540,8 @@ mod tests {
   #[test]
   pub fn setExt() {
     let narsese = "<{a} --> b>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<{a} --> b>");
@@ -243,8 +553,8 @@ mod tests {
   #[test]
   pub fn setExtProd() {
     let narsese = "<{(a*c)} --> b>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<{( a * c )} --> b>");
@@ -256,8 +566,8 @@ mod tests {
   #[test]
   pub fn setExtProd_spaces() {
     let narsese = "<{(a * c)} --> b>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<{( a * c )} --> b>");
@@ -269,8 +579,8 @@ mod tests {
   #[test]
   pub fn prod2() {
     let narsese = "<(a*c) --> x>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<( a * c ) --> x>");
@@ -282,8 +592,8 @@ mod tests {
   #[test]
   pub fn img2_0() {
     let narsese = "<(a /1 c) --> x>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<( a /1 c ) --> x>");
@@ -295,8 +605,8 @@ mod tests {
   #[test]
   pub fn img2_1() {
     let narsese = "<(a /2 c) --> x>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<( a /2 c ) --> x>");
@@ -309,8 +619,8 @@ mod tests {
   #[test]
   pub fn seq2() {
     let narsese = "(a,c).".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "( a , c )");
@@ -322,8 +632,8 @@ mod tests {
   #[test]
   pub fn seq3() {
     let narsese = "(a,c,d).".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "( a , c , d )");
@@ -335,8 +645,8 @@ mod tests {
   #[test]
   pub fn prod3() {
     let narsese = "<(a*c*z) --> x>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<( a * c * z ) --> x>");
@@ -348,8 +658,8 @@ mod tests {
   #[test]
   pub fn prod4() {
     let narsese = "<(a*c*z*y) --> x>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<( a * c * z * y ) --> x>");
@@ -361,8 +671,8 @@ mod tests {
   #[test]
   pub fn intint2() {
     let narsese = "<(a|c) --> x>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<( a | c ) --> x>");
@@ -374,8 +684,8 @@ mod tests {
   #[test]
   pub fn extint2() {
     let narsese = "<(a&c) --> x>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<( a & c ) --> x>");
@@ -388,8 +698,8 @@ mod tests {
   #[test]
   pub fn conj2_0() {
     let narsese = "<(a&&c) ==> x>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<( a && c ) ==> x>");
@@ -401,8 +711,8 @@ mod tests {
   #[test]
   pub fn conj2_1() {
     let narsese = "<(<a --> b>&&c) ==> x>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<( <a --> b> && c ) ==> x>");
@@ -414,8 +724,8 @@ mod tests {
   #[test]
   pub fn conj3_0() {
     let narsese = "<(a&&c&&d) ==> x>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<( a && c && d ) ==> x>");
@@ -427,8 +737,8 @@ mod tests {
   #[test]
   pub fn conj4_0() {
     let narsese = "<(a&&c&&d&&e) ==> x>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<( a && c && d && e ) ==> x>");
@@ -440,8 +750,8 @@ mod tests {
   #[test]
   pub fn seqPredImpl() {
     let narsese = "<(a,b) =/> c>.".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "<( a , b ) =/> c>");
@@ -455,8 +765,8 @@ mod tests {
   #[test]
   pub fn goalWithoutTv() {
     let narsese = "a-c!".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, _tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "a-c");
@@ -464,11 +774,23 @@ mod tests {
   }
 
 
+  // quest
+  #[test]
+  pub fn quest_0() {
+    let narsese = "<a --> b>@".to_string();
+    let parsed = parseNarsese2(&narsese).ok().unwrap();
+    assert_eq!(convTermToStr(&parsed.term), "<a --> b>");
+    assert_eq!(parsed.punct, EnumPunctation::QUEST);
+
+    let sentence = crate::NarSentence::newEternalSentenceByTv(&parsed.term, parsed.punct, &parsed.tv, crate::NarStamp::newStamp(&vec![0]));
+    assert_eq!(crate::NarSentence::convSentenceTermPunctToStr(&sentence, true), "<a --> b>@");
+  }
+
   #[test]
   pub fn neg_0() {
     let narsese = "(!a).".to_string();
-    let parseResOpt: Option<(Term, Tv, EnumPunctation, bool)> = parseNarsese(&narsese);
-    assert_eq!(parseResOpt.is_some(), true);
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
     
     let (term, tv, punct, _isEvent) = parseResOpt.unwrap();
     assert_eq!(convTermToStr(&term), "(! a )");
@@ -476,6 +798,274 @@ mod tests {
     assert_eq!((tv.c - 0.9).abs() < 0.01, true);
     assert_eq!(punct, EnumPunctation::JUGEMENT);
   }
+
+
+  #[test]
+  pub fn tense_0() {
+    let narsese = "<a --> b>. :|:".to_string();
+    let parsed = parseNarsese2(&narsese).ok().unwrap();
+    assert_eq!(convTermToStr(&parsed.term), "<a --> b>");
+    assert_eq!(parsed.tense, EnumTense::PRESENT);
+    
+    let narsese = "<a --> b>! :/: {0.6 0.8}".to_string();
+    let parsed = parseNarsese2(&narsese).ok().unwrap();
+    assert_eq!(parsed.tense, EnumTense::FUTURE);
+    assert_eq!(parsed.punct, EnumPunctation::GOAL);
+    assert_eq!((parsed.tv.f - 0.6).abs() < 0.01, true);
+    assert_eq!((parsed.tv.c - 0.8).abs() < 0.01, true);
+
+    let narsese = "<a --> b>. {0.6 0.8} :\\:".to_string();
+    let parsed = parseNarsese2(&narsese).ok().unwrap();
+    assert_eq!(parsed.tense, EnumTense::PAST);
+    assert_eq!((parsed.tv.f - 0.6).abs() < 0.01, true);
+  }
+
+  // explicit occurrence time
+  #[test]
+  pub fn tense_absolute() {
+    let narsese = "a. :!-42:".to_string();
+    let parsed = parseNarsese2(&narsese).ok().unwrap();
+    assert_eq!(parsed.tense, EnumTense::ABSOLUTE(-42));
+    assert_eq!(convTenseToStr(parsed.tense), ":!-42:");
+
+    let narsese = "a. :!x:".to_string();
+    let err = parseNarsese2(&narsese).err().unwrap();
+    assert_eq!(err.kind, EnumNarseseErrorKind::BAD_TENSE);
+    assert_eq!(err.offset, 5);
+  }
+
+  #[test]
+  pub fn seqInterval() {
+    let narsese = "<(a,+5,^b,+3) =/> c>.".to_string();
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
+    
+    let (term, _tv, _punct, isEvent) = parseResOpt.unwrap();
+    assert_eq!(convTermToStr(&term), "<( a , +5 , ^b , +3 ) =/> c>");
+    assert_eq!(isEvent, false);
+  }
+
+  // sequences in the form of OpenNARS and ONA
+  #[test]
+  pub fn seqOpenNarsOna() {
+    let narsese = "<(&/,a,+5,^b) =/> c>.".to_string();
+    let parsed = parseNarsese2(&narsese).ok().unwrap();
+    assert_eq!(convTermToStr(&parsed.term), "<( a , +5 , ^b ) =/> c>");
+
+    let narsese = "<(a &/ ^b) =/> c>.".to_string();
+    let parsed = parseNarsese2(&narsese).ok().unwrap();
+    assert_eq!(convTermToStr(&parsed.term), "<( a , ^b ) =/> c>");
+
+    let narsese = "<(a &/ +5 &/ ^b) =/> c>.".to_string();
+    let parsed = parseNarsese2(&narsese).ok().unwrap();
+    assert_eq!(convTermToStr(&parsed.term), "<( a , +5 , ^b ) =/> c>");
+  }
+
+  // TV of procedural knowledge is transcribed to whole counts of evidence, explicit exponential time delta
+  #[test]
+  pub fn predImplTv() {
+    let narsese = "<(a,^x) =/> b>. +EXPDT3 {0.8 0.9}".to_string();
+    let parsed = parseNarsese2(&narsese).ok().unwrap();
+    assert_eq!(parsed.expDt, Some(3));
+    let evi = crate::NarSentence::convTvToCnt(&parsed.tv);
+    assert_eq!((crate::NarSentence::retFreq(&evi) - 7.0/9.0).abs() < 0.0001, true); // 0.8 is rounded to 7 of 9 observations
+    assert_eq!((crate::NarSentence::retConf(&evi) - 0.9).abs() < 0.0001, true);
+
+    assert_eq!(parseNarsese2(&"<(a,^x) =/> b>.".to_string()).ok().unwrap().expDt, None);
+    assert_eq!(parseNarsese2(&"<(a,^x) =/> b>. +EXPDTx".to_string()).err().unwrap().kind, EnumNarseseErrorKind::BAD_EXPDT);
+  }
+
+  #[test]
+  pub fn budget_0() {
+    let narsese = "$0.8;0.5;0.3$ <a --> b>.".to_string();
+    let parsed = parseNarsese2(&narsese).ok().unwrap();
+    assert_eq!(convTermToStr(&parsed.term), "<a --> b>");
+    let budget = parsed.budget.unwrap();
+    assert_eq!((budget.p - 0.8).abs() < 0.01, true);
+    assert_eq!((budget.d - 0.5).abs() < 0.01, true);
+    assert_eq!((budget.q - 0.3).abs() < 0.01, true);
+
+    // missing durability is 1.0, missing quality is 0.0
+    let narsese = "$0.2$a! :|:".to_string();
+    let parsed = parseNarsese2(&narsese).ok().unwrap();
+    assert_eq!(convTermToStr(&parsed.term), "a");
+    let budget = parsed.budget.unwrap();
+    assert_eq!((budget.p - 0.2).abs() < 0.01, true);
+    assert_eq!((budget.d - 1.0).abs() < 0.01, true);
+    assert_eq!(budget.q, 0.0);
+
+    // independent variable isn't a budget
+    let narsese = "<$1 --> b>.".to_string();
+    assert_eq!(parseNarsese2(&narsese).ok().unwrap().budget.is_none(), true);
+  }
+
+  #[test]
+  pub fn err_budget() {
+    let narsese = "$0.8;1.5$ <a --> b>.".to_string();
+    let err = parseNarsese2(&narsese).err().unwrap();
+    assert_eq!(err.kind, EnumNarseseErrorKind::BAD_BUDGET);
+    assert_eq!(err.offset, 5);
+
+    // error offsets are relative to the complete narsese
+    let narsese = "$0.8$ <a -/> b>.".to_string();
+    let err = parseNarsese2(&narsese).err().unwrap();
+    assert_eq!(err.kind, EnumNarseseErrorKind::UNKNOWN_COPULA);
+    assert_eq!(err.offset, 8);
+  }
+
+  #[test]
+  pub fn opCall_0() {
+    let narsese = "^go(a, b)! :|:".to_string();
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
+    
+    let (term, _tv, punct, isEvent) = parseResOpt.unwrap();
+    assert_eq!(convTermToStr(&term), "^go(a, b)"); // short form by default
+    assert_eq!(convTermToStr2(&term, false), "<{( {SELF} * a * b )} --> ^go>");
+    assert_eq!(punct, EnumPunctation::GOAL);
+    assert_eq!(isEvent, true);
+
+    // must be the same term as the canonical form
+    let (termCanonical, _, _, _) = parseNarsese(&"<{({SELF}*a*b)} --> ^go>! :|:".to_string()).ok().unwrap();
+    assert_eq!(checkEqTerm(&term, &termCanonical), true);
+
+    // written as product
+    let (term2, _, _, _) = parseNarsese(&"(^go, a, b)! :|:".to_string()).ok().unwrap();
+    assert_eq!(checkEqTerm(&term2, &termCanonical), true);
+  }
+
+  #[test]
+  pub fn opCall_1() {
+    let narsese = "<(a,^go()) =/> b>.".to_string();
+    let parseResOpt: Result<(Term, Tv, EnumPunctation, bool), NarseseError> = parseNarsese(&narsese);
+    assert_eq!(parseResOpt.is_ok(), true);
+    
+    let (term, _tv, _punct, _isEvent) = parseResOpt.unwrap();
+    assert_eq!(convTermToStr2(&term, true), "<( a , ^go() ) =/> b>");
+
+    // op without {SELF} is printed in the long form
+    let (term2, _, _, _) = parseNarsese(&"<{(x*a)} --> ^go>.".to_string()).ok().unwrap();
+    assert_eq!(convTermToStr2(&term2, true), "<{( x * a )} --> ^go>");
+  }
+
+  // nested op calls
+  #[test]
+  pub fn opCall_nested() {
+    let narsese = "<(a,^nal9_exeAndInject((^nop(dummy0),^nop(dummy0)), b)) =/> g>.".to_string();
+    let (term, _, _, _) = parseNarsese(&narsese).ok().unwrap();
+    let (termCanonical, _, _, _) = parseNarsese(&"<(a,<{({SELF}*(<{({SELF}*dummy0)} --> ^nop>,<{({SELF}*dummy0)} --> ^nop>)*b)} --> ^nal9_exeAndInject>) =/> g>.".to_string()).ok().unwrap();
+    assert_eq!(checkEqTerm(&term, &termCanonical), true);
+    assert_eq!(convTermToStr2(&term, true), "<( a , ^nal9_exeAndInject(( ^nop(dummy0) , ^nop(dummy0) ), b) ) =/> g>");
+  }
+
+  // printed terms must parse back to the same term, is required to load exported knowledge
+  #[test]
+  pub fn printedRoundtrip() {
+    for iNarsese in &[
+      "<(a,^left()) =/> b>.", "<(a,+5) =/> b>.", "<(a*b*c) --> r>.", "<{a b} --> [c d]>.", "<(a&&b&&c) ==> d>.",
+      "<(a|b|c) --> d>.", "<(a&b) --> d>.", "<(a;b) =/> c>.", "<(!a) --> b>.", "<(r /2 a) --> b>.", "<#x --> $y>.", "^go(a, {b c})!",
+      "<(a - b) --> c>.", "<c --> (a ~ (b - c))>.",
+    ] {
+      let (term, _, _, _) = parseNarsese(&iNarsese.to_string()).ok().unwrap();
+      let printed:String = format!("{}.", convTermToStr2(&term, true));
+      let (term2, _, _, _) = parseNarsese(&printed).ok().unwrap();
+      assert_eq!(checkEqTerm(&term, &term2), true, "{} was printed as {}", iNarsese, printed);
+    }
+  }
+
+  // commutative compounds must have the same canonical form independent of the order of the components
+  #[test]
+  pub fn normalizeCommutative() {
+    let parseTerm = |narsese:&str| -> Term {parseNarsese(&narsese.to_string()).ok().unwrap().0};
+    assert_eq!(checkEqTerm(&normalize(&parseTerm("<{b a} --> c>.")), &normalize(&parseTerm("<{a b a} --> c>."))), true);
+    assert_eq!(convTermToStr(&normalize(&parseTerm("<(c&&(b&&a)) ==> d>."))), "<( a && b && c ) ==> d>");
+    assert_eq!(convTermToStr(&normalize(&parseTerm("<(b|a|b) --> d>."))), "<( a | b ) --> d>");
+    assert_eq!(convTermToStr(&normalize(&parseTerm("<(b*a) --> d>."))), "<( b * a ) --> d>"); // product isn't commutative
+    assert_eq!(convTermToStr(&normalize(&parseTerm("<(<$y --> a>&&<$x --> b>) ==> c>."))), "<( <$y --> a> && <$x --> b> ) ==> c>"); // names of variables don't decide the order
+
+    // unification must find the matching order
+    let unified = crate::NarUnify::unify(&parseTerm("<{?x b} --> c>."), &parseTerm("<{b a} --> c>."));
+    assert_eq!(convTermToStr(&unified.unwrap()[0].val), "a");
+  }
+
+  // NAL-2 copulas must be expanded to inheritance with sets
+  #[test]
+  pub fn nal2Copulas() {
+    let parseTerm = |narsese:&str| -> Term {parseNarsese(&narsese.to_string()).ok().unwrap().0};
+    assert_eq!(convTermToStr(&parseTerm("<tom {-- cat>.")), "<{tom} --> cat>");
+    assert_eq!(convTermToStr(&parseTerm("<sky --] blue>.")), "<sky --> [blue]>");
+    assert_eq!(convTermToStr(&parseTerm("<sky {-] blue>.")), "<{sky} --> [blue]>");
+  }
+
+  // variables must be renamed in the order of the first occurrence
+  #[test]
+  pub fn alphaEquivalence() {
+    let parseTerm = |narsese:&str| -> Term {parseNarsese(&narsese.to_string()).ok().unwrap().0};
+    assert_eq!(convTermToStr(&standardizeVars(&parseTerm("<($x*#y) --> $x>."))), "<( $1 * #2 ) --> $1>");
+    assert_eq!(alphaEq(&parseTerm("<($x*a) --> r>."), &parseTerm("<($y*a) --> r>.")), true);
+    assert_eq!(alphaEq(&parseTerm("<($x*$y) --> r>."), &parseTerm("<($y*$y) --> r>.")), false);
+    assert_eq!(alphaEq(&parseTerm("<($x*a) --> r>."), &parseTerm("<(#x*a) --> r>.")), false);
+    assert_eq!(alphaEq(&parseTerm("<(<$x --> a>&&<$y --> b>) ==> c>."), &parseTerm("<(<$y --> a>&&<$x --> b>) ==> c>.")), true); // commutative
+    assert_eq!(alphaEq(&parseTerm("<(<$x --> a>&&<$y --> b>) ==> c>."), &parseTerm("<(<$y --> a>&&<$y --> b>) ==> c>.")), false);
+  }
+
+  // malformed TV must return error and not panic
+  #[test]
+  pub fn err_badTv() {
+    let narsese = "<a --> b>. {1.0 x}".to_string();
+    let err = parseNarsese(&narsese).err().unwrap();
+    assert_eq!(err.kind, EnumNarseseErrorKind::BAD_TV);
+    assert_eq!(err.offset, 16);
+    assert_eq!(err.column, 17);
+  }
+
+  #[test]
+  pub fn err_unbalanced() {
+    let narsese = "<(a * b --> c>.".to_string();
+    let err = parseNarsese(&narsese).err().unwrap();
+    assert_eq!(err.kind, EnumNarseseErrorKind::UNBALANCED_BRACKETS);
+    assert_eq!(err.offset, 13);
+    assert_eq!(err.expected, vec![")".to_string()]);
+  }
+
+  // more than one space in front of a copula must not hang the parser
+  #[test]
+  pub fn err_doubleSpace() {
+    for iNarsese in &["<a  --> b>.", "<a  ==> b>.", "<a  --] b>.", "<(a;b)   {-- c>."] {
+      let parsed = parseNarsese2(&iNarsese.to_string()).ok().unwrap();
+      let expected = parseNarsese2(&iNarsese.split_whitespace().collect::<Vec<&str>>().join(" ")).ok().unwrap();
+      assert_eq!(checkEqTerm(&parsed.term, &expected.term), true);
+    }
+    assert_eq!(parseNarsese2(&"<a  -/> b>.".to_string()).err().unwrap().kind, EnumNarseseErrorKind::UNKNOWN_COPULA);
+  }
+
+  #[test]
+  pub fn err_unknownCopula() {
+    let narsese = "<a -/> b>.".to_string();
+    let err = parseNarsese(&narsese).err().unwrap();
+    assert_eq!(err.kind, EnumNarseseErrorKind::UNKNOWN_COPULA);
+    assert_eq!(err.offset, 2);
+    assert_eq!(err.found, "-/>");
+  }
+
+  #[test]
+  pub fn err_trailingInput() {
+    let narsese = "<a --> b> c.".to_string();
+    let err = parseNarsese(&narsese).err().unwrap();
+    assert_eq!(err.kind, EnumNarseseErrorKind::TRAILING_INPUT);
+    assert_eq!(err.offset, 9);
+  }
+
+  #[test]
+  pub fn err_punctuation() {
+    // non-ASCII input must not panic
+    let narsese = "<a --> b>€".to_string();
+    let err = parseNarsese(&narsese).err().unwrap();
+    assert_eq!(err.kind, EnumNarseseErrorKind::BAD_PUNCTUATION);
+    assert_eq!(err.offset, 9);
+
+    assert_eq!(parseNarsese(&"".to_string()).err().unwrap().kind, EnumNarseseErrorKind::EMPTY);
+  }
 }
 
 
@@ -497,10 +1087,34 @@ fn alpha2(input: &str) -> IResult<&str, &str> {
 
 fn a(input:&str)  -> IResult<&str, Term> {
   let (input, _) = tag("{")(input)?;
-  let (input, termContent) = parseSubjOrPred(input, true)?; // many_m_n!(1, 3, tag("a"))(input)?;
+  let (input, elements) = parseSetElements(input)?;
   let (input, _) = tag("}")(input)?;
 
-  Ok((input, Term::SetExt(vec![Box::new(termContent)])))  // return {termContent}
+  Ok((input, Term::SetExt(elements.into_iter().map(|v| Box::new(v)).collect())))  // return {elements}
+}
+
+// elements of a set separated by space, ex: a b c
+fn parseSetElements(input:&str) -> IResult<&str, Vec<Term>> {
+  let mut elements = vec![];
+  let (mut input, element) = parseSubjOrPred(input, true)?;
+  elements.push(element);
+
+  loop { // loop for more elements
+    let res0: IResult<&str, &str> = tag(" ")(input);
+    match res0 {
+      Ok((input2, _)) => {
+        input = input2;
+      },
+      Err(_) => {
+        break;
+      },
+    };
+
+    let (input2, element) = parseSubjOrPred(input, true)?;
+    input = input2;
+    elements.push(element);
+  }
+  Ok((input, elements))
 }
 
 fn b(input:&str)  -> IResult<&str, Term> {
@@ -509,12 +1123,74 @@ fn b(input:&str)  -> IResult<&str, Term> {
 }
 
 
+// name of op, ex: ^go
+fn opName(input:&str) -> IResult<&str, String> {
+  let (input, _) = tag("^")(input)?;
+  let (input, name) = alpha2(input)?;
+  Ok((input, format!("^{}", name)))
+}
+
+// arguments of a op call separated by ",", ex: a, b
+fn opArgs(input:&str) -> IResult<&str, Vec<Term>> {
+  let mut args = vec![];
+  let (mut input, arg) = parseSubjOrPred(input, true)?;
+  args.push(arg);
+
+  loop { // loop for more arguments
+    let res0: IResult<&str, &str> = tag(",")(input);
+    match res0 {
+      Ok((input2, _)) => {
+        input = input2;
+      },
+      Err(_) => {
+        break;
+      },
+    };
+
+    let (input2, _) = space0(input)?;
+    let (input2, arg) = parseSubjOrPred(input2, true)?;
+    input = input2;
+    args.push(arg);
+  }
+  Ok((input, args))
+}
+
+// op call, ex: ^go(a, b)
+pub fn parseOpCall(input:&str) -> IResult<&str, Term> {
+  let (input, name) = opName(input)?;
+  let (input, _) = tag("(")(input)?;
+  let (input, args) = match tag::<&str, &str, nom::error::Error<&str>>(")")(input) {
+    Ok((input, _)) => {return Ok((input, encodeOpCall(&vec![], &name)));}, // op without arguments
+    Err(_) => opArgs(input)?,
+  };
+  let (input, _) = tag(")")(input)?;
+  Ok((input, encodeOpCall(&args, &name)))
+}
+
+// op call written as product, ex: (^go, a, b)
+pub fn parseOpCall2(input:&str) -> IResult<&str, Term> {
+  let (input, _) = tag("(")(input)?;
+  let (input, name) = opName(input)?;
+  let (input, _) = tag(",")(input)?;
+  let (input, _) = space0(input)?;
+  let (input, args) = opArgs(input)?;
+  let (input, _) = tag(")")(input)?;
+  Ok((input, encodeOpCall(&args, &name)))
+}
+
+/// builds the canonical form of a op call, <{({SELF} * args...)} --> ^name>
+fn encodeOpCall(args:&Vec<Term>, name:&String) -> Term {
+  let mut args2:Vec<Term> = vec![Term::SetExt(vec![Box::new(Term::Name("SELF".to_string()))])];
+  args2.extend(args.iter().cloned());
+  encodeOp(&args2, name)
+}
+
 fn c(input:&str)  -> IResult<&str, Term> {
   let (input, _) = tag("[")(input)?;
-  let (input, termContent) = parseSubjOrPred(input, true)?;
+  let (input, elements) = parseSetElements(input)?;
   let (input, _) = tag("]")(input)?;
 
-  Ok((input, Term::SetInt(vec![Box::new(termContent)])))  // return {termContent}
+  Ok((input, Term::SetInt(elements.into_iter().map(|v| Box::new(v)).collect())))  // return [elements]
 }
 
 
@@ -536,13 +1212,35 @@ fn indepVar(input:&str) -> IResult<&str, Term> {
 
 // /param enStatement enable parsing of statement
 fn parseSubjOrPred(input: &str, _enStatement:bool) -> IResult<&str, Term> {
+  let mut furthestErr:Option<nom::Err<nom::error::Error<&str>>> = None; // error which got furthest, is the most useful to report
+
+  {
+    let res0 = parseOpCall(input);
+    match res0 {
+      Ok(term) => {
+        return Ok(term.clone())
+      },
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
+    }
+  }
+
+  {
+    let res0 = parseOpCall2(input);
+    match res0 {
+      Ok(term) => {
+        return Ok(term.clone())
+      },
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
+    }
+  }
+
   {
     let res0 = a(input);
     match res0 {
       Ok(term) => {
         return Ok(term.clone())
       },
-      Err(_) => {}, // try other choice
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
     }
   }
 
@@ -552,7 +1250,27 @@ fn parseSubjOrPred(input: &str, _enStatement:bool) -> IResult<&str, Term> {
       Ok(term) => {
         return Ok(term.clone())
       },
-      Err(_) => {}, // try other choice
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
+    }
+  }
+
+  {
+    let res0 = parseSeqOpenNars(input);
+    match res0 {
+      Ok(term) => {
+        return Ok(term.clone())
+      },
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
+    }
+  }
+
+  {
+    let res0 = parseSeqOna(input);
+    match res0 {
+      Ok(term) => {
+        return Ok(term.clone())
+      },
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
     }
   }
 
@@ -562,7 +1280,7 @@ fn parseSubjOrPred(input: &str, _enStatement:bool) -> IResult<&str, Term> {
       Ok(term) => {
         return Ok(term.clone())
       },
-      Err(_) => {}, // try other choice
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
     }
   }
 
@@ -572,7 +1290,7 @@ fn parseSubjOrPred(input: &str, _enStatement:bool) -> IResult<&str, Term> {
       Ok(term) => {
         return Ok(term.clone())
       },
-      Err(_) => {}, // try other choice
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
     }
   }
 
@@ -582,7 +1300,7 @@ fn parseSubjOrPred(input: &str, _enStatement:bool) -> IResult<&str, Term> {
       Ok(term) => {
         return Ok(term.clone())
       },
-      Err(_) => {}, // try other choice
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
     }
   }
 
@@ -592,7 +1310,7 @@ fn parseSubjOrPred(input: &str, _enStatement:bool) -> IResult<&str, Term> {
       Ok(term) => {
         return Ok(term.clone())
       },
-      Err(_) => {}, // try other choice
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
     }
   }
 
@@ -602,55 +1320,58 @@ fn parseSubjOrPred(input: &str, _enStatement:bool) -> IResult<&str, Term> {
       Ok(term) => {
         return Ok(term.clone())
       },
-      Err(_) => {}, // try other choice
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
     }
   }
 
   {
-    let res0 = parseImg0(input);
+    let res0 = parseImg(input);
     match res0 {
       Ok(term) => {
         return Ok(term.clone())
       },
-      Err(_) => {}, // try other choice
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
     }
   }
 
+
   {
-    let res0 = parseImg1(input);
+    let res0 = parseConj(input);
     match res0 {
       Ok(term) => {
         return Ok(term.clone())
       },
-      Err(_) => {}, // try other choice
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
     }
   }
 
   {
-    let res0 = parseConj2(input);
+    let res0 = parsePar(input);
     match res0 {
       Ok(term) => {
         return Ok(term.clone())
       },
-      Err(_) => {}, // try other choice
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
     }
   }
+
   {
-    let res0 = parseConj3(input);
+    let res0 = parseDiffExt(input);
     match res0 {
       Ok(term) => {
         return Ok(term.clone())
       },
-      Err(_) => {}, // try other choice
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
     }
   }
+
   {
-    let res0 = parseConj4(input);
+    let res0 = parseDiffInt(input);
     match res0 {
       Ok(term) => {
         return Ok(term.clone())
       },
-      Err(_) => {}, // try other choice
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
     }
   }
 
@@ -660,7 +1381,7 @@ fn parseSubjOrPred(input: &str, _enStatement:bool) -> IResult<&str, Term> {
       Ok(term) => {
         return Ok(term.clone())
       },
-      Err(_) => {}, // try other choice
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
     }
   }
 
@@ -670,7 +1391,7 @@ fn parseSubjOrPred(input: &str, _enStatement:bool) -> IResult<&str, Term> {
       Ok(term) => {
         return Ok(term.clone())
       },
-      Err(_) => {}, // try other choice
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
     }
   }
 
@@ -680,7 +1401,7 @@ fn parseSubjOrPred(input: &str, _enStatement:bool) -> IResult<&str, Term> {
       Ok(term) => {
         return Ok(term.clone())
       },
-      Err(_) => {}, // try other choice
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
     }
   }
 
@@ -690,11 +1411,29 @@ fn parseSubjOrPred(input: &str, _enStatement:bool) -> IResult<&str, Term> {
       Ok(term) => {
         return Ok(term.clone())
       },
-      Err(_) => {}, // try other choice
+      Err(e) => {furthestErr = selFurthestErr(furthestErr, e);}, // try other choice
     }
   }
 
-  return b(input)
+  match b(input) {
+    Ok(term) => Ok(term),
+    Err(e) => Err(selFurthestErr(furthestErr, e).unwrap()),
+  }
+}
+
+/// selects the error which got further into the input
+fn selFurthestErr<'a>(a:Option<nom::Err<nom::error::Error<&'a str>>>, b:nom::Err<nom::error::Error<&'a str>>) -> Option<nom::Err<nom::error::Error<&'a str>>> {
+  // helper to compute the length of the remaining input
+  fn retRemainingLen(e:&nom::Err<nom::error::Error<&str>>) -> usize {
+    match e {
+      nom::Err::Error(e) | nom::Err::Failure(e) => e.input.len(),
+      nom::Err::Incomplete(_) => 0,
+    }
+  }
+  match a {
+    Some(a) if retRemainingLen(&a) <= retRemainingLen(&b) => Some(a),
+    _ => Some(b),
+  }
 }
 
 
@@ -720,6 +1459,21 @@ fn copEquiv(input:&str)  -> IResult<&str, Copula> {
   Ok((input, Copula::EQUIV))
 }
 
+/// parses copula including the NAL-2 instance, property and instance-property copulas
+///
+/// returns the copula and if the subject must be wrapped into {} and the predicate into []
+fn parseCopula2(input: &str) -> IResult<&str, (Copula, bool, bool)> {
+  for (token, isInstance, isProperty) in &[(" {-- ", true, false), (" --] ", false, true), (" {-] ", true, true)] {
+    let res0: IResult<&str, &str> = tag(*token)(input);
+    if let Ok((input, _)) = res0 {
+      return Ok((input, (Copula::INH, *isInstance, *isProperty)));
+    }
+  }
+
+  let (input, copula) = parseCopula(input)?;
+  Ok((input, (copula, false, false)))
+}
+
 fn parseCopula(input: &str) -> IResult<&str, Copula> {
   {
     let res0 = copInh(input);
@@ -776,7 +1530,7 @@ pub fn parseSeq(input: &str) -> IResult<&str, Term> {
   let (input, a) = parseSubjOrPred(input, true)?;
   subterms.push(a.clone());
   let (input, _) = tag(",")(input)?;
-  let (mut input, b) = parseSubjOrPred(input, true)?;
+  let (mut input, b) = parseSeqElement(input)?;
   subterms.push(b.clone());
 
   loop { // loop for more sub-terms
@@ -790,7 +1544,7 @@ pub fn parseSeq(input: &str) -> IResult<&str, Term> {
       },
     };
 
-    let (input2, subterm) = parseSubjOrPred(input, true)?;
+    let (input2, subterm) = parseSeqElement(input)?;
     input = input2;
     subterms.push(subterm.clone());
   }
@@ -801,21 +1555,146 @@ pub fn parseSeq(input: &str) -> IResult<&str, Term> {
 }
 
 
+// parses sequence in the form of OpenNARS, ex: (&/,a,+5,b)
+pub fn parseSeqOpenNars(input: &str) -> IResult<&str, Term> {
+  let mut subterms = vec![];
+
+  let (input, _) = tag("(&/,")(input)?;
+  let (mut input, a) = parseSubjOrPred(input, true)?;
+  subterms.push(a);
+
+  loop { // loop for more sub-terms
+    let res0: IResult<&str, &str> = tag(",")(input);
+    match res0 {
+      Ok((input2, _)) => {
+        input = input2;
+      },
+      Err(_) => {
+        break;
+      },
+    };
+
+    let (input2, subterm) = parseSeqElement(input)?;
+    input = input2;
+    subterms.push(subterm);
+  }
+
+  let (input, _) = tag(")")(input)?;
+  Ok((input, seq(&subterms)))
+}
+
+// parses sequence in the form of ONA, ex: (a &/ b)
+pub fn parseSeqOna(input: &str) -> IResult<&str, Term> {
+  let mut subterms = vec![];
+
+  let (input, _) = tag("(")(input)?;
+  let (mut input, a) = parseSubjOrPred(input, true)?;
+  subterms.push(a);
+
+  loop { // loop for more sub-terms
+    let res0: IResult<&str, &str> = tag("&/")(input);
+    match res0 {
+      Ok((input2, _)) => {
+        let (input2, _) = space0(input2)?; // space after the connector isn't removed by normalizeSpaces()
+        input = input2;
+      },
+      Err(_) => {
+        break;
+      },
+    };
+
+    let (input2, subterm) = parseSeqElement(input)?;
+    input = input2;
+    subterms.push(subterm);
+  }
+
+  if subterms.len() < 2 {
+    return Err(nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Tag)));
+  }
+
+  let (input, _) = tag(")")(input)?;
+  Ok((input, seq(&subterms)))
+}
+
+// element of sequence after the first element, can be a interval
+fn parseSeqElement(input: &str) -> IResult<&str, Term> {
+  match parseInterval(input) {
+    Ok(res) => Ok(res),
+    Err(_) => parseSubjOrPred(input, true), // try other choice
+  }
+}
+
+// interval between events, ex: +5
+pub fn parseInterval(input: &str) -> IResult<&str, Term> {
+  let (input, _) = tag("+")(input)?;
+  let (input, dt) = map_res(digit1, |s: &str| s.parse::<i64>())(input)?;
+  Ok((input, Term::Interval(dt)))
+}
+
 pub fn parseExtInt2(input: &str) -> IResult<&str, Term> {
+  let (input, elements) = parseCompound(input, "&")?;
+  Ok((input, Term::ExtInt(elements.iter().map(|v| Box::new((*v).clone())).collect())))
+}
+pub fn parseIntInt2(input: &str) -> IResult<&str, Term> {
+  let (input, elements) = parseCompound(input, "|")?;
+  Ok((input, Term::IntInt(elements.iter().map(|v| Box::new((*v).clone())).collect())))
+}
+
+pub fn parsePar(input: &str) -> IResult<&str, Term> {
+  let (input, elements) = parseCompound(input, ";")?;
+  Ok((input, Term::Par(elements.iter().map(|v| Box::new((*v).clone())).collect())))
+}
+
+// parses extensional difference, ex: (a - b)
+pub fn parseDiffExt(input: &str) -> IResult<&str, Term> {
+  let (input, (a, b)) = parseDiff(input, " - ")?; // needs spaces because '-' can be part of a name
+  Ok((input, Term::DiffExt(Box::new(a), Box::new(b))))
+}
+
+// parses intensional difference, ex: (a ~ b)
+pub fn parseDiffInt(input: &str) -> IResult<&str, Term> {
+  let (input, (a, b)) = parseDiff(input, " ~ ")?;
+  Ok((input, Term::DiffInt(Box::new(a), Box::new(b))))
+}
+
+// parses difference with exactly two elements
+fn parseDiff<'a>(input: &'a str, connector: &str) -> IResult<&'a str, (Term, Term)> {
   let (input, _) = tag("(")(input)?;
   let (input, a) = parseSubjOrPred(input, true)?;
-  let (input, _) = tag("&")(input)?;
+  let (input, _) = tag(connector)(input)?;
   let (input, b) = parseSubjOrPred(input, true)?;
   let (input, _) = tag(")")(input)?;
-  Ok((input, Term::ExtInt([&a,&b].iter().map(|v| Box::new((*v).clone())).collect())))
+  Ok((input, (a, b)))
 }
-pub fn parseIntInt2(input: &str) -> IResult<&str, Term> {
+
+// parses compound in brackets with at least two elements separated by the connector, ex: (a&&b&&c)
+fn parseCompound<'a>(input: &'a str, connector: &str) -> IResult<&'a str, Vec<Term>> {
+  let mut subterms = vec![];
   let (input, _) = tag("(")(input)?;
-  let (input, a) = parseSubjOrPred(input, true)?;//parse0(input)?;
-  let (input, _) = tag("|")(input)?;
-  let (input, b) = parseSubjOrPred(input, true)?;//parse0(input)?;
+  let (input, a) = parseSubjOrPred(input, true)?;
+  subterms.push(a);
+  let (input, _) = tag(connector)(input)?;
+  let (mut input, b) = parseSubjOrPred(input, true)?;
+  subterms.push(b);
+
+  loop { // loop for more sub-terms
+    let res0: IResult<&str, &str> = tag(connector)(input);
+    match res0 {
+      Ok((input2, _)) => {
+        input = input2;
+      },
+      Err(_) => {
+        break;
+      },
+    };
+
+    let (input2, subterm) = parseSubjOrPred(input, true)?;
+    input = input2;
+    subterms.push(subterm);
+  }
+
   let (input, _) = tag(")")(input)?;
-  Ok((input, Term::IntInt([&a,&b].iter().map(|v| Box::new((*v).clone())).collect())))
+  Ok((input, subterms))
 }
 
 
@@ -883,54 +1762,25 @@ pub fn parseProd(input: &str) -> IResult<&str, Term> {
   Ok((input, p(&subterms)))
 }
 
-// parse image
-pub fn parseImg0(input: &str) -> IResult<&str, Term> {
-  let (input, _) = tag("(")(input)?;
-  let (input, a) = parseSubjOrPred(input, true)?;
-  let (input, _) = tag(" /1 ")(input)?;
-  let (mut input, b) = parseSubjOrPred(input, true)?;
-  let (input, _) = tag(")")(input)?;
-  Ok((input, Term::Img(Box::new(a.clone()), 0, vec![Box::new(b.clone())])))
-}
-pub fn parseImg1(input: &str) -> IResult<&str, Term> {
+// parse image, ex: (rel /1 b), index is one based
+pub fn parseImg(input: &str) -> IResult<&str, Term> {
   let (input, _) = tag("(")(input)?;
-  let (input, a) = parseSubjOrPred(input, true)?;
-  let (input, _) = tag(" /2 ")(input)?;
-  let (mut input, b) = parseSubjOrPred(input, true)?;
+  let (input, rel) = parseSubjOrPred(input, true)?;
+  let (input, _) = tag(" /")(input)?;
+  let (input, idx) = map_res(digit1, |s: &str| s.parse::<usize>())(input)?;
+  if idx == 0 {
+    return Err(nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Digit)));
+  }
+  let (input, _) = tag(" ")(input)?;
+  let (input, elements) = parseSetElements(input)?;
   let (input, _) = tag(")")(input)?;
-  Ok((input, Term::Img(Box::new(a.clone()), 1, vec![Box::new(b.clone())])))
+  Ok((input, Term::Img(Box::new(rel), idx-1, elements.into_iter().map(|v| Box::new(v)).collect())))
 }
 
-// parses product with two components
-pub fn parseConj2(input: &str) -> IResult<&str, Term> {
-  let (input, _) = tag("(")(input)?;
-  let (input, a) = parseSubjOrPred(input, true)?;
-  let (input, _) = tag("&&")(input)?;
-  let (input, b) = parseSubjOrPred(input, true)?;
-  let (input, _) = tag(")")(input)?;
-  Ok((input, conj(&vec![a, b])))
-}
-pub fn parseConj3(input: &str) -> IResult<&str, Term> {
-  let (input, _) = tag("(")(input)?;
-  let (input, a) = parseSubjOrPred(input, true)?;
-  let (input, _) = tag("&&")(input)?;
-  let (input, b) = parseSubjOrPred(input, true)?;
-  let (input, _) = tag("&&")(input)?;
-  let (input, c) = parseSubjOrPred(input, true)?;
-  let (input, _) = tag(")")(input)?;
-  Ok((input, conj(&vec![a, b, c])))
-}
-pub fn parseConj4(input: &str) -> IResult<&str, Term> {
-  let (input, _) = tag("(")(input)?;
-  let (input, a) = parseSubjOrPred(input, true)?;
-  let (input, _) = tag("&&")(input)?;
-  let (input, b) = parseSubjOrPred(input, true)?;
-  let (input, _) = tag("&&")(input)?;
-  let (input, c) = parseSubjOrPred(input, true)?;
-  let (input, _) = tag("&&")(input)?;
-  let (input, d) = parseSubjOrPred(input, true)?;
-  let (input, _) = tag(")")(input)?;
-  Ok((input, conj(&vec![a, b, c, d])))
+// parses conjunction, ex: (a&&b&&c)
+pub fn parseConj(input: &str) -> IResult<&str, Term> {
+  let (input, elements) = parseCompound(input, "&&")?;
+  Ok((input, conj(&elements)))
 }
 
 pub fn parseStatement(input: &str) -> IResult<&str, Term> {
@@ -943,13 +1793,17 @@ pub fn parseStatement(input: &str) -> IResult<&str, Term> {
   let (input, subj) = parseSubjOrPred(input, true)?;
 
   //let (input, _) = tag(" --> ")(input)?; // TODO< remove spaces >
-  let (input, copula) = parseCopula(input)?;
+  let (input, (copula, isInstance, isProperty)) = parseCopula2(input)?;
 
   //let (input, pred) = alpha2(input)?;
   let (input, pred) = parseSubjOrPred(input, true)?;
   
   let (input, _) = tag(">")(input)?;
 
+  // NAL-2 copulas are sugar for inheritance with sets
+  let subj = if isInstance {Term::SetExt(vec![Box::new(subj)])} else {subj};
+  let pred = if isProperty {Term::SetInt(vec![Box::new(pred)])} else {pred};
+
   Ok((input, s(copula, &subj, &pred)))
 }
 
@@ -31,7 +31,7 @@ impl NarProc::Op for OpNop {
 /// NAL9 operator to execute a sequence of operations and inject a event as input after doing so
 /// 
 /// ex:
-/// `<(a,<{({SELF}*(<{({SELF}*dummy0)} --> ^nop>,<{({SELF}*dummy0)} --> ^nop>)*b)} --> ^nal9_exeAndInject>) =/> g>..`
+/// `<(a,^nal9_exeAndInject((^nop(dummy0),^nop(dummy0)), b)) =/> g>.`
 /// 
 pub struct Op_nal9__exec_and_inject {
 }
@@ -142,9 +142,8 @@ impl NarProc::Op for Op__nlp_rel_0 {
 
                 let memWrite = mem.write();
                 
-                // HACK< belongs into decl memory >
-                let cfg__maxComplexity = 64;
-                let cfg__nConceptBeliefs = 60;
+                let cfg__maxComplexity = memWrite.cfg.maxComplexity;
+                let cfg__nConceptBeliefs = memWrite.cfg.nlpNConceptBeliefs;
 
                 NarWorkingCycle::memAddTask(Arc::clone(&memWrite.shared), &sentence, true, cfg__maxComplexity, cfg__nConceptBeliefs, 1.0, nar.t);
             },
@@ -11,6 +11,7 @@ use std::sync::Arc;
 use crate::Term::*;
 use crate::NarProc;
 use crate::Nar;
+use crate::NarConfig::NarConfigBuilder;
 use crate::NarGoalSystem;
 use crate::NarWorkingCycle::Mem2;
 
@@ -18,8 +19,10 @@ pub fn procChaosEntry() {
     let mut t:i64 = 0; // discrete time
     let maxT:Option<i64> = Some(1100);
 
-    let mut nar:Nar::Nar = Nar::createNar();
-    nar.procNar.cfgNMaxEvidence = 1000; // only allow 5000 beliefs
+    let cfg = NarConfigBuilder::new()
+        .procNMaxEvidence(1000) // only allow 1000 beliefs
+        .build().unwrap();
+    let mut nar:Nar::Nar = Nar::createNarWithConfig(cfg);
 
     nar.procNar.ops.push(Rc::new(Box::new( OpNop {
         selfName: "^L".to_string(),
@@ -31,7 +34,7 @@ pub fn procChaosEntry() {
     
     loop { // reasoner/modification mainloop
         if t % 10 == 0 {
-            Nar::inputN(&mut nar, &"0-1-xc! :|:".to_string()); // add goal
+            Nar::inputN(&mut nar, &"0-1-xc! :|:".to_string()).unwrap(); // add goal
         }
         
         
@@ -2,7 +2,7 @@
 
 use std::io;
 use rand::Rng;
-use rand::rngs::ThreadRng;
+use rand::rngs::StdRng;
 
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -10,6 +10,7 @@ use std::sync::Arc;
 use parking_lot::RwLock;
 
 use crate::Nar;
+use crate::NarConfig::{NarConfigBuilder, createRng, RNG_STREAM_ENV};
 use crate::NarProc;
 use crate::NarGoalSystem;
 use crate::Term::*;
@@ -35,24 +36,23 @@ pub fn run(maxEpochs:i64) {
     let mut losses:i64 = 0;
 
 
-    let mut rng: ThreadRng = rand::thread_rng();
-
-    let mut nar:Nar::Nar = Nar::createNar();
     //nar.procNar.cfg__eviCnt = 10000; // almost axiomatic
-    nar.procNar.goalSystem.write().cfg__enGoalSatisfaction = false; // disable because we want goals to persist
-    
-    // resources
-    nar.procNar.cfg__nConcepts = 500; // 10000;
-    nar.procNar.cfg__nConceptBeliefs = 100; // 1000;
-    nar.procNar.goalSystem.write().nMaxEntries = 1000; // 5000; // give more resources (memory - goals)
-    //nar.procNar.cfg__nGoalDeriverSamples = 100; // give a lot of samples so that it builds the tree fast
+    let cfg = NarConfigBuilder::new()
+        .goalEnSatisfaction(false) // disable because we want goals to persist
 
-    // debugging
-    //nar.procNar.cfgVerbosity = 1; // debug perceptions
-    nar.procNar.cfg__enAnticipation = false; // disable for testing
+        // resources
+        .procNConcepts(500) // 10000;
+        .procNConceptBeliefs(100) // 1000;
+        .goalNMaxEntries(1000) // 5000; // give more resources (memory - goals)
 
+        // debugging
+        //.procVerbosity(1) // debug perceptions
+        .procEnAnticipation(false) // disable for testing
 
-    nar.procNar.cfgEnBabbling = false; // disable by default
+        .procEnBabbling(false) // disable by default
+        .build().unwrap();
+    let mut rng: StdRng = createRng(&cfg, RNG_STREAM_ENV);
+    let mut nar:Nar::Nar = Nar::createNarWithConfig(cfg);
 
 
     let move_:RefCell<Option<i64>> = RefCell::new(None);
@@ -86,7 +86,7 @@ pub fn run(maxEpochs:i64) {
             }
 
             if !found {
-                Nar::inputN(&mut nar, &"w! :|:".to_string()); // add goal
+                Nar::inputN(&mut nar, &"w! :|:".to_string()).unwrap(); // add goal
             }
         }
 
@@ -287,7 +287,7 @@ pub fn run(maxEpochs:i64) {
                 }
 
                 if winner.unwrap() == 'a' { // NARS won!
-                    Nar::inputN(&mut nar, &"w. :|:".to_string()); // add win event
+                    Nar::inputN(&mut nar, &"w. :|:".to_string()).unwrap(); // add win event
 
                     for _iStep in 0..50 { // give reasoner time
                         NarProc::narStep0(&mut nar.procNar);
@@ -10,6 +10,7 @@ use crate::Term::*;
 use crate::Term::convTermToStr;
 use crate::NarProc;
 use crate::Nar;
+use crate::NarConfig::{NarConfig, createRng, RNG_STREAM_ENV};
 use crate::NarGoalSystem;
 use crate::NarWorkingCycle::Mem2;
 
@@ -17,12 +18,18 @@ use crate::EnvPong3;
 
 // /return ratio
 pub fn reasoner1Entry() -> f64 {
-    let mut rng = rand::thread_rng();
+    reasoner1Entry2(NarConfig::default())
+}
+
+/// run pong with a configuration, the environment is seeded by the seed of the configuration
+// /return ratio
+pub fn reasoner1Entry2(cfg:NarConfig) -> f64 {
+    let mut rng = createRng(&cfg, RNG_STREAM_ENV);
 
     let mut t:i64 = 0; // discrete time
     let maxT:Option<i64> = Some(5000);
 
-    let mut nar:Nar::Nar = Nar::createNar();
+    let mut nar:Nar::Nar = Nar::createNarWithConfig(cfg);
     
 
     let envPong:RefCell<EnvPong3::EnvState> = RefCell::new(EnvPong3::makeEnvState());
@@ -42,7 +49,7 @@ pub fn reasoner1Entry() -> f64 {
     
     loop { // reasoner/modification mainloop
         if t % 10 == 0 {
-            Nar::inputN(&mut nar, &"0-1-xc! :|:".to_string()); // add goal
+            Nar::inputN(&mut nar, &"0-1-xc! :|:".to_string()).unwrap(); // add goal
         }
         
         {
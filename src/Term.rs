@@ -1,3 +1,7 @@
+use std::cmp::Ordering;
+
+use crate::TermUtils::decodeOp;
+
 #[derive(PartialEq, Eq, Hash, Copy, Clone)]
 pub enum Copula {
     SIM, // <-> similarity
@@ -24,160 +28,152 @@ pub enum Term {
     ExtInt(Vec<Box<Term>>), // &  extensional intersection
     Par(Vec<Box<Term>>), // &| parallel events
     Neg(Box<Term>), // negation
+    DiffExt(Box<Term>, Box<Term>), // - extensional difference
+    DiffInt(Box<Term>, Box<Term>), // ~ intensional difference
+    Interval(i64), // +N interval between events in a sequence
+}
+
+/// iterator over the direct components of a term, is returned by Term::children()
+pub struct Children<'a> {
+    first:Option<&'a Term>,
+    second:Option<&'a Term>,
+    rest:std::slice::Iter<'a, Box<Term>>,
 }
 
+impl<'a> Iterator for Children<'a> {
+    type Item = &'a Term;
+
+    fn next(&mut self) -> Option<&'a Term> {
+        if let Some(res) = self.first.take() {
+            return Some(res);
+        }
+        if let Some(res) = self.second.take() {
+            return Some(res);
+        }
+        self.rest.next().map(|iv| &**iv)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len:usize = self.first.is_some() as usize + self.second.is_some() as usize + self.rest.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for Children<'a> {}
+
 impl Clone for Term {
     fn clone(&self) -> Term {
-        match &*self {
-            Term::Stmt(copula, subj, pred) => {
-                Term::Stmt(*copula, subj.clone(), pred.clone())
-            }
-            Term::Name(name) => Term::Name(name.clone()),
-            Term::Seq(seq) => {
-                let mut arr = vec![];
-                for i in seq {
-                    arr.push(i.clone());
-                }
-                Term::Seq(arr)
-            },
-            Term::SetInt(set) => {
-                let mut arr = vec![];
-                for i in set {
-                    arr.push(i.clone());
-                }
-                Term::SetInt(arr)
-            }
-            Term::SetExt(set) => {
-                let mut arr = vec![];
-                for i in set {
-                    arr.push(i.clone());
-                }
-                Term::SetExt(arr)
-            },
-            Term::QVar(name) => {
-                Term::QVar(name.clone())
-            },
-            Term::DepVar(name) => {
-                Term::DepVar(name.clone())
-            },
-            Term::IndepVar(name) => {
-                Term::IndepVar(name.clone())
-            },
-            Term::Conj(elements) => {
-                let mut arr = vec![];
-                for i in elements {
-                    arr.push(i.clone());
-                }
-                Term::Conj(arr)
+        self.map_children(|iChild| iChild.clone())
+    }
+}
+
+/// generic traversal of terms
+///
+/// this is the only place which has to know how the compounds store their components,
+/// the helpers below and in other modules are built on top of it
+impl Term {
+    /// returns a iterator over the direct components, ex: subject and predicate of a statement
+    ///
+    /// the relation of a image is the first component. Doesn't allocate
+    pub fn children(&self) -> Children<'_> {
+        let empty:&[Box<Term>] = &[];
+        match self {
+            Term::Stmt(_, subj, pred) | Term::DiffExt(subj, pred) | Term::DiffInt(subj, pred) => Children{first:Some(subj), second:Some(pred), rest:empty.iter()},
+            Term::Seq(elements) | Term::SetInt(elements) | Term::SetExt(elements) | Term::Conj(elements) |
+            Term::Prod(elements) | Term::IntInt(elements) | Term::ExtInt(elements) | Term::Par(elements) => {
+                Children{first:None, second:None, rest:elements.iter()}
             },
-            Term::Prod(elements) => {
-                let mut arr = vec![];
-                for i in elements {
-                    arr.push(i.clone());
-                }
-                Term::Prod(arr)
+            Term::Img(rel, _idx, elements) => Children{first:Some(rel), second:None, rest:elements.iter()},
+            Term::Neg(term) => Children{first:Some(term), second:None, rest:empty.iter()},
+            Term::Name(_) | Term::QVar(_) | Term::DepVar(_) | Term::IndepVar(_) | Term::Interval(_) => Children{first:None, second:None, rest:empty.iter()},
+        }
+    }
+
+    /// builds a term of the same type where every direct component was replaced by the result of f
+    ///
+    /// f is called on the components in the order of children(), returns a copy for atomic terms
+    pub fn map_children<F:FnMut(&Term) -> Term>(&self, mut f:F) -> Term {
+        let mut mapVec = |elements:&[Box<Term>]| -> Vec<Box<Term>> {
+            elements.iter().map(|iv| Box::new(f(iv))).collect()
+        };
+
+        match self {
+            Term::Stmt(copula, subj, pred) => {
+                let subj2 = f(subj);
+                let pred2 = f(pred);
+                Term::Stmt(*copula, Box::new(subj2), Box::new(pred2))
             },
-            Term::Img(rel,idx,others) => {
-                let mut arr = vec![];
-                for i in others {
-                    arr.push(i.clone());
-                }
-                Term::Img(rel.clone(),*idx,arr)
+            Term::Name(name) => Term::Name(name.clone()),
+            Term::Seq(seq) => Term::Seq(mapVec(seq)),
+            Term::SetInt(set) => Term::SetInt(mapVec(set)),
+            Term::SetExt(set) => Term::SetExt(mapVec(set)),
+            Term::QVar(name) => Term::QVar(name.clone()),
+            Term::DepVar(name) => Term::DepVar(name.clone()),
+            Term::IndepVar(name) => Term::IndepVar(name.clone()),
+            Term::Conj(elements) => Term::Conj(mapVec(elements)),
+            Term::Prod(elements) => Term::Prod(mapVec(elements)),
+            Term::Img(rel, idx, elements) => {
+                let rel2 = f(rel);
+                Term::Img(Box::new(rel2), *idx, elements.iter().map(|iv| Box::new(f(iv))).collect())
             },
-            Term::IntInt(set) => {
-                let mut arr = vec![];
-                for i in set {
-                    arr.push(i.clone());
-                }
-                Term::IntInt(arr)
+            Term::IntInt(set) => Term::IntInt(mapVec(set)),
+            Term::ExtInt(set) => Term::ExtInt(mapVec(set)),
+            Term::Par(elements) => Term::Par(mapVec(elements)),
+            Term::Neg(term) => Term::Neg(Box::new(f(term))),
+            Term::DiffExt(a, b) => {
+                let a2 = f(a);
+                let b2 = f(b);
+                Term::DiffExt(Box::new(a2), Box::new(b2))
             },
-            Term::ExtInt(set) => {
-                let mut arr = vec![];
-                for i in set {
-                    arr.push(i.clone());
-                }
-                Term::ExtInt(arr)
-            }
-            Term::Par(elements) => {
-                let mut arr = vec![];
-                for i in elements {
-                    arr.push(i.clone());
-                }
-                Term::Par(arr)
-            },
-            Term::Neg(term) => {
-                Term::Neg(term.clone())
+            Term::DiffInt(a, b) => {
+                let a2 = f(a);
+                let b2 = f(b);
+                Term::DiffInt(Box::new(a2), Box::new(b2))
             },
+            Term::Interval(dt) => Term::Interval(*dt),
         }
     }
-}
 
-// helper
-fn retSubterms2(t:&Term, res:&mut Vec<Term>) {
-    res.push(t.clone());
-    
-    match t {
-        Term::Stmt(_, subj, pred) => {
-            retSubterms2(&subj, res);
-            retSubterms2(&pred, res);
+    /// folds over the term and all subterms in pre-order
+    pub fn fold<A, F:FnMut(A, &Term) -> A>(&self, init:A, f:&mut F) -> A {
+        let mut acc:A = f(init, self);
+        for iChild in self.children() {
+            acc = iChild.fold(acc, f);
         }
-        Term::Seq(seq) => {
-            for i in seq {
-                retSubterms2(&i, res);
-            }
-        },
-        Term::SetInt(set) => {
-            for i in set {
-                retSubterms2(&i, res);
-            }
+        acc
+    }
+
+    /// returns the subterm at the path, every entry of the path is a index into children()
+    ///
+    /// ex: the path [0, 1] of <(a * b) --> c> addresses b
+    pub fn at(&self, path:&[usize]) -> Option<&Term> {
+        match path.split_first() {
+            Some((idx, rest)) => self.children().nth(*idx)?.at(rest),
+            None => Some(self),
         }
-        Term::SetExt(set) => {
-            for i in set {
-                retSubterms2(&i, res);
-            }
-        },
-        Term::Conj(elements) => {
-            for i in elements {
-                retSubterms2(&i, res);
-            }
-        },
-        Term::Prod(elements) => {
-            for i in elements {
-                retSubterms2(&i, res);
-            }
-        },
-        Term::Img(rel,_idx,elements) => {
-            for i in elements {
-                retSubterms2(&i, res);
-            }
-            retSubterms2(&rel, res);
-        },
-        Term::IntInt(set) => {
-            for i in set {
-                retSubterms2(&i, res);
-            }
-        },
-        Term::ExtInt(set) => {
-            for i in set {
-                retSubterms2(&i, res);
-            }
-        },
-        Term::Par(elements) => {
-            for i in elements {
-                retSubterms2(&i, res);
-            }
-        },
-        Term::Neg(term) => {
-            retSubterms2(&term, res);
-        },
-        _=>{}, // no special handling necessary for "terminal" ones
+    }
+
+    /// returns a copy where the subterm at the path is replaced with the term
+    ///
+    /// returns None if the path doesn't exist
+    pub fn replace_at(&self, path:&[usize], with:&Term) -> Option<Term> {
+        let (idx, rest) = match path.split_first() {
+            Some(v) => v,
+            None => {return Some(with.clone());}
+        };
+        let replaced:Term = self.children().nth(*idx)?.replace_at(rest, with)?;
+
+        let mut childIdx = 0;
+        Some(self.map_children(|iChild| {
+            let res = if childIdx == *idx {replaced.clone()} else {iChild.clone()};
+            childIdx += 1;
+            res
+        }))
     }
 }
 
 pub fn retSubterms(t:&Term) -> Vec<Term> {
-    let mut res=vec![];
-    retSubterms2(&t,&mut res);
-    res
+    t.fold(vec![], &mut |mut res:Vec<Term>, iv| {res.push(iv.clone()); res})
 }
 
 pub fn retUniqueSubterms(t:&Term)->Vec<Term> {
@@ -200,346 +196,220 @@ pub fn retUniqueSubterms(t:&Term)->Vec<Term> {
 
 
 pub fn calcComplexity(t:&Term) -> u64 {
+    let childrenComplexity:u64 = t.children().map(|iv| calcComplexity(iv)).sum();
     match t {
-        Term::Stmt(_, subj, pred) => {
-            1 + calcComplexity(subj) + calcComplexity(pred)
-        }
-        Term::Name(_) => 1,
-        Term::Seq(seq) => {
-            let mut c = 0;
-            for i in seq {
-                c+=calcComplexity(i);
-            }
-            c
-        },
-        Term::SetInt(set) => {
-            let mut c = 0;
-            for i in set {
-                c+=calcComplexity(i);
-            }
-            c
-        }
-        Term::SetExt(set) => {
-            let mut c = 0;
-            for i in set {
-                c+=calcComplexity(i);
-            }
-            c
-        },
-        Term::QVar(_) => {
-            1
-        },
-        Term::DepVar(_) => {
-            1
-        },
-        Term::IndepVar(_) => {
-            1
-        },
-        Term::Conj(elements) => {
-            let mut c = 0;
-            for i in elements {
-                c+=calcComplexity(i);
-            }
-            c
-        },
-        Term::Prod(elements) => {
-            let mut c = 0;
-            for i in elements {
-                c+=calcComplexity(i);
-            }
-            c
-        },
-        Term::Img(rel,_idx,elements) => {
-            let mut c = 0;
-            for i in elements {
-                c+=calcComplexity(i);
-            }
-            c+=calcComplexity(rel);
-            c
-        },
-        Term::IntInt(set) => {
-            let mut c = 0;
-            for i in set {
-                c+=calcComplexity(i);
-            }
-            c
-        },
-        Term::ExtInt(set) => {
-            let mut c = 0;
-            for i in set {
-                c+=calcComplexity(i);
-            }
-            c
-        },
-        Term::Par(elements) => {
-            let mut c = 0;
-            for i in elements {
-                c+=calcComplexity(i);
-            }
-            c
-        },
-        Term::Neg(term) => {
-            1 + calcComplexity(term)
-        },
+        Term::Stmt(_, _, _) | Term::Neg(_) => 1 + childrenComplexity,
+        _ if childrenComplexity == 0 => 1, // atomic term
+        _ => childrenComplexity,
     }
 }
 
+/// converts term to string, op calls are printed in the short form ^name(args...)
 pub fn convTermToStr(t:&Term) -> String {
+    convTermToStr2(t, true)
+}
+
+/// converts term to string
+///
+/// /param enShortOps prints op calls in the short form ^name(args...) instead of the long form <{({SELF} * args...)} --> ^name>
+// PUBLICAPI
+pub fn convTermToStr2(t:&Term, enShortOps:bool) -> String {
+    if enShortOps {
+        if let Some(opStr) = convOpCallToStr(t) {
+            return opStr;
+        }
+    }
+
+    let inner:Vec<String> = t.children().map(|iv| convTermToStr2(iv, enShortOps)).collect();
     match t {
-        Term::Stmt(Copula, subj, pred) => {
-            let subjStr = convTermToStr(subj);
-            let predStr = convTermToStr(pred);
+        Term::Stmt(Copula, _, _) => {
             let copStr = match Copula {Copula::SIM=>{"<->"},Copula::INH=>{"-->"},Copula::PREDIMPL=>"=/>",Copula::IMPL=>{"==>"},Copula::EQUIV=>{"<=>"}};
-            format!("<{} {} {}>", subjStr, copStr, predStr)
+            format!("<{} {} {}>", inner[0], copStr, inner[1])
         }
         Term::Name(name) => name.to_string(),
-        Term::Seq(seq) => {
-            let mut inner = convTermToStr(&seq[0]);
-            for i in 1..seq.len() {
-                inner = format!("{} , {}", inner, convTermToStr(&seq[i]));
-            }
-            format!("( {} )", inner)
-        },
-        Term::SetInt(set) => {
-            let mut inner = convTermToStr(&set[0]);
-            for i in 1..set.len() {
-                inner = format!("{} {}", inner, convTermToStr(&set[i]));
-            }
-            format!("[{}]", inner)
-        },
-        Term::SetExt(set) => {
-            let mut inner = convTermToStr(&set[0]);
-            for i in 1..set.len() {
-                inner = format!("{} {}", inner, convTermToStr(&set[i]));
-            }
-            format!("{{{}}}", inner)
-        },
-        Term::QVar(name) => {
-            format!("?{}", name)
-        },
-        Term::DepVar(name) => {
-            format!("#{}", name)
-        },
-        Term::IndepVar(name) => {
-            format!("${}", name)
-        },
-        Term::Conj(elements) => {
-            let mut inner = convTermToStr(&elements[0]);
-            for i in 1..elements.len() {
-                inner = format!("{} && {}", inner, convTermToStr(&elements[i]));
-            }
-            format!("( {} )", inner)
-        },
-        Term::Prod(elements) => {
-            let mut inner = convTermToStr(&elements[0]);
-            for i in 1..elements.len() {
-                inner = format!("{} * {}", inner, convTermToStr(&elements[i]));
-            }
-            format!("( {} )", inner)
-        },
-        Term::Img(rel,idx,elements) => {
-            let mut inner = convTermToStr(&elements[0]);
-            for i in 1..elements.len() {
-                inner = format!("{} {}", inner, convTermToStr(&elements[i]));
-            }
-            format!("( {} /{} {} )", convTermToStr(&rel), idx+1, inner)
-        },
-        Term::IntInt(elements) => {
-            let mut inner = convTermToStr(&elements[0]);
-            for i in 1..elements.len() {
-                inner = format!("{} | {}", inner, convTermToStr(&elements[i]));
-            }
-            format!("( {} )", inner)
-        },
-        Term::ExtInt(elements) => {
-            let mut inner = convTermToStr(&elements[0]);
-            for i in 1..elements.len() {
-                inner = format!("{} & {}", inner, convTermToStr(&elements[i]));
-            }
-            format!("( {} )", inner)
-        },
-        Term::Par(seq) => {
-            let mut inner = convTermToStr(&seq[0]);
-            for i in 1..seq.len() {
-                inner = format!("{} ; {}", inner, convTermToStr(&seq[i]));
-            }
-            format!("( {} )", inner)
-        },
-        Term::Neg(term) => {
-            let inner = convTermToStr(term);
-            format!("(! {} )", inner)
-        },
+        Term::Seq(_) => format!("( {} )", inner.join(" , ")),
+        Term::SetInt(_) => format!("[{}]", inner.join(" ")),
+        Term::SetExt(_) => format!("{{{}}}", inner.join(" ")),
+        Term::QVar(name) => format!("?{}", name),
+        Term::DepVar(name) => format!("#{}", name),
+        Term::IndepVar(name) => format!("${}", name),
+        Term::Conj(_) => format!("( {} )", inner.join(" && ")),
+        Term::Prod(_) => format!("( {} )", inner.join(" * ")),
+        Term::Img(_, idx, _) => format!("( {} /{} {} )", inner[0], idx+1, inner[1..].join(" ")),
+        Term::IntInt(_) => format!("( {} )", inner.join(" | ")),
+        Term::ExtInt(_) => format!("( {} )", inner.join(" & ")),
+        Term::Par(_) => format!("( {} )", inner.join(" ; ")),
+        Term::Neg(_) => format!("(! {} )", inner[0]),
+        Term::DiffExt(_, _) => format!("( {} - {} )", inner[0], inner[1]),
+        Term::DiffInt(_, _) => format!("( {} ~ {} )", inner[0], inner[1]),
+        Term::Interval(dt) => format!("+{}", dt),
     }
 }
 
 
-pub fn checkEqTerm(a:&Term, b:&Term) -> bool {
-    match a {
-        Term::Stmt(copulaa, subja, preda) => {
-            match b {
-                Term::Stmt(copulab, subjb, predb) => copulaa == copulab && checkEqTerm(&subja, &subjb) && checkEqTerm(&preda, &predb),
-                _ => false
+/// converts op call of the form <{({SELF} * args...)} --> ^name> to the short form ^name(args...)
+/// returns None if the term isn't a op call
+fn convOpCallToStr(t:&Term) -> Option<String> {
+    let (args, name) = decodeOp(t)?;
+    let isSelf = match &args[0] {
+        Term::SetExt(set) if set.len() == 1 => checkEqTerm(&set[0], &Term::Name("SELF".to_string())),
+        _ => false
+    };
+    if !isSelf || !name.starts_with('^') {
+        return None;
+    }
+    let argsAsStr:Vec<String> = args[1..].iter().map(|iArg| convTermToStr2(iArg, true)).collect();
+    Some(format!("{}({})", name, argsAsStr.join(", ")))
+}
+
+/// is the compound commutative, the order of the components doesn't matter
+pub fn isCommutative(t:&Term) -> bool {
+    match t {
+        Term::SetInt(_) | Term::SetExt(_) | Term::Conj(_) | Term::IntInt(_) | Term::ExtInt(_) | Term::Par(_) => true,
+        _ => false
+    }
+}
+
+/// brings the term into the canonical form
+///
+/// components of commutative compounds are sorted and deduplicated,
+/// nested conjunctions, intersections and parallel events are flattened, ex: ( a && ( c && b ) ) becomes ( a && b && c )
+pub fn normalize(t:&Term) -> Term {
+    // helper to normalize the components of a commutative compound
+    // /param isSameType returns the components of a nested compound of the same type, is used to flatten
+    fn normalizeComponents(elements:&Vec<Box<Term>>, isSameType:&dyn Fn(&Term) -> Option<Vec<Box<Term>>>) -> Vec<Box<Term>> {
+        let mut res:Vec<Box<Term>> = vec![];
+        for iElement in elements {
+            let normalized:Term = normalize(iElement);
+            match isSameType(&normalized) {
+                Some(nested) => {res.extend(nested);}, // flatten
+                None => {res.push(Box::new(normalized));}
             }
         }
-        Term::Name(namea) => {
-            match b {
-                Term::Name(nameb) => namea == nameb,
-                _ => false
-            }
-        },
-        Term::Seq(seqa) => {
-            match b {
-                Term::Seq(seqb) => {
-                    if seqa.len() == seqb.len() {
-                        for idx in 0..seqa.len() {
-                            if !checkEqTerm(&seqa[idx], &seqb[idx]) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
-            }
-        },
-        Term::SetInt(seta) => {
-            match b {
-                Term::SetInt(setb) => {
-                    if seta.len() == setb.len() {
-                        for idx in 0..seta.len() {
-                            if !checkEqTerm(&seta[idx], &setb[idx]) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
-            }
-        },
-        Term::SetExt(seta) => {
-            match b {
-                Term::SetExt(setb) => {
-                    if seta.len() == setb.len() {
-                        for idx in 0..seta.len() {
-                            if !checkEqTerm(&seta[idx], &setb[idx]) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
-            }
-        },
-        Term::QVar(namea) => {
-            match b {
-                Term::QVar(nameb) => namea == nameb,
-                _ => false
-            }
-        },
-        Term::DepVar(namea) => {
-            match b {
-                Term::DepVar(nameb) => namea == nameb,
-                _ => false
-            }
-        },
-        Term::IndepVar(namea) => {
-            match b {
-                Term::IndepVar(nameb) => namea == nameb,
-                _ => false
-            }
-        },
-        Term::Conj(elementsa) => {
-            match b {
-                Term::Conj(elementsb) => {
-                    if elementsa.len() == elementsb.len() {
-                        for idx in 0..elementsa.len() {
-                            if !checkEqTerm(&elementsa[idx], &elementsb[idx]) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
-            }
-        },
-        Term::Prod(elementsa) => {
-            match b {
-                Term::Prod(elementsb) => {
-                    if elementsa.len() == elementsb.len() {
-                        for idx in 0..elementsa.len() {
-                            if !checkEqTerm(&elementsa[idx], &elementsb[idx]) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
-            }
-        },
-        Term::Img(rela,idxa,elementsa) => {
-            match b {
-                Term::Img(relb,idxb,elementsb) if idxa==idxb => {
-                    if !checkEqTerm(&rela, &relb) {return false};
-
-                    if elementsa.len() == elementsb.len() {
-                        for idx in 0..elementsa.len() {
-                            if !checkEqTerm(&elementsa[idx], &elementsb[idx]) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
-            }
-        },
-        Term::IntInt(seta) => {
-            match b {
-                Term::IntInt(setb) => {
-                    if seta.len() == setb.len() {
-                        for idx in 0..seta.len() {
-                            if !checkEqTerm(&seta[idx], &setb[idx]) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
-            }
-        },
-        Term::ExtInt(seta) => {
-            match b {
-                Term::ExtInt(setb) => {
-                    if seta.len() == setb.len() {
-                        for idx in 0..seta.len() {
-                            if !checkEqTerm(&seta[idx], &setb[idx]) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
-            }
-        },
-        Term::Par(elementsa) => {
-            match b {
-                Term::Par(elementsb) => {
-                    if elementsa.len() == elementsb.len() {
-                        for idx in 0..elementsa.len() {
-                            if !checkEqTerm(&elementsa[idx], &elementsb[idx]) {return false};
-                        }
-                        true
-                    }
-                    else {false}
-                },
-                _ => false
-            }
-        },
-        Term::Neg(terma) => {
-            match b {
-                Term::Neg(termb) => checkEqTerm(&terma, &termb),
-                _ => false
-            }
-        },
+        res.sort_by(|a, b| cmpTerm(a, b));
+        res.dedup_by(|a, b| checkEqTerm(a, b));
+        res
+    }
+
+    match t {
+        Term::SetInt(set) => Term::SetInt(normalizeComponents(set, &|_| None)),
+        Term::SetExt(set) => Term::SetExt(normalizeComponents(set, &|_| None)),
+        Term::Conj(elements) => Term::Conj(normalizeComponents(elements, &|iv| match iv {Term::Conj(nested) => Some(nested.clone()), _ => None})),
+        Term::IntInt(set) => Term::IntInt(normalizeComponents(set, &|iv| match iv {Term::IntInt(nested) => Some(nested.clone()), _ => None})),
+        Term::ExtInt(set) => Term::ExtInt(normalizeComponents(set, &|iv| match iv {Term::ExtInt(nested) => Some(nested.clone()), _ => None})),
+        Term::Par(elements) => Term::Par(normalizeComponents(elements, &|iv| match iv {Term::Par(nested) => Some(nested.clone()), _ => None})),
+        _ => t.map_children(normalize), // order of the components matters
+    }
+}
+
+/// does the term contain any variable?
+pub fn checkHasVar(t:&Term) -> bool {
+    t.fold(false, &mut |acc, iv| acc || matches!(iv, Term::QVar(_) | Term::DepVar(_) | Term::IndepVar(_)))
+}
+
+/// renames the variables to $1, #2, ?3 and so on in the order of the first occurrence
+///
+/// alpha-equivalent terms like <($x*a) --> r> and <($y*a) --> r> have the same standardized form
+pub fn standardizeVars(t:&Term) -> Term {
+    // helper which does the renaming
+    // /param renamed maps the original variables to the renamed ones
+    fn helper(t:&Term, renamed:&mut Vec<(Term, Term)>) -> Term {
+        match t {
+            Term::QVar(_) | Term::DepVar(_) | Term::IndepVar(_) => {
+                if let Some((_, with)) = renamed.iter().find(|(var, _)| checkEqTerm(var, t)) {
+                    return with.clone();
+                }
+                let name:String = format!("{}", renamed.len()+1);
+                let with:Term = match t {
+                    Term::QVar(_) => Term::QVar(name),
+                    Term::DepVar(_) => Term::DepVar(name),
+                    _ => Term::IndepVar(name),
+                };
+                renamed.push((t.clone(), with.clone()));
+                with
+            },
+            _ => t.map_children(|iChild| helper(iChild, renamed)),
+        }
+    }
+
+    helper(t, &mut vec![])
+}
+
+/// are the terms equal up to the renaming of variables?
+///
+/// the standardized terms are normalized again, because the renaming can change the order of the components of commutative compounds
+pub fn alphaEq(a:&Term, b:&Term) -> bool {
+    if checkEqTerm(a, b) {
+        return true; // fast path
     }
+    checkHasVar(a) && checkHasVar(b) && checkEqTerm(&normalize(&standardizeVars(&normalize(a))), &normalize(&standardizeVars(&normalize(b))))
+}
+
+/// total order of terms, is used to sort the components of commutative compounds
+///
+/// the names of variables are only compared if the terms are equal otherwise,
+/// so the order of alpha-equivalent components doesn't depend on how the variables are named
+pub fn cmpTerm(a:&Term, b:&Term) -> Ordering {
+    cmpTerm2(a, b, false).then_with(|| cmpTerm2(a, b, true))
+}
+
+/// order of terms
+///
+/// /param enVarNames are the names of variables of the same type compared? variables of the same type are equal if not
+pub fn cmpTerm2(a:&Term, b:&Term, enVarNames:bool) -> Ordering {
+    // helper to compute the rank of the type of the term
+    fn retRank(t:&Term) -> u8 {
+        match t {
+            Term::Stmt(_, _, _) => 0, Term::Name(_) => 1, Term::Seq(_) => 2, Term::SetInt(_) => 3, Term::SetExt(_) => 4,
+            Term::QVar(_) => 5, Term::DepVar(_) => 6, Term::IndepVar(_) => 7, Term::Conj(_) => 8, Term::Prod(_) => 9,
+            Term::Img(_, _, _) => 10, Term::IntInt(_) => 11, Term::ExtInt(_) => 12, Term::Par(_) => 13, Term::Neg(_) => 14,
+            Term::Interval(_) => 15, Term::DiffExt(_, _) => 16, Term::DiffInt(_, _) => 17,
+        }
+    }
+
+    // helper to compare the values which are not stored in the components, terms must have the same rank
+    fn cmpNode(a:&Term, b:&Term, enVarNames:bool) -> Ordering {
+        match (a, b) {
+            (Term::Stmt(copulaa, _, _), Term::Stmt(copulab, _, _)) => (*copulaa as u8).cmp(&(*copulab as u8)),
+            (Term::Name(namea), Term::Name(nameb)) => namea.cmp(nameb),
+            (Term::QVar(namea), Term::QVar(nameb)) | (Term::DepVar(namea), Term::DepVar(nameb)) | (Term::IndepVar(namea), Term::IndepVar(nameb)) => {
+                if enVarNames {namea.cmp(nameb)} else {Ordering::Equal}
+            },
+            (Term::Img(_, idxa, _), Term::Img(_, idxb, _)) => idxa.cmp(idxb),
+            (Term::Interval(dta), Term::Interval(dtb)) => dta.cmp(dtb),
+            _ => Ordering::Equal,
+        }
+    }
+
+    let res = retRank(a).cmp(&retRank(b)).then_with(|| cmpNode(a, b, enVarNames));
+    if res != Ordering::Equal {
+        return res;
+    }
+    for (ia, ib) in a.children().zip(b.children()) {
+        let res = cmpTerm2(ia, ib, enVarNames);
+        if res != Ordering::Equal {
+            return res;
+        }
+    }
+    a.children().len().cmp(&b.children().len())
+}
+
+/// are the terms of the same type with the same values which are not stored in the components?
+///
+/// ex: copula of statements, name of names and variables, index of images
+pub fn checkEqNode(a:&Term, b:&Term) -> bool {
+    match (a, b) {
+        (Term::Stmt(copulaa, _, _), Term::Stmt(copulab, _, _)) => copulaa == copulab,
+        (Term::Name(namea), Term::Name(nameb)) | (Term::QVar(namea), Term::QVar(nameb)) |
+        (Term::DepVar(namea), Term::DepVar(nameb)) | (Term::IndepVar(namea), Term::IndepVar(nameb)) => namea == nameb,
+        (Term::Img(_, idxa, _), Term::Img(_, idxb, _)) => idxa == idxb,
+        (Term::Interval(dta), Term::Interval(dtb)) => dta == dtb,
+        _ => std::mem::discriminant(a) == std::mem::discriminant(b),
+    }
+}
+
+pub fn checkEqTerm(a:&Term, b:&Term) -> bool {
+    checkEqNode(a, b) && a.children().len() == b.children().len() && a.children().zip(b.children()).all(|(ia, ib)| checkEqTerm(ia, ib))
 }
 
 
@@ -549,87 +419,51 @@ pub fn count_occurence_subterms(t: &Term, searched: &Term) -> i64 {
         return 1;
     }
 
-    return match t {
-        Term::Stmt(_, subja, preda) => {
-            count_occurence_subterms(subja, searched) + count_occurence_subterms(preda, searched)
-        }
-        Term::Name(_) => {
-            0
-        },
-        Term::Seq(seqa) => {
-            let mut n = 0;
-            for idx in 0..seqa.len() {
-                n += count_occurence_subterms(&seqa[idx], searched);
-            }
-            n
-        },
-        Term::SetInt(seta) => {
-            let mut n = 0;
-            for idx in 0..seta.len() {
-                n += count_occurence_subterms(&seta[idx], searched);
-            }
-            n
-        },
-        Term::SetExt(seta) => {
-            let mut n = 0;
-            for idx in 0..seta.len() {
-                n += count_occurence_subterms(&seta[idx], searched);
-            }
-            n
-        },
-        Term::QVar(_) => {
-            0
-        },
-        Term::DepVar(_) => {
-            0
-        },
-        Term::IndepVar(_) => {
-            0
-        },
-        Term::Conj(elementsa) => {
-            let mut n = 0;
-            for idx in 0..elementsa.len() {
-                n += count_occurence_subterms(&elementsa[idx], searched);
-            }
-            n
-        },
-        Term::Prod(elementsa) => {
-            let mut n = 0;
-            for idx in 0..elementsa.len() {
-                n += count_occurence_subterms(&elementsa[idx], searched);
-            }
-            n
-        },
-        Term::Img(rela,idxa,elementsa) => {
-            let mut n = 0;
-            for idx in 0..elementsa.len() {
-                n += count_occurence_subterms(&elementsa[idx], searched);
-            }
-            n
-        },
-        Term::IntInt(seta) => {
-            let mut n = 0;
-            for idx in 0..seta.len() {
-                n += count_occurence_subterms(&seta[idx], searched);
-            }
-            n
-        },
-        Term::ExtInt(seta) => {
-            let mut n = 0;
-            for idx in 0..seta.len() {
-                n += count_occurence_subterms(&seta[idx], searched);
-            }
-            n
-        },
-        Term::Par(elementsa) => {
-            let mut n = 0;
-            for idx in 0..elementsa.len() {
-                n += count_occurence_subterms(&elementsa[idx], searched);
-            }
-            n
-        },
-        Term::Neg(terma) => {
-            count_occurence_subterms(terma, searched)
-        },
+    t.children().map(|iv| count_occurence_subterms(iv, searched)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parseTerm(narsese:&str) -> Term {
+        crate::NarseseParser::parseNarsese(&narsese.to_string()).ok().unwrap().0
+    }
+
+    // children() must return the components in order, the relation of the image first
+    #[test]
+    pub fn children_a() {
+        let term:Term = parseTerm("<(a * b) --> ( c /2 d )>.");
+        assert_eq!(term.children().len(), 2);
+        let imgChildren:Vec<String> = term.at(&[1]).unwrap().children().map(|iv| convTermToStr(iv)).collect();
+        assert_eq!(imgChildren, vec!["c", "d"]);
+        assert_eq!(parseTerm("a.").children().len(), 0);
+        assert_eq!(parseTerm("<(!a) --> b>.").at(&[0]).unwrap().children().len(), 1);
+    }
+
+    // positional paths must address the children in the order of children()
+    #[test]
+    pub fn termTraversal() {
+        let term:Term = parseTerm("<(a * b) --> ( c /2 d )>.");
+        assert_eq!(convTermToStr(term.at(&[0, 1]).unwrap()), "b");
+        assert_eq!(convTermToStr(term.at(&[1, 0]).unwrap()), "c"); // relation of the image is the first child
+        assert_eq!(term.at(&[0, 2]).is_none(), true);
+        assert_eq!(convTermToStr(&term.replace_at(&[0, 1], &Term::Name("x".to_string())).unwrap()), "<( a * x ) --> ( c /2 d )>");
+        assert_eq!(term.fold(0, &mut |acc, _| acc + 1), 7);
+    }
+
+    // equality and order must look at the type, the values of the term and all components
+    #[test]
+    pub fn eqAndCmp() {
+        assert_eq!(checkEqTerm(&parseTerm("<(a * b) --> c>."), &parseTerm("<(a * b) --> c>.")), true);
+        assert_eq!(checkEqTerm(&parseTerm("<(a * b) --> c>."), &parseTerm("<(a * b) <-> c>.")), false);
+        assert_eq!(checkEqTerm(&parseTerm("<(a * b) --> c>."), &parseTerm("<(a * b * c) --> c>.")), false);
+        assert_eq!(checkEqTerm(&parseTerm("<(r /1 a) --> c>."), &parseTerm("<(r /2 a) --> c>.")), false);
+        assert_eq!(checkEqTerm(&parseTerm("<$x --> c>."), &parseTerm("<#x --> c>.")), false);
+
+        assert_eq!(cmpTerm(&parseTerm("a."), &parseTerm("b.")), Ordering::Less);
+        assert_eq!(cmpTerm(&parseTerm("<(a * b) --> c>."), &parseTerm("<(a * b * c) --> c>.")), Ordering::Less);
+        assert_eq!(cmpTerm(&parseTerm("<(a * b) --> c>."), &parseTerm("<(a * b) --> c>.")), Ordering::Equal);
+        assert_eq!(cmpTerm2(&parseTerm("<$x --> c>."), &parseTerm("<$y --> c>."), false), Ordering::Equal);
     }
 }
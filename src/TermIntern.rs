@@ -0,0 +1,181 @@
+//! interning of terms
+//!
+//! a TermId shares the term by Arc and caches the hash and the complexity, it is used for the keys of the concepts and of the lookup of tasks by term.
+//! there is no global table (and no global lock), every map interns its keys itself under the lock which protects the map:
+//! a new key is only created if the map has no entry of the term, so equal terms of a map share one allocation.
+//! lookups take a borrowed &Term and don't allocate, see retByTerm()
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::Term::{Term, calcComplexity};
+
+/// interned term with cached values
+pub struct InternedTerm {
+    pub term:Term,
+    hash:u64,
+    complexity:u64,
+}
+
+/// handle to a interned term
+///
+/// is cheap to clone and to hash. Dereferences to the Term so it can be used where a &Term is expected
+// PUBLICAPI
+#[derive(Clone)]
+pub struct TermId(Arc<InternedTerm>);
+
+impl TermId {
+    /// creates a new id, use internIn() to reuse the key of a map
+    pub fn new(t:&Term) -> TermId {
+        TermId(Arc::new(InternedTerm{term:t.clone(), hash:calcHash(t), complexity:calcComplexity(t)}))
+    }
+
+    pub fn term(&self) -> &Term {
+        &self.0.term
+    }
+
+    /// cached complexity of the term
+    pub fn complexity(&self) -> u64 {
+        self.0.complexity
+    }
+
+    /// do both ids share the allocation?
+    pub fn ptrEq(&self, other:&TermId) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Deref for TermId {
+    type Target = Term;
+
+    fn deref(&self) -> &Term {
+        &self.0.term
+    }
+}
+
+impl PartialEq for TermId {
+    fn eq(&self, other:&TermId) -> bool {
+        self.ptrEq(other) || (self.0.hash == other.0.hash && self.0.term == other.0.term) // ids of different maps don't share the allocation
+    }
+}
+
+impl Eq for TermId {}
+
+impl Hash for TermId {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.0.hash);
+    }
+}
+
+/// key of a map of TermId's, is implemented for TermId and for a borrowed term with the precomputed hash
+///
+/// IMPL< the map is looked up with &dyn TermKey, which TermId can be borrowed as, so the lookup by &Term doesn't have to build a TermId >
+pub trait TermKey {
+    fn retTerm(&self) -> &Term;
+    fn retHash(&self) -> u64;
+}
+
+impl TermKey for TermId {
+    fn retTerm(&self) -> &Term {
+        &self.0.term
+    }
+
+    fn retHash(&self) -> u64 {
+        self.0.hash
+    }
+}
+
+/// borrowed term with the hash, is used for lookups
+struct TermRef<'a> {
+    term:&'a Term,
+    hash:u64,
+}
+
+impl<'a> TermKey for TermRef<'a> {
+    fn retTerm(&self) -> &Term {
+        self.term
+    }
+
+    fn retHash(&self) -> u64 {
+        self.hash
+    }
+}
+
+impl<'a> Borrow<dyn TermKey + 'a> for TermId {
+    fn borrow(&self) -> &(dyn TermKey + 'a) {
+        self
+    }
+}
+
+// must hash and compare the same way as TermId
+impl Hash for dyn TermKey + '_ {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.retHash());
+    }
+}
+
+impl PartialEq for dyn TermKey + '_ {
+    fn eq(&self, other:&(dyn TermKey + '_)) -> bool {
+        self.retHash() == other.retHash() && self.retTerm() == other.retTerm()
+    }
+}
+
+impl Eq for dyn TermKey + '_ {}
+
+fn calcHash(t:&Term) -> u64 {
+    let mut hasher = DefaultHasher::new(); // hasher without random state, so the hash is the same in every run
+    t.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// returns the value of the term
+// PUBLICAPI
+pub fn retByTerm<'a, V, S:BuildHasher>(map:&'a HashMap<TermId, V, S>, t:&Term) -> Option<&'a V> {
+    map.get(&TermRef{term:t, hash:calcHash(t)} as &dyn TermKey)
+}
+
+/// returns the value of the term for modification
+// PUBLICAPI
+pub fn retByTermMut<'a, V, S:BuildHasher>(map:&'a mut HashMap<TermId, V, S>, t:&Term) -> Option<&'a mut V> {
+    map.get_mut(&TermRef{term:t, hash:calcHash(t)} as &dyn TermKey)
+}
+
+/// returns the key of the term in the map, returns a new id if the map has no entry of the term
+// PUBLICAPI
+pub fn internIn<V, S:BuildHasher>(map:&HashMap<TermId, V, S>, t:&Term) -> TermId {
+    match map.get_key_value(&TermRef{term:t, hash:calcHash(t)} as &dyn TermKey) {
+        Some((key, _)) => key.clone(),
+        None => TermId::new(t),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Term::{Copula, checkEqTerm};
+
+    // equal terms must be found by the borrowed term, new keys are only created for terms which aren't in the map
+    #[test]
+    pub fn internTerm() {
+        let term = Term::Stmt(Copula::INH, Box::new(Term::Name("a".to_string())), Box::new(Term::Name("b".to_string())));
+        let id0 = TermId::new(&term);
+        let id1 = TermId::new(&term.clone());
+        assert_eq!(id0 == id1, true); // equal even without sharing the allocation
+        assert_eq!(id0.complexity(), 3);
+        assert_eq!(checkEqTerm(&id1, &term), true);
+
+        let mut map:HashMap<TermId, i64> = HashMap::new();
+        map.insert(id0.clone(), 5);
+        assert_eq!(retByTerm(&map, &term), Some(&5));
+        *retByTermMut(&mut map, &term).unwrap() += 1;
+        assert_eq!(map.get(&id1), Some(&6));
+        assert_eq!(retByTerm(&map, &Term::Name("a".to_string())), None);
+
+        assert_eq!(internIn(&map, &term).ptrEq(&id0), true);
+        assert_eq!(internIn(&map, &term).ptrEq(&id1), false);
+    }
+}
@@ -35,6 +35,28 @@ pub fn ind(a:&Tv,b:&Tv)->Tv {
     abd(b,a)
 }
 
+pub fn exe(a:&Tv,b:&Tv)->Tv {
+    let w = a.f*b.f*a.c*b.c;
+    let c = w2c(w);
+    Tv{f:1.0,c:c}
+}
+
+// analogy
+// /param a TV of the inheritance
+// /param b TV of the similarity
+pub fn ana(a:&Tv,b:&Tv)->Tv {
+    let f = a.f*b.f;
+    let c = a.c*b.c*b.f;
+    Tv{f:f,c:c}
+}
+
+// resemblance
+pub fn resem(a:&Tv,b:&Tv)->Tv {
+    let f = a.f*b.f;
+    let c = a.c*b.c*or(a.f, b.f);
+    Tv{f:f,c:c}
+}
+
 pub fn rev(a:&Tv,b:&Tv)->Tv {
     let w1:f64 = c2w(a.c);
     let w2:f64 = c2w(b.c);
@@ -59,6 +81,45 @@ pub fn int(a:&Tv,b:&Tv)->Tv {
     Tv{f:a.f*b.f,c:a.c*b.c}
 }
 
+// union
+pub fn uni(a:&Tv,b:&Tv)->Tv {
+    Tv{f:or(a.f, b.f),c:a.c*b.c}
+}
+
+// difference
+pub fn dif(a:&Tv,b:&Tv)->Tv {
+    Tv{f:a.f*(1.0-b.f),c:a.c*b.c}
+}
+
+// decomposition functions, the letters are the polarity of the simple premise, the compound premise and the conclusion
+// /param a TV of the premise about the component
+// /param b TV of the premise about the compound
+pub fn decomposePNN(a:&Tv,b:&Tv)->Tv {
+    let fn_ = a.f*(1.0-b.f);
+    Tv{f:1.0-fn_,c:fn_*a.c*b.c}
+}
+
+pub fn decomposeNPP(a:&Tv,b:&Tv)->Tv {
+    let f = (1.0-a.f)*b.f;
+    Tv{f:f,c:f*a.c*b.c}
+}
+
+pub fn decomposePNP(a:&Tv,b:&Tv)->Tv {
+    let f = a.f*(1.0-b.f);
+    Tv{f:f,c:f*a.c*b.c}
+}
+
+pub fn decomposeNNN(a:&Tv,b:&Tv)->Tv {
+    let fn_ = (1.0-a.f)*(1.0-b.f);
+    Tv{f:1.0-fn_,c:fn_*a.c*b.c}
+}
+
+pub fn contraposition(a:&Tv)->Tv {
+    let w = (1.0-a.f)*a.c;
+    let c = w2c(w);
+    Tv{f:0.0,c:c}
+}
+
 pub fn neg(a:&Tv)->Tv {
     Tv{f:1.0-a.f,c:a.c}
 }
@@ -11,11 +11,29 @@ extern crate nom;
 
 use std::sync::Arc;
 
+use nar20_1::NarConfig::{NarConfig, loadNarConfigFile};
+
+/// loads the configuration from the key=value file in the environment variable NAR_CFG
+/// uses the default configuration if the variable isn't set
+fn loadConfig() -> NarConfig {
+    match std::env::var("NAR_CFG") {
+        Ok(path) => {
+            match loadNarConfigFile(&path) {
+                Ok(cfg) => cfg,
+                Err(err) => {
+                    panic!("couldn't load configuration {}: {}", path, err);
+                }
+            }
+        },
+        Err(_) => NarConfig::default()
+    }
+}
+
 pub fn main() {
     let runEnv:String = std::env::args().nth(1).expect("no environment given");
 
     if runEnv == "it" { // run interactive
-        let mut nar = nar20_1::Nar::createNar();
+        let mut nar = nar20_1::Nar::createNarWithConfig(loadConfig());
         nar.mem.read().shared.write().procMem = Arc::clone(&nar.procNar.evidenceMem); // wire up proc memory for proc-Q&A
 
         for iFilepathIdx in 0..std::env::args().len()-2 { // iterate over paths of nars files to load
@@ -32,7 +50,7 @@ pub fn main() {
     }
     else if runEnv == "envPong3" { // run environment
         // jump to environment test
-        nar20_1::Reasoner1Entry::reasoner1Entry();
+        nar20_1::Reasoner1Entry::reasoner1Entry2(loadConfig());
     }
     else if runEnv == "envTTT2" { // run environment
         nar20_1::ProcTicTacToe::run(500);
@@ -51,8 +69,10 @@ pub fn main() {
         // TODO< compute number of runs based on statistics >
         let mut avgRatio: f64 = 0.0;
         let mut nRuns = 0;
-        for _iRun in 0..10 {
-            let runRatio:f64 = nar20_1::Reasoner1Entry::reasoner1Entry();
+        for iRun in 0..10 {
+            let mut cfg = loadConfig();
+            cfg.seed = cfg.seed.map(|seed| seed + iRun); // every run gets its own seed
+            let runRatio:f64 = nar20_1::Reasoner1Entry::reasoner1Entry2(cfg);
             avgRatio += runRatio;
             nRuns += 1;
         }
@@ -64,7 +84,7 @@ pub fn main() {
         
 
         for iFilepathIdx in 0..std::env::args().len()-2 { // iterate over paths of nars files to load
-            let mut nar = nar20_1::Nar::createNar();
+            let mut nar = nar20_1::Nar::createNarWithConfig(loadConfig());
             
             let iFilePath:String = std::env::args().nth(2+iFilepathIdx).unwrap();
             
@@ -98,7 +118,7 @@ pub fn main() {
             for iFilepathIdx in 0..std::env::args().len()-3 { // iterate over paths of nars files to load
                 cntEvals+=1;
                 
-                let mut nar = nar20_1::Nar::createNar();
+                let mut nar = nar20_1::Nar::createNarWithConfig(loadConfig());
                 
                 let iFilePath:String = std::env::args().nth(3+iFilepathIdx).unwrap();
                 
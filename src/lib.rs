@@ -14,11 +14,16 @@ pub mod Tv;
 pub mod Term;
 pub mod TermApi;
 pub mod TermUtils;
+pub mod TermIntern;
 pub mod Nar;
+pub mod NarConfig;
 pub mod NarUnify;
+pub mod NarRule;
 pub mod NarProc;
 pub mod NarGoalSystem;
 pub mod NarInfProcedural;
+pub mod NarObserver;
+pub mod NarPersist;
 
 // quality of life
 pub mod NarInteractive;